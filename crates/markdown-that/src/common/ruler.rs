@@ -84,6 +84,15 @@ impl<M: Eq + Hash + Copy + Debug, T: Clone> Ruler<M, T> {
         self.compiled.get_or_init(|| self.compile()).1.iter()
     }
 
+    /// Ordered iteration through rules, each one is returned together with its marks.
+    pub fn iter_with_marks(&self) -> impl Iterator<Item = (&[M], &T)> {
+        let (indices, values) = self.compiled.get_or_init(|| self.compile());
+        indices
+            .iter()
+            .zip(values)
+            .map(|(idx, value)| (self.deps[*idx].marks.as_slice(), value))
+    }
+
     fn compile(&self) -> (Vec<usize>, Vec<T>) {
         // ID -> [RuleItem index]
         let mut idhash = HashMap::<M, Vec<usize>>::new();
@@ -133,6 +133,148 @@ pub fn escape_html(str: &str) -> Cow<str> {
     html_escape::encode_double_quoted_attribute(str)
 }
 
+/// Escape LaTeX special characters (`# $ % & _ { } ~ ^ \`, and `< > |`
+/// that aren't available in the default font encoding).
+/// ```
+/// # use markdown_that::common::utils::escape_latex;
+/// assert_eq!(escape_latex("50% & $5"), "50\\% \\& \\$5");
+/// assert_eq!(escape_latex("a\\b"), "a\\textbackslash{}b");
+/// ```
+pub fn escape_latex(str: &str) -> Cow<'_, str> {
+    const SPECIAL: &[char] = &[
+        '#', '$', '%', '&', '_', '{', '}', '~', '^', '\\', '<', '>', '|', '\0',
+    ];
+    if !str.contains(SPECIAL) {
+        return Cow::Borrowed(str);
+    }
+
+    let mut result = String::with_capacity(str.len() + 16);
+    for ch in str.chars() {
+        match ch {
+            '#' | '$' | '%' | '&' | '_' | '{' | '}' => {
+                result.push('\\');
+                result.push(ch);
+            }
+            '~' => result.push_str("\\textasciitilde{}"),
+            '^' => result.push_str("\\textasciicircum{}"),
+            '\\' => result.push_str("\\textbackslash{}"),
+            '<' => result.push_str("\\textless{}"),
+            '>' => result.push_str("\\textgreater{}"),
+            '|' => result.push_str("\\textbar{}"),
+            '\0' => result.push('\u{FFFD}'),
+            _ => result.push(ch),
+        }
+    }
+    Cow::Owned(result)
+}
+
+/// Escape backslashes and hyphens for roff (`\` -> `\e`, `-` -> `\-`), so that
+/// hyphens in command line options are kept as is.
+///
+/// Control characters (`.` and `'`) at the start of a line must be escaped separately.
+/// ```
+/// # use markdown_that::common::utils::escape_roff;
+/// assert_eq!(escape_roff("--help"), "\\-\\-help");
+/// assert_eq!(escape_roff("a\\b"), "a\\eb");
+/// ```
+pub fn escape_roff(str: &str) -> Cow<'_, str> {
+    if !str.contains(['\\', '-', '\0']) {
+        return Cow::Borrowed(str);
+    }
+
+    let mut result = String::with_capacity(str.len() + 8);
+    for ch in str.chars() {
+        match ch {
+            '\\' => result.push_str("\\e"),
+            '-' => result.push_str("\\-"),
+            '\0' => result.push('\u{FFFD}'),
+            _ => result.push(ch),
+        }
+    }
+    Cow::Owned(result)
+}
+
+/// Escape Typst markup characters (`\ * _ ` # $ < > @ [ ] ~`), and `-` or `/`
+/// when they would start a shorthand (`--`, `-?`, `-1`) or a comment (`//`, `/*`).
+///
+/// Markers at the start of a line (headings, lists) must be escaped separately.
+/// ```
+/// # use markdown_that::common::utils::escape_typst;
+/// assert_eq!(escape_typst("#1 *bold*"), "\\#1 \\*bold\\*");
+/// assert_eq!(escape_typst("a -- b // c"), "a \\-- b \\// c");
+/// ```
+pub fn escape_typst(str: &str) -> Cow<'_, str> {
+    const SPECIAL: &[char] = &[
+        '\\', '*', '_', '`', '#', '$', '<', '>', '@', '[', ']', '~', '-', '/', '\0',
+    ];
+    if !str.contains(SPECIAL) {
+        return Cow::Borrowed(str);
+    }
+
+    let mut result = String::with_capacity(str.len() + 8);
+    let mut chars = str.chars().peekable();
+    while let Some(ch) = chars.next() {
+        let next = chars.peek().copied();
+        match ch {
+            '\\' | '*' | '_' | '`' | '#' | '$' | '<' | '>' | '@' | '[' | ']' | '~' => {
+                result.push('\\');
+                result.push(ch);
+            }
+            '-' if matches!(next, Some('-' | '?' | '0'..='9')) => result.push_str("\\-"),
+            '/' if matches!(next, Some('/' | '*')) => result.push_str("\\/"),
+            '\0' => result.push('\u{FFFD}'),
+            _ => result.push(ch),
+        }
+    }
+    Cow::Owned(result)
+}
+
+/// Escape `& < >` for Slack messages (other characters, including quotes,
+/// must be kept as is).
+/// ```
+/// # use markdown_that::common::utils::escape_slack;
+/// assert_eq!(escape_slack("<a & \"b\">"), "&lt;a &amp; \"b\"&gt;");
+/// ```
+pub fn escape_slack(str: &str) -> Cow<'_, str> {
+    if !str.contains(['&', '<', '>']) {
+        return Cow::Borrowed(str);
+    }
+
+    let mut result = String::with_capacity(str.len() + 8);
+    for ch in str.chars() {
+        match ch {
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            _ => result.push(ch),
+        }
+    }
+    Cow::Owned(result)
+}
+
+/// Escape characters used by Discord markdown (`\ * _ ~ ` | [ ] < > #`) with backslashes.
+///
+/// Markers at the start of a line (lists, quotes) must be escaped separately.
+/// ```
+/// # use markdown_that::common::utils::escape_discord;
+/// assert_eq!(escape_discord("*a* <b>"), "\\*a\\* \\<b\\>");
+/// ```
+pub fn escape_discord(str: &str) -> Cow<'_, str> {
+    const SPECIAL: &[char] = &['\\', '*', '_', '~', '`', '|', '[', ']', '<', '>', '#'];
+    if !str.contains(SPECIAL) {
+        return Cow::Borrowed(str);
+    }
+
+    let mut result = String::with_capacity(str.len() + 8);
+    for ch in str.chars() {
+        if SPECIAL.contains(&ch) {
+            result.push('\\');
+        }
+        result.push(ch);
+    }
+    Cow::Owned(result)
+}
+
 /// Unicode case folding and space normalization, used for reference labels.
 ///
 /// So that strings equal, according to the commonmark standard, are converted to
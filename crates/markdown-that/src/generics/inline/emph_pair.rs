@@ -53,6 +53,7 @@ use crate::parser::core::CoreRule;
 use crate::parser::extset::{MarkdownThatExt, NodeExt};
 use crate::parser::inline::builtin::InlineParserRule;
 use crate::parser::inline::{InlineRule, InlineState, Text};
+use crate::parser::limits::{ParseBudget, ParseError, exceeds_depth};
 use crate::{MarkdownThat, Node, NodeValue};
 
 #[derive(Debug, Default)]
@@ -203,6 +204,14 @@ fn scan_and_match_delimiters<const MARKER: char>(
 
                 let (marker_len, marker_fn) = matched_rule.unwrap();
 
+                // don't wrap content that would end up nested deeper than allowed,
+                // markers are kept as text instead
+                let limit = state.md.limits.max_nesting;
+                if exceeds_depth(&state.node.children[idx + 1..], limit - state.level) {
+                    ParseBudget::report(state.root_ext, ParseError::TooDeep { limit });
+                    break;
+                }
+
                 closer.remaining -= marker_len;
                 opener.remaining -= marker_len;
 
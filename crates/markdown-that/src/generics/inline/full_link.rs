@@ -15,10 +15,10 @@
 //!
 use std::collections::HashMap;
 
-use crate::common::utils::unescape_all;
+use crate::common::utils::{normalize_reference, unescape_all};
 use crate::parser::extset::{InlineRootExt, MarkdownThatExt};
 use crate::parser::inline::{InlineRule, InlineState};
-use crate::plugins::cmark::block::reference::ReferenceMap;
+use crate::plugins::cmark::block::reference::{ReferenceMap, UsedReferences};
 use crate::{MarkdownThat, Node};
 
 #[derive(Debug)]
@@ -159,11 +159,14 @@ fn rule_run(
 struct LinkLabelScanCache(HashMap<(usize, bool), Option<usize>>);
 impl InlineRootExt for LinkLabelScanCache {}
 
-// Parse link label
-//
-// this function assumes that the first character ("[") already matches;
-// returns the end of the label
-fn parse_link_label(state: &mut InlineState, start: usize, enable_nested: bool) -> Option<usize> {
+/// Parse link label starting at `start`, returns position of the closing `]`.
+///
+/// This function assumes that the first character (`[`) already matches.
+pub fn parse_link_label(
+    state: &mut InlineState,
+    start: usize,
+    enable_nested: bool,
+) -> Option<usize> {
     let cache = state
         .inline_ext
         .get_or_insert_default::<LinkLabelScanCache>();
@@ -351,6 +354,60 @@ pub fn parse_link_title(str: &str, start: usize, max: usize) -> Option<ParseLink
     }
 }
 
+/// Helper function used to write `<href>` part of the links, reverse of [parse_link_destination].
+pub fn format_link_destination(url: &str) -> String {
+    let mut level: i32 = 0;
+    let mut balanced = true;
+    for ch in url.chars() {
+        match ch {
+            '(' => level += 1,
+            ')' => level -= 1,
+            _ => (),
+        }
+        if !(0..=32).contains(&level) {
+            balanced = false;
+        }
+    }
+
+    let needs_brackets = url.is_empty()
+        || level != 0
+        || !balanced
+        || url.contains(|ch: char| matches!(ch, '\0'..=' ' | '\x7f' | '<' | '>'));
+
+    if needs_brackets {
+        format!("<{}>", escape_link_fragment(url, &['<', '>']))
+    } else {
+        escape_link_fragment(url, &[])
+    }
+}
+
+/// Helper function used to write `"title"` part of the links, reverse of [parse_link_title].
+pub fn format_link_title(title: &str) -> String {
+    format!("\"{}\"", escape_link_fragment(title, &['"']))
+}
+
+// Escape backslashes, entity-like sequences and given characters,
+// so that `unescape_all` returns the original string.
+fn escape_link_fragment(str: &str, special: &[char]) -> String {
+    let mut result = String::with_capacity(str.len());
+    for (pos, ch) in str.char_indices() {
+        let escape = match ch {
+            '\\' => true,
+            '&' => {
+                let rest = &str[pos + 1..];
+                let len = rest.find(|ch: char| !ch.is_ascii_alphanumeric() && ch != '#');
+                matches!(len, Some(len) if len > 0 && rest[len..].starts_with(';'))
+            }
+            ch => special.contains(&ch),
+        };
+        if escape {
+            result.push('\\');
+        }
+        result.push(ch);
+    }
+    result
+}
+
 struct ParseLinkResult {
     pub label_start: usize,
     pub label_end: usize,
@@ -458,12 +515,20 @@ fn parse_link(state: &mut InlineState, pos: usize, enable_nested: bool) -> Optio
     };
 
     let (destination, title) = references.get(label)?;
+    let href = Some(destination.to_owned());
+    let title = title.map(|s| s.to_owned());
+
+    if state.root_ext.contains::<UsedReferences>() {
+        let label = normalize_reference(label);
+        let used = state.root_ext.get_mut::<UsedReferences>().unwrap();
+        used.0.insert(label);
+    }
 
     Some(ParseLinkResult {
         label_start,
         label_end,
-        href: Some(destination.to_owned()),
-        title: title.map(|s| s.to_owned()),
+        href,
+        title,
         end: pos,
     })
 }
@@ -11,7 +11,8 @@ pub mod builtin;
 use crate::common::TypeKey;
 use crate::common::ruler::Ruler;
 use crate::parser::extset::RootExtSet;
-use crate::parser::inline::InlineRoot;
+use crate::parser::inline::{InlineRoot, Text};
+use crate::parser::limits::{ParseBudget, ParseError, exceeds_depth};
 use crate::parser::node::NodeEmpty;
 use crate::{MarkdownThat, Node};
 
@@ -34,12 +35,19 @@ impl BlockParser {
     /// Generate tokens for input range
     ///
     pub fn tokenize(&self, state: &mut BlockState) {
+        self.tokenize_until(state, |_| false);
+    }
+
+    /// Same as [tokenize](BlockParser::tokenize), but stops before a block
+    /// that starts at a line for which `stop` returns true.
+    pub(crate) fn tokenize_until(&self, state: &mut BlockState, stop: impl Fn(usize) -> bool) {
+        state.level += 1;
         stacker::maybe_grow(64 * 1024, 1024 * 1024, || {
             let mut has_empty_lines = false;
 
             while state.line < state.line_max {
                 state.line = state.skip_empty_lines(state.line);
-                if state.line >= state.line_max {
+                if state.line >= state.line_max || stop(state.line) {
                     break;
                 }
 
@@ -49,10 +57,18 @@ impl BlockParser {
                     break;
                 }
 
-                // If nesting level exceeded - skip tail to the end. That's not an ordinary
-                // situation, and we should not care about content.
-                if state.level >= state.md.max_nesting {
-                    state.line = state.line_max;
+                // If nesting level is exceeded, keep the block as plain text,
+                // one paragraph at a time, so content after the container is parsed as usual.
+                let limit = state.md.limits.max_nesting;
+                if state.level > limit {
+                    ParseBudget::report(state.root_ext, ParseError::TooDeep { limit });
+                    let end = Self::paragraph_end(state, &stop);
+                    Self::push_lines_as_text(state, end);
+                    continue;
+                }
+                // If parse is out of budget, keep the rest of the content as plain text.
+                if !ParseBudget::check(state.root_ext, 0) {
+                    Self::push_rest_as_text(state);
                     break;
                 }
 
@@ -74,6 +90,20 @@ impl BlockParser {
                 if let Some((mut node, len)) = ok {
                     state.line += len;
                     if !node.is::<NodeEmpty>() {
+                        if exceeds_depth(&node.children, limit - state.level) {
+                            // node is nested too deep, keep its content as plain text
+                            ParseBudget::report(state.root_ext, ParseError::TooDeep { limit });
+                            let start = state.line - len;
+                            let (content, _) =
+                                state.get_lines(start, state.line, state.blk_indent, false);
+                            node = Node::new(Text { content });
+                        }
+                        if !ParseBudget::check(state.root_ext, 1) {
+                            // out of budget, keep this block and the rest as plain text
+                            state.line -= len;
+                            Self::push_rest_as_text(state);
+                            break;
+                        }
                         node.srcmap = state.get_map(state.line - len, state.line - 1);
                         state.node.children.push(node);
                     }
@@ -106,6 +136,42 @@ impl BlockParser {
                 }
             }
         });
+        state.level -= 1;
+    }
+
+    // end of a paragraph starting at the current line, same as the paragraph rule finds it
+    fn paragraph_end(state: &mut BlockState, stop: &impl Fn(usize) -> bool) -> usize {
+        let start_line = state.line;
+        let mut next_line = start_line + 1;
+
+        while next_line < state.line_max && !state.is_empty(next_line) && !stop(next_line) {
+            // lazy continuation, or a quirk for blockquotes
+            if state.line_indent(next_line) < state.md.max_indent
+                && state.line_offsets[next_line].indent_nonspace >= 0
+            {
+                state.line = next_line;
+                let terminated = state.test_rules_at_line();
+                state.line = start_line;
+                if terminated {
+                    break;
+                }
+            }
+            next_line += 1;
+        }
+
+        next_line
+    }
+
+    fn push_rest_as_text(state: &mut BlockState) {
+        Self::push_lines_as_text(state, state.line_max);
+    }
+
+    fn push_lines_as_text(state: &mut BlockState, end: usize) {
+        let (content, _) = state.get_lines(state.line, end, state.blk_indent, false);
+        let mut node = Node::new(Text { content });
+        node.srcmap = state.get_map(state.line, end - 1);
+        state.node.children.push(node);
+        state.line = end;
     }
 
     /// Process input string and push block tokens into `out_tokens`
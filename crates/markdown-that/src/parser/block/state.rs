@@ -44,6 +44,8 @@ where
     /// indent of the current list block.
     pub list_indent: Option<u32>,
 
+    /// Depth of nodes being added to the tree (root is 0), used to enforce
+    /// [max_nesting](crate::parser::limits::ParseLimits::max_nesting).
     pub level: u32,
 }
 
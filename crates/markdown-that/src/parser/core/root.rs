@@ -1,10 +1,13 @@
 use crate::parser::extset::RootExtSet;
+use crate::parser::renderer::MarkdownRenderer;
 use crate::{Node, NodeValue, Renderer};
 
 #[derive(Debug)]
 /// Root node of the AST.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Root {
     pub content: String,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub ext: RootExtSet,
 }
 
@@ -21,4 +24,11 @@ impl NodeValue for Root {
     fn render(&self, node: &Node, fmt: &mut dyn Renderer) {
         fmt.contents(&node.children);
     }
+
+    fn render_markdown(&self, node: &Node, fmt: &mut MarkdownRenderer) {
+        match fmt.format_options() {
+            Some(_) => fmt.format_contents(&node.children),
+            None => fmt.contents(&node.children),
+        }
+    }
 }
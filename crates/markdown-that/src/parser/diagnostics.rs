@@ -0,0 +1,99 @@
+//! Problems found in the source while parsing and linting.
+//!
+//! Markdown has no syntax errors, any input is a valid document, but some of it
+//! is likely a mistake (e.g. a link reference defined twice). Rules report these
+//! by pushing a [Diagnostic] to [Diagnostics] stored in root data, and
+//! [lint](crate::plugins::lint) plugin adds more checks on top of the final tree.
+//!
+//! ```rust
+//! use markdown_that::parser::diagnostics::{Diagnostics, Severity};
+//!
+//! let md = &mut markdown_that::MarkdownThat::new();
+//! markdown_that::plugins::cmark::add(md);
+//!
+//! let ast = md.parse("[foo]\n\n[foo]: /a\n[foo]: /b\n");
+//! let diagnostics = Diagnostics::of(&ast);
+//! assert_eq!(diagnostics.len(), 1);
+//! assert_eq!(diagnostics[0].severity, Severity::Warning);
+//! assert_eq!(diagnostics[0].code, "MD053");
+//! assert_eq!(diagnostics[0].srcmap.unwrap().get_byte_offsets(), (17, 26));
+//! ```
+use std::fmt;
+
+use crate::Node;
+use crate::common::sourcemap::SourcePos;
+use crate::parser::core::Root;
+use crate::parser::extset::{RootExt, RootExtSet};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Info => "info",
+            Self::Warning => "warning",
+            Self::Error => "error",
+        })
+    }
+}
+
+/// A single problem found in the source.
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// Human-readable description of the problem.
+    pub message: String,
+    /// Location of the problem in the source, if known.
+    pub srcmap: Option<SourcePos>,
+    /// Identifier of the check, markdownlint rule names are used
+    /// for the built-in ones (e.g. `MD001`).
+    pub code: &'static str,
+}
+
+impl Diagnostic {
+    pub fn new(
+        severity: Severity,
+        code: &'static str,
+        message: impl Into<String>,
+        srcmap: Option<SourcePos>,
+    ) -> Self {
+        Self {
+            severity,
+            message: message.into(),
+            srcmap,
+            code,
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}[{}]: {}", self.severity, self.code, self.message)
+    }
+}
+
+/// All diagnostics for a document, stored in root data.
+#[derive(Debug, Default)]
+pub struct Diagnostics(pub Vec<Diagnostic>);
+
+impl RootExt for Diagnostics {}
+
+impl Diagnostics {
+    /// Report a problem, this is meant to be called from rules.
+    pub fn push(root_ext: &mut RootExtSet, diagnostic: Diagnostic) {
+        root_ext.get_or_insert_default::<Self>().0.push(diagnostic);
+    }
+
+    /// Get diagnostics collected while parsing `root`.
+    pub fn of(root: &Node) -> &[Diagnostic] {
+        root.cast::<Root>()
+            .and_then(|root| root.ext.get::<Self>())
+            .map_or(&[], |diagnostics| diagnostics.0.as_slice())
+    }
+}
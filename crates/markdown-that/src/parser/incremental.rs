@@ -0,0 +1,243 @@
+//! Incremental reparsing, allows editors to update the AST after a text change
+//! without parsing the whole document again.
+//!
+//! ```rust
+//! use markdown_that::parser::incremental::TextEdit;
+//!
+//! let md = &mut markdown_that::MarkdownThat::new();
+//! markdown_that::plugins::cmark::add(md);
+//!
+//! let ast = md.parse("# title\n\nfoo\n\nbar\n");
+//! let ast = md.reparse(ast, &TextEdit::new(12..12, "d"));
+//! assert_eq!(ast.render(), "<h1>title</h1>\n<p>food</p>\n<p>bar</p>\n");
+//! ```
+use std::collections::HashMap;
+use std::ops::Range;
+
+use crate::common::TypeKey;
+use crate::common::sourcemap::{SourcePos, SourceWithLineStarts};
+use crate::parser::block::BlockState;
+use crate::parser::block::builtin::BlockParserRule;
+use crate::parser::core::Root;
+use crate::parser::diagnostics::Diagnostics;
+use crate::parser::extset::RootExtSet;
+use crate::parser::limits::ParseBudget;
+use crate::plugins::sourcepos::{SyntaxPosRule, set_sourcepos};
+use crate::{MarkdownThat, Node};
+
+/// Change of the source text: bytes in `range` are replaced with `text`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextEdit {
+    /// Byte range in the old source.
+    pub range: Range<usize>,
+    /// Text inserted instead of that range.
+    pub text: String,
+}
+
+impl TextEdit {
+    pub fn new(range: Range<usize>, text: impl Into<String>) -> Self {
+        Self {
+            range,
+            text: text.into(),
+        }
+    }
+
+    // position in the new source of a byte located after the edit in the old source
+    fn shift(&self, pos: usize) -> usize {
+        pos - self.range.end + self.range.start + self.text.len()
+    }
+}
+
+impl MarkdownThat {
+    /// Apply `edit` to the source of a document returned by [parse](MarkdownThat::parse),
+    /// and update its AST, so result is the same as parsing the new source from scratch.
+    ///
+    /// Only top-level blocks affected by the edit are parsed again: blocks before it
+    /// are kept as is, and blocks after it are kept with their source positions shifted.
+    /// Core rules (other than block parser) are only executed on the reparsed blocks.
+    ///
+    /// Link reference definitions are global to the document, so if any of them is
+    /// added, removed or changed, the whole document is parsed again. The same happens
+    /// if a top-level node has no source map, the new source is larger than
+    /// [max_source_bytes](crate::parser::limits::ParseLimits::max_source_bytes),
+    /// or a core rule that looks at the whole document is enabled (see
+    /// [mark_document_rule](MarkdownThat::mark_document_rule), e.g. [lint](crate::plugins::lint)
+    /// checks or [footnotes](crate::plugins::extra::footnotes)).
+    /// Other [limits](MarkdownThat::limits) apply to the reparsed blocks.
+    ///
+    /// # Panics
+    ///
+    /// If `tree` isn't a [Root] node, or the range of `edit` is out of bounds
+    /// or doesn't lie on char boundaries.
+    pub fn reparse(&self, mut tree: Node, edit: &TextEdit) -> Node {
+        let root = tree
+            .cast_mut::<Root>()
+            .expect("reparse requires a tree returned by parse");
+        let old_source = std::mem::take(&mut root.content);
+        let mut ext = std::mem::take(&mut root.ext);
+        let mut source = old_source.clone();
+        source.replace_range(edit.range.clone(), &edit.text);
+
+        if self
+            .limits
+            .max_source_bytes
+            .is_some_and(|limit| source.len() > limit)
+            || self.has_document_rules()
+        {
+            return self.parse(&source);
+        }
+        ext.insert(ParseBudget::new(&self.limits));
+        self.store_render_options(&mut ext);
+        // diagnostics for the reparsed blocks are reported again
+        let old_diagnostics = ext.remove::<Diagnostics>();
+
+        let Some((replaced, old_range, blocks)) =
+            self.reparse_blocks(&tree.children, &old_source, &source, edit, &mut ext)
+        else {
+            return self.parse(&source);
+        };
+
+        // run core rules on the new blocks, they see the whole new source and root data
+        let len = source.len();
+        let mut root = Node::new(Root {
+            content: source,
+            ext,
+        });
+        root.srcmap = Some(SourcePos::new(0, len));
+        root.children = blocks;
+        for (marks, rule) in self.core_rules() {
+            if !marks.contains(&TypeKey::of::<BlockParserRule>()) {
+                rule(&mut root, self);
+            }
+        }
+
+        let mut tail = tree.children.split_off(replaced.end);
+        for node in tail.iter_mut() {
+            node.walk_mut(|node, _| {
+                if let Some(map) = node.srcmap {
+                    let (start, end) = map.get_byte_offsets();
+                    node.srcmap = Some(SourcePos::new(edit.shift(start), edit.shift(end)));
+                }
+            });
+        }
+
+        let tail_len = tail.len();
+        tree.children.truncate(replaced.start);
+        tree.children.append(&mut root.children);
+        tree.children.append(&mut tail);
+        tree.srcmap = root.srcmap;
+
+        let data = root.cast_mut::<Root>().unwrap();
+        let content = std::mem::take(&mut data.content);
+        let mut ext = std::mem::take(&mut data.ext);
+        ext.remove::<ParseBudget>();
+
+        if let Some(Diagnostics(old)) = old_diagnostics {
+            let diagnostics = ext.get_or_insert_default::<Diagnostics>();
+            for mut diagnostic in old {
+                if let Some(map) = diagnostic.srcmap {
+                    let (start, end) = map.get_byte_offsets();
+                    if start >= old_range.end {
+                        diagnostic.srcmap =
+                            Some(SourcePos::new(edit.shift(start), edit.shift(end)));
+                    } else if start >= old_range.start {
+                        continue;
+                    }
+                }
+                diagnostics.0.push(diagnostic);
+            }
+            diagnostics
+                .0
+                .sort_by_key(|diagnostic| diagnostic.srcmap.map(|map| map.get_byte_offsets().0));
+        }
+
+        let sourcepos = TypeKey::of::<SyntaxPosRule>();
+        if self
+            .core_rules()
+            .any(|(marks, _)| marks.contains(&sourcepos))
+        {
+            // line numbers of the blocks after the edit might have changed
+            let mapping = SourceWithLineStarts::new(&content);
+            set_sourcepos(&mut tree, &mapping, 0);
+            let tail_start = tree.children.len() - tail_len;
+            for node in tree.children[tail_start..].iter_mut() {
+                node.walk_mut(|node, _| set_sourcepos(node, &mapping, 0));
+            }
+        }
+
+        let data = tree.cast_mut::<Root>().unwrap();
+        data.content = content;
+        data.ext = ext;
+        tree
+    }
+
+    // Parse top-level blocks affected by the edit, returns range of the old blocks
+    // they replace and its byte range in the old source, or `None` if the whole
+    // document must be parsed again.
+    fn reparse_blocks(
+        &self,
+        old_blocks: &[Node],
+        old_source: &str,
+        source: &str,
+        edit: &TextEdit,
+        ext: &mut RootExtSet,
+    ) -> Option<(Range<usize>, Range<usize>, Vec<Node>)> {
+        // offsets of the lines where old blocks start
+        let mut starts = Vec::with_capacity(old_blocks.len());
+        for node in old_blocks {
+            let (start, _) = node.srcmap?.get_byte_offsets();
+            starts.push(
+                old_source[..start]
+                    .rfind(['\n', '\r'])
+                    .map_or(0, |pos| pos + 1),
+            );
+        }
+
+        // start from the block before the edited one, because the edit
+        // may make it continue into the next one (e.g. lazy paragraph continuation)
+        let first = starts
+            .partition_point(|start| *start < edit.range.start)
+            .saturating_sub(2);
+        let start = if first == 0 { 0 } else { starts[first] };
+
+        let mut state = BlockState::new(source, self, ext, Node::new(Root::new(String::new())));
+        state.line = state
+            .line_offsets
+            .binary_search_by_key(&start, |offsets| offsets.line_start)
+            .ok()?;
+
+        // if a new block starts on the same line as an old block after the edit,
+        // the rest of the document is parsed in the same way as before
+        let mut sync_lines = HashMap::new();
+        let after_edit = starts.partition_point(|start| *start < edit.range.end);
+        for (idx, old_start) in starts.iter().enumerate().skip(after_edit) {
+            let new_start = edit.shift(*old_start);
+            if let Ok(line) = state
+                .line_offsets
+                .binary_search_by_key(&new_start, |offsets| offsets.line_start)
+            {
+                sync_lines.insert(line, idx);
+            }
+        }
+
+        self.block
+            .tokenize_until(&mut state, |line| sync_lines.contains_key(&line));
+
+        let resume = sync_lines
+            .get(&state.line)
+            .copied()
+            .unwrap_or(old_blocks.len());
+        let blocks = std::mem::take(&mut state.node.children);
+
+        let (old_end, new_end) = match starts.get(resume) {
+            Some(old_start) => (*old_start, edit.shift(*old_start)),
+            None => (old_source.len(), source.len()),
+        };
+        if old_source[start..old_end].contains("]:") || source[start..new_end].contains("]:") {
+            // reference definitions might be added or removed
+            return None;
+        }
+
+        Some((first..resume, start..old_end, blocks))
+    }
+}
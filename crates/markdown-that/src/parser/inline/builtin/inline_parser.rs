@@ -1,6 +1,7 @@
 use crate::parser::block::builtin::BlockParserRule;
 use crate::parser::core::{CoreRule, Root};
 use crate::parser::extset::{InlineRootExtSet, RootExtSet};
+use crate::parser::inline::InlineState;
 use crate::{MarkdownThat, Node, NodeValue};
 
 #[derive(Debug)]
@@ -34,7 +35,13 @@ pub fn add(md: &mut MarkdownThat) {
 pub struct InlineParserRule;
 impl CoreRule for InlineParserRule {
     fn run(root: &mut Node, md: &MarkdownThat) {
-        fn walk_recursive(node: &mut Node, md: &MarkdownThat, root_ext: &mut RootExtSet) {
+        // `depth` is the depth of `node` in the tree, used to enforce max_nesting
+        fn walk_recursive(
+            node: &mut Node,
+            depth: u32,
+            md: &MarkdownThat,
+            root_ext: &mut RootExtSet,
+        ) {
             let mut idx = 0;
             while idx < node.children.len() {
                 let child = &mut node.children[idx];
@@ -46,9 +53,13 @@ impl CoreRule for InlineParserRule {
                     let mut root = std::mem::take(child);
                     root.ext = std::mem::take(&mut node.ext);
                     root.children = Vec::new();
-                    root = md
-                        .inline
-                        .parse(content, mapping, root, md, root_ext, &mut inline_ext);
+
+                    // inline nodes replace InlineRoot, so they are children of `node`
+                    let mut state =
+                        InlineState::new(content, mapping, md, root_ext, &mut inline_ext, root);
+                    state.level = depth;
+                    md.inline.tokenize(&mut state);
+                    let mut root = state.node;
 
                     let len = root.children.len();
                     node.children
@@ -57,7 +68,7 @@ impl CoreRule for InlineParserRule {
                     idx += len;
                 } else {
                     stacker::maybe_grow(64 * 1024, 1024 * 1024, || {
-                        walk_recursive(child, md, root_ext);
+                        walk_recursive(child, depth + 1, md, root_ext);
                     });
                     idx += 1;
                 }
@@ -79,7 +90,7 @@ impl CoreRule for InlineParserRule {
             root.children.push(node);
         }*/
 
-        walk_recursive(root, md, &mut root_ext);
+        walk_recursive(root, 0, md, &mut root_ext);
 
         let data = root.cast_mut::<Root>().unwrap();
         data.ext = root_ext;
@@ -4,10 +4,12 @@
 use regex::{self, Regex};
 
 use crate::parser::inline::{InlineRule, InlineState};
+use crate::parser::renderer::{AnsiRenderer, MarkdownRenderer, TextRenderer};
 use crate::{MarkdownThat, Node, NodeValue, Renderer};
 
 #[derive(Debug)]
 /// Plain text AST node.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Text {
     pub content: String,
 }
@@ -16,6 +18,25 @@ impl NodeValue for Text {
     fn render(&self, _: &Node, fmt: &mut dyn Renderer) {
         fmt.text(&self.content);
     }
+
+    fn render_markdown(&self, node: &Node, fmt: &mut MarkdownRenderer) {
+        // text taken from the source as is was plain text there, so it only needs
+        // escaping if it's moved to the start of a line; anything else (modified
+        // by plugins or user) has to be escaped fully
+        if fmt.source_of(node) == Some(self.content.as_str()) {
+            fmt.text_verbatim(&self.content);
+        } else {
+            fmt.text(&self.content);
+        }
+    }
+
+    fn render_text(&self, _: &Node, fmt: &mut TextRenderer) {
+        fmt.text(&self.content);
+    }
+
+    fn render_ansi(&self, _: &Node, fmt: &mut AnsiRenderer) {
+        fmt.text(&self.content);
+    }
 }
 
 #[derive(Debug)]
@@ -30,6 +51,18 @@ impl NodeValue for TextSpecial {
     fn render(&self, _: &Node, fmt: &mut dyn Renderer) {
         fmt.text(&self.content);
     }
+
+    fn render_markdown(&self, _: &Node, fmt: &mut MarkdownRenderer) {
+        fmt.text_raw(&self.markup);
+    }
+
+    fn render_text(&self, _: &Node, fmt: &mut TextRenderer) {
+        fmt.text(&self.content);
+    }
+
+    fn render_ansi(&self, _: &Node, fmt: &mut AnsiRenderer) {
+        fmt.text(&self.content);
+    }
 }
 
 pub fn add(md: &mut MarkdownThat) {
@@ -19,6 +19,7 @@ pub use builtin::skip_text::{Text, TextSpecial};
 use crate::common::TypeKey;
 use crate::common::ruler::Ruler;
 use crate::parser::extset::{InlineRootExtSet, RootExtSet};
+use crate::parser::limits::{ParseBudget, ParseError, exceeds_depth};
 use crate::{MarkdownThat, Node};
 
 use super::node::NodeEmpty;
@@ -48,7 +49,7 @@ impl InlineParser {
         stacker::maybe_grow(64 * 1024, 1024 * 1024, || {
             let mut ok = None;
 
-            if state.level < state.md.max_nesting {
+            if state.level < state.md.limits.max_nesting {
                 for rule in self.ruler.iter() {
                     ok = rule.0(state);
                     if ok.is_some() {
@@ -67,7 +68,7 @@ impl InlineParser {
                 //       (we can replace it by preventing links from being parsed in
                 //       validation mode)
                 //
-                state.pos = state.pos_max;
+                ok = Some(state.pos_max - state.pos);
             }
 
             if let Some(len) = ok {
@@ -82,9 +83,22 @@ impl InlineParser {
     /// Generate tokens for input range
     ///
     pub fn tokenize(&self, state: &mut InlineState) {
+        state.level += 1;
+        let limit = state.md.limits.max_nesting;
+        if state.level > limit {
+            // too deep, so the content is kept as plain text
+            ParseBudget::report(state.root_ext, ParseError::TooDeep { limit });
+        }
+
         stacker::maybe_grow(64 * 1024, 1024 * 1024, || {
             let end = state.pos_max;
 
+            if state.pos < end && !ParseBudget::check(state.root_ext, 0) {
+                // out of budget, keep the content as plain text
+                state.trailing_text_push(state.pos, end);
+                state.pos = end;
+            }
+
             while state.pos < end {
                 // Try all possible rules.
                 // On success, the rule should:
@@ -94,7 +108,7 @@ impl InlineParser {
                 // - return true
                 let mut ok = None;
 
-                if state.level < state.md.max_nesting {
+                if state.level <= limit {
                     for rule in self.ruler.iter() {
                         ok = rule.1(state);
                         if ok.is_some() {
@@ -106,6 +120,18 @@ impl InlineParser {
                 if let Some((mut node, len)) = ok {
                     state.pos += len;
                     if !node.is::<NodeEmpty>() {
+                        if exceeds_depth(&node.children, limit - state.level) {
+                            // node is nested too deep, keep its source as plain text
+                            ParseBudget::report(state.root_ext, ParseError::TooDeep { limit });
+                            state.trailing_text_push(state.pos - len, state.pos);
+                            continue;
+                        }
+                        if !ParseBudget::check(state.root_ext, 1) {
+                            // out of budget, keep this node and the rest as plain text
+                            state.trailing_text_push(state.pos - len, end);
+                            state.pos = end;
+                            break;
+                        }
                         node.srcmap = state.get_map(state.pos - len, state.pos);
                         state.node.children.push(node);
                         if state.pos >= end {
@@ -121,6 +147,7 @@ impl InlineParser {
                 state.pos += len;
             }
         });
+        state.level -= 1;
     }
 
     /// Process input string and push inline tokens into `out_tokens`
@@ -56,7 +56,8 @@ where
     /// inside raw HTML and Markdown links.
     pub link_level: i32,
 
-    /// Counter used to prevent recursion by image and link rules.
+    /// Depth of nodes being added to the tree (root is 0), used to enforce
+    /// [max_nesting](crate::parser::limits::ParseLimits::max_nesting).
     pub level: u32,
 }
 
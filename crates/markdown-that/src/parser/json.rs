@@ -0,0 +1,326 @@
+//! JSON export and import of the AST (requires `serde` feature).
+//!
+//! Every node is represented as an object with its type name, source map (byte offsets),
+//! attributes, fields of the node value and children:
+//!
+//! ```json
+//! {
+//!   "type": "Link",
+//!   "srcmap": [0, 23],
+//!   "value": { "url": "https://example.org", "title": null },
+//!   "children": [ ... ]
+//! }
+//! ```
+//!
+//! Node values need to be registered in [NodeRegistry] under a name that stays the same
+//! between versions, all built-in nodes are registered by default. Custom nodes opt in
+//! by implementing `Serialize` and `Deserialize`:
+//!
+//! ```rust
+//! use markdown_that::parser::json::NodeRegistry;
+//! use markdown_that::{Node, NodeValue, Renderer};
+//!
+//! #[derive(Debug, serde::Serialize, serde::Deserialize)]
+//! struct Spoiler { title: String }
+//!
+//! impl NodeValue for Spoiler {
+//!     fn render(&self, node: &Node, fmt: &mut dyn Renderer) {
+//!         fmt.open("details", &node.attrs);
+//!         fmt.contents(&node.children);
+//!         fmt.close("details");
+//!     }
+//! }
+//!
+//! let mut registry = NodeRegistry::new();
+//! registry.add::<Spoiler>("Spoiler");
+//!
+//! let node = Node::new(Spoiler { title: "hidden".into() });
+//! let json = registry.to_json(&node).unwrap();
+//! let node = registry.from_json(&json).unwrap();
+//! assert_eq!(node.cast::<Spoiler>().unwrap().title, "hidden");
+//! ```
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+
+use crate::common::TypeKey;
+use crate::common::sourcemap::SourcePos;
+use crate::parser::inline::TextSpecial;
+use crate::{Node, NodeValue};
+
+type ToValueFn = fn(&Node) -> serde_json::Result<Value>;
+type FromValueFn = fn(Value) -> serde_json::Result<Node>;
+
+#[derive(Debug, Clone, Copy)]
+struct Entry {
+    to_value: ToValueFn,
+    from_value: FromValueFn,
+}
+
+/// Set of node types that can be converted to and from JSON,
+/// node types are identified by the name they are registered with.
+///
+/// Attribute names are stored as `&'static str` in [Node::attrs], so only the
+/// names known to the registry are accepted on import, see [add_attr](NodeRegistry::add_attr).
+#[derive(Debug, Clone)]
+pub struct NodeRegistry {
+    by_key: HashMap<TypeKey, (&'static str, Entry)>,
+    by_name: HashMap<&'static str, Entry>,
+    attr_names: HashSet<&'static str>,
+}
+
+// attributes set by built-in plugins and commonly used HTML ones
+const BUILTIN_ATTRS: &[&str] = &[
+    "align",
+    "alt",
+    "checked",
+    "class",
+    "data-sourcepos",
+    "dir",
+    "disabled",
+    "height",
+    "href",
+    "id",
+    "lang",
+    "name",
+    "rel",
+    "src",
+    "start",
+    "style",
+    "target",
+    "title",
+    "type",
+    "width",
+];
+
+impl NodeRegistry {
+    /// Create a registry with all built-in nodes.
+    pub fn new() -> Self {
+        let mut registry = Self::empty();
+        registry.add_builtins();
+        registry
+    }
+
+    /// Create a registry without any nodes.
+    pub fn empty() -> Self {
+        Self {
+            by_key: HashMap::new(),
+            by_name: HashMap::new(),
+            attr_names: BUILTIN_ATTRS.iter().copied().collect(),
+        }
+    }
+
+    /// Register a node type under `name`, so it can be exported and imported.
+    ///
+    /// The name is written into JSON as node type, so it shouldn't change
+    /// once documents are stored.
+    pub fn add<T: NodeValue + Serialize + DeserializeOwned>(&mut self, name: &'static str) {
+        let entry = Entry {
+            to_value: |node| serde_json::to_value(node.cast::<T>().unwrap()),
+            from_value: |value| Ok(Node::new(serde_json::from_value::<T>(value)?)),
+        };
+        self.remove::<T>();
+        self.by_key.insert(TypeKey::of::<T>(), (name, entry));
+        self.by_name.insert(name, entry);
+    }
+
+    /// Remove a node type from the registry.
+    pub fn remove<T: NodeValue>(&mut self) {
+        if let Some((name, _)) = self.by_key.remove(&TypeKey::of::<T>()) {
+            self.by_name.remove(name);
+        }
+    }
+
+    /// Allow attribute `name` on imported nodes, attributes that aren't
+    /// known to the registry are rejected.
+    pub fn add_attr(&mut self, name: &'static str) {
+        self.attr_names.insert(name);
+    }
+
+    /// Check whether a node type is registered.
+    pub fn has<T: NodeValue>(&self) -> bool {
+        self.by_key.contains_key(&TypeKey::of::<T>())
+    }
+
+    /// Convert AST into JSON value, fails if any node in the tree isn't registered.
+    pub fn to_json(&self, node: &Node) -> serde_json::Result<Value> {
+        serde_json::to_value(self.export(node)?)
+    }
+
+    /// Convert JSON value (made by [to_json](NodeRegistry::to_json)) back into AST.
+    pub fn from_json(&self, json: &Value) -> serde_json::Result<Node> {
+        let json = JsonNode::deserialize(json)?;
+        self.import(json)
+    }
+
+    fn export(&self, node: &Node) -> serde_json::Result<JsonNode> {
+        let (name, entry) = self.by_key.get(&node.node_type).ok_or_else(|| {
+            serde::ser::Error::custom(format!("node type {} is not registered", node.name()))
+        })?;
+
+        let mut children = Vec::with_capacity(node.children.len());
+        for child in node.children.iter() {
+            stacker::maybe_grow(64 * 1024, 1024 * 1024, || {
+                children.push(self.export(child)?);
+                serde_json::Result::Ok(())
+            })?;
+        }
+
+        Ok(JsonNode {
+            node_type: (*name).to_owned(),
+            srcmap: node.srcmap.map(|srcmap| srcmap.get_byte_offsets()),
+            attrs: node
+                .attrs
+                .iter()
+                .map(|(name, value)| ((*name).to_owned(), value.clone()))
+                .collect(),
+            value: (entry.to_value)(node)?,
+            children,
+        })
+    }
+
+    fn import(&self, json: JsonNode) -> serde_json::Result<Node> {
+        let entry = self.by_name.get(json.node_type.as_str()).ok_or_else(|| {
+            serde::de::Error::custom(format!("node type {} is not registered", json.node_type))
+        })?;
+
+        let mut node = (entry.from_value)(json.value)?;
+        node.srcmap = json.srcmap.map(|(start, end)| SourcePos::new(start, end));
+        node.attrs.reserve_exact(json.attrs.len());
+        for (name, value) in json.attrs {
+            let name = self.attr_names.get(name.as_str()).ok_or_else(|| {
+                serde::de::Error::custom(format!("attribute {name} is not registered"))
+            })?;
+            node.attrs.push((name, value));
+        }
+
+        node.children.reserve_exact(json.children.len());
+        for child in json.children {
+            stacker::maybe_grow(64 * 1024, 1024 * 1024, || {
+                node.children.push(self.import(child)?);
+                serde_json::Result::Ok(())
+            })?;
+        }
+
+        Ok(node)
+    }
+
+    fn add_builtins(&mut self) {
+        use crate::parser::core::Root;
+        use crate::parser::inline::Text;
+        use crate::plugins::cmark::block::{
+            blockquote, code, fence, heading, hr, lheading, list, paragraph, reference,
+        };
+        use crate::plugins::cmark::inline::{autolink, backticks, emphasis, image, link, newline};
+        use crate::plugins::extra::{footnotes, strikethrough, tables};
+        use crate::plugins::gfm::tasklist;
+        use crate::plugins::html::{html_block, html_inline};
+
+        self.add::<Root>("Root");
+        self.add::<Text>("Text");
+        self.add::<TextSpecial>("TextSpecial");
+
+        self.add::<blockquote::Blockquote>("Blockquote");
+        self.add::<code::CodeBlock>("CodeBlock");
+        self.add::<fence::CodeFence>("CodeFence");
+        self.add::<heading::ATXHeading>("ATXHeading");
+        self.add::<hr::ThematicBreak>("ThematicBreak");
+        self.add::<lheading::SetextHeader>("SetextHeader");
+        self.add::<list::BulletList>("BulletList");
+        self.add::<list::OrderedList>("OrderedList");
+        self.add::<list::ListItem>("ListItem");
+        self.add::<paragraph::Paragraph>("Paragraph");
+        self.add::<reference::Definition>("Definition");
+
+        self.add::<autolink::Autolink>("Autolink");
+        self.add::<backticks::CodeInline>("CodeInline");
+        self.add::<emphasis::Em>("Em");
+        self.add::<emphasis::Strong>("Strong");
+        self.add::<image::Image>("Image");
+        self.add::<link::Link>("Link");
+        self.add::<newline::Hardbreak>("Hardbreak");
+        self.add::<newline::Softbreak>("Softbreak");
+
+        self.add::<html_block::HtmlBlock>("HtmlBlock");
+        self.add::<html_inline::HtmlInline>("HtmlInline");
+
+        self.add::<footnotes::FootnoteDefinition>("FootnoteDefinition");
+        self.add::<footnotes::FootnoteReference>("FootnoteReference");
+        self.add::<footnotes::FootnoteSection>("FootnoteSection");
+        self.add::<footnotes::FootnoteItem>("FootnoteItem");
+        self.add::<footnotes::FootnoteBackref>("FootnoteBackref");
+        self.add::<strikethrough::Strikethrough>("Strikethrough");
+        self.add::<tables::Table>("Table");
+        self.add::<tables::TableHead>("TableHead");
+        self.add::<tables::TableBody>("TableBody");
+        self.add::<tables::TableRow>("TableRow");
+        self.add::<tables::TableCell>("TableCell");
+        self.add::<tasklist::TaskListItem>("TaskListItem");
+        #[cfg(feature = "linkify")]
+        self.add::<crate::plugins::extra::linkify::Linkified>("Linkified");
+        #[cfg(feature = "syntect")]
+        self.add::<crate::plugins::extra::syntect::SyntectSnippet>("SyntectSnippet");
+    }
+}
+
+impl Default for NodeRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct JsonNode {
+    #[serde(rename = "type")]
+    node_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    srcmap: Option<(usize, usize)>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    attrs: Vec<(String, String)>,
+    #[serde(default, skip_serializing_if = "Value::is_null")]
+    value: Value,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    children: Vec<JsonNode>,
+}
+
+// Find `value` among the strings built-in plugins use for `&'static str` fields.
+fn known_str<E: serde::de::Error>(value: &str, known: &[&'static str]) -> Result<&'static str, E> {
+    known
+        .iter()
+        .find(|known| **known == value)
+        .copied()
+        .ok_or_else(|| E::invalid_value(serde::de::Unexpected::Str(value), &"built-in value"))
+}
+
+// Nodes with `&'static str` fields can't derive `Deserialize` (serde would
+// require `'de: 'static`), so they are converted through these structs.
+
+#[derive(Serialize, Deserialize)]
+struct TextSpecialJson<S> {
+    content: S,
+    markup: S,
+    info: S,
+}
+
+impl Serialize for TextSpecial {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        TextSpecialJson {
+            content: self.content.as_str(),
+            markup: self.markup.as_str(),
+            info: self.info,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for TextSpecial {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let json = TextSpecialJson::<String>::deserialize(deserializer)?;
+        Ok(Self {
+            content: json.content,
+            markup: json.markup,
+            info: known_str(&json.info, &["autolink", "entity", "escape"])?,
+        })
+    }
+}
@@ -0,0 +1,158 @@
+//! Safeguards for parsing untrusted input.
+//!
+//! ```rust
+//! use markdown_that::parser::limits::ParseError;
+//!
+//! let md = &mut markdown_that::MarkdownThat::new();
+//! markdown_that::plugins::cmark::add(md);
+//! md.limits.max_nodes = Some(3);
+//!
+//! // `parse` keeps the rest of the input as plain text
+//! let html = md.parse("*a* *b* *c*").render();
+//! assert_eq!(html, "<p><em>a</em> *b* *c*</p>\n");
+//!
+//! // `try_parse` returns an error instead
+//! let result = md.try_parse("*a* *b* *c*");
+//! assert_eq!(result.unwrap_err(), ParseError::TooManyNodes { limit: 3 });
+//! ```
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use crate::Node;
+use crate::parser::extset::{RootExt, RootExtSet};
+
+/// Limits applied to every [parse](crate::MarkdownThat::parse), set them in
+/// [md.limits](crate::MarkdownThat::limits).
+///
+/// When a limit is exceeded, `parse` keeps the rest of the input as plain text,
+/// and [try_parse](crate::MarkdownThat::try_parse) returns [ParseError].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseLimits {
+    /// Maximum size of the source in bytes, larger documents are not parsed at all.
+    pub max_source_bytes: Option<usize>,
+
+    /// Maximum number of nodes returned by block and inline rules
+    /// (text between them and nodes created by rules internally aren't counted).
+    pub max_nodes: Option<usize>,
+
+    /// Maximum depth of the resulting tree (children of the root are at depth 1),
+    /// nodes that would be nested deeper are kept as plain text.
+    pub max_nesting: u32,
+
+    /// Time budget for a single parse.
+    pub time_budget: Option<Duration>,
+}
+
+impl Default for ParseLimits {
+    fn default() -> Self {
+        Self {
+            max_source_bytes: None,
+            max_nodes: None,
+            max_nesting: 100,
+            time_budget: None,
+        }
+    }
+}
+
+/// Error returned by [try_parse](crate::MarkdownThat::try_parse) if a [limit](ParseLimits)
+/// is exceeded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// Source is larger than [max_source_bytes](ParseLimits::max_source_bytes).
+    SourceTooLarge { size: usize, limit: usize },
+    /// More nodes than [max_nodes](ParseLimits::max_nodes) are created.
+    TooManyNodes { limit: usize },
+    /// Content is nested deeper than [max_nesting](ParseLimits::max_nesting).
+    TooDeep { limit: u32 },
+    /// Parsing takes longer than [time_budget](ParseLimits::time_budget).
+    TimedOut { budget: Duration },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SourceTooLarge { size, limit } => {
+                write!(f, "source is {size} bytes, limit is {limit} bytes")
+            }
+            Self::TooManyNodes { limit } => write!(f, "more than {limit} nodes"),
+            Self::TooDeep { limit } => write!(f, "nesting is deeper than {limit} levels"),
+            Self::TimedOut { budget } => write!(f, "parsing takes longer than {budget:?}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+// Limits for the current parse, tokenizers look it up in root data.
+#[derive(Debug)]
+pub(crate) struct ParseBudget {
+    nodes: usize,
+    max_nodes: Option<usize>,
+    deadline: Option<(Instant, Duration)>,
+    // true if tokenizers must keep the rest of the input as text
+    exhausted: bool,
+    // first exceeded limit
+    pub(crate) error: Option<ParseError>,
+}
+
+impl RootExt for ParseBudget {}
+
+impl ParseBudget {
+    pub(crate) fn new(limits: &ParseLimits) -> Self {
+        Self {
+            nodes: 0,
+            max_nodes: limits.max_nodes,
+            deadline: limits
+                .time_budget
+                .map(|budget| (Instant::now() + budget, budget)),
+            exhausted: false,
+            error: None,
+        }
+    }
+
+    // check if tokenizers can continue, accounting for `nodes` new nodes
+    pub(crate) fn check(root_ext: &mut RootExtSet, nodes: usize) -> bool {
+        let Some(budget) = root_ext.get_mut::<ParseBudget>() else {
+            return true;
+        };
+        if budget.exhausted {
+            return false;
+        }
+
+        budget.nodes += nodes;
+        let error = if let Some(limit) = budget.max_nodes.filter(|limit| budget.nodes > *limit) {
+            ParseError::TooManyNodes { limit }
+        } else if let Some((_, time_budget)) = budget
+            .deadline
+            .filter(|(deadline, _)| Instant::now() >= *deadline)
+        {
+            ParseError::TimedOut {
+                budget: time_budget,
+            }
+        } else {
+            return true;
+        };
+
+        budget.exhausted = true;
+        budget.error.get_or_insert(error);
+        false
+    }
+
+    // record an error without stopping the parse (nesting is limited locally)
+    pub(crate) fn report(root_ext: &mut RootExtSet, error: ParseError) {
+        if let Some(budget) = root_ext.get_mut::<ParseBudget>() {
+            budget.error.get_or_insert(error);
+        }
+    }
+}
+
+// true if `nodes` (or their descendants) go deeper than `levels` below their parent;
+// used to check if a node can be added without exceeding max_nesting
+pub(crate) fn exceeds_depth(nodes: &[Node], levels: u32) -> bool {
+    match levels {
+        0 => !nodes.is_empty(),
+        _ => nodes
+            .iter()
+            .any(|node| exceeds_depth(&node.children, levels - 1)),
+    }
+}
@@ -1,12 +1,16 @@
+use std::io;
+
 use crate::Node;
 use crate::common::TypeKey;
 use crate::common::ruler::Ruler;
 use crate::common::sourcemap::SourcePos;
 use crate::parser::block::{self, BlockParser};
 use crate::parser::core::{Root, *};
-use crate::parser::extset::MarkdownThatExtSet;
-use crate::parser::inline::{self, InlineParser};
+use crate::parser::extset::{MarkdownThatExtSet, RootExtSet};
+use crate::parser::inline::{self, InlineParser, Text};
+use crate::parser::limits::{ParseBudget, ParseError, ParseLimits};
 use crate::parser::linkfmt::{LinkFormatter, MDLinkFormatter};
+use crate::parser::renderer::HtmlRenderOptions;
 
 type RuleFn = fn(&mut Node, &MarkdownThat);
 
@@ -25,18 +29,16 @@ pub struct MarkdownThat {
     /// Storage for custom data used in plugins.
     pub ext: MarkdownThatExtSet,
 
-    /// Maximum depth of the generated AST, exists to prevent recursion
-    /// (if markdown source reaches this depth, deeply nested structures
-    /// will be parsed as plain text).
-    /// TODO: doesn't work
-    #[doc(hidden)]
-    pub max_nesting: u32,
+    /// Limits protecting against untrusted input (size, node count, nesting, time).
+    pub limits: ParseLimits,
 
     /// Maximum allowed indentation for syntax blocks
     /// default i32::MAX, indented code blocks will set this to 4
     pub max_indent: i32,
 
     ruler: Ruler<TypeKey, RuleFn>,
+    // core rules that look at the whole document, see `mark_document_rule`
+    document_rules: Vec<TypeKey>,
 }
 
 impl MarkdownThat {
@@ -45,8 +47,63 @@ impl MarkdownThat {
     }
 
     pub fn parse(&self, src: &str) -> Node {
-        let mut node = Node::new(Root::new(src.to_owned()));
-        node.srcmap = Some(SourcePos::new(0, src.len()));
+        self.parse_owned(src.to_owned())
+    }
+
+    /// Same as [parse](MarkdownThat::parse), but returns an error if any of the
+    /// [limits](MarkdownThat::limits) is exceeded, instead of keeping the rest
+    /// of the input as plain text.
+    pub fn try_parse(&self, src: &str) -> Result<Node, ParseError> {
+        if let Some(limit) = self
+            .limits
+            .max_source_bytes
+            .filter(|limit| src.len() > *limit)
+        {
+            return Err(ParseError::SourceTooLarge {
+                size: src.len(),
+                limit,
+            });
+        }
+
+        let (node, error) = self.parse_with_limits(src.to_owned());
+        match error {
+            Some(error) => Err(error),
+            None => Ok(node),
+        }
+    }
+
+    /// Read the whole document from `reader` and parse it.
+    ///
+    /// Returns an error of kind [InvalidData](io::ErrorKind::InvalidData) if input
+    /// isn't valid UTF-8. To process input block by block instead, use [stream](MarkdownThat::stream).
+    pub fn parse_reader(&self, mut reader: impl io::Read) -> io::Result<Node> {
+        let mut src = String::new();
+        reader.read_to_string(&mut src)?;
+        Ok(self.parse_owned(src))
+    }
+
+    fn parse_owned(&self, src: String) -> Node {
+        self.parse_with_limits(src).0
+    }
+
+    fn parse_with_limits(&self, src: String) -> (Node, Option<ParseError>) {
+        let len = src.len();
+        let mut root = Root::new(src);
+        self.store_render_options(&mut root.ext);
+        let mut node = Node::new(root);
+        node.srcmap = Some(SourcePos::new(0, len));
+
+        if let Some(limit) = self.limits.max_source_bytes.filter(|limit| len > *limit) {
+            // too large to parse, keep the whole source as plain text
+            let content = node.cast::<Root>().unwrap().content.clone();
+            let mut text = Node::new(Text { content });
+            text.srcmap = node.srcmap;
+            node.children.push(text);
+            return (node, Some(ParseError::SourceTooLarge { size: len, limit }));
+        }
+
+        let budget = ParseBudget::new(&self.limits);
+        node.cast_mut::<Root>().unwrap().ext.insert(budget);
 
         for rule in self.ruler.iter() {
             rule(&mut node, self);
@@ -55,7 +112,9 @@ impl MarkdownThat {
                 "root node of the AST must always be Root"
             );
         }
-        node
+
+        let budget = node.cast_mut::<Root>().unwrap().ext.remove::<ParseBudget>();
+        (node, budget.and_then(|budget| budget.error))
     }
 
     pub fn add_rule<T: CoreRule>(&mut self) -> RuleBuilder<RuleFn> {
@@ -70,6 +129,49 @@ impl MarkdownThat {
     pub fn remove_rule<T: CoreRule>(&mut self) {
         self.ruler.remove(TypeKey::of::<T>());
     }
+
+    /// Mark core rule `T` as looking at the whole document (e.g. collecting data
+    /// from all blocks into one place), so it isn't executed on a part of it:
+    /// while it's enabled, [reparse](MarkdownThat::reparse) parses the whole
+    /// document, and [stream](MarkdownThat::stream) returns all blocks at the end.
+    pub fn mark_document_rule<T: CoreRule>(&mut self) {
+        let key = TypeKey::of::<T>();
+        if !self.document_rules.contains(&key) {
+            self.document_rules.push(key);
+        }
+    }
+
+    /// Maximum depth of the AST, same as [limits.max_nesting](ParseLimits::max_nesting).
+    #[deprecated(note = "use `md.limits.max_nesting` instead")]
+    pub fn max_nesting(&self) -> u32 {
+        self.limits.max_nesting
+    }
+
+    /// Set maximum depth of the AST, same as [limits.max_nesting](ParseLimits::max_nesting).
+    #[deprecated(note = "use `md.limits.max_nesting` instead")]
+    pub fn set_max_nesting(&mut self, max_nesting: u32) {
+        self.limits.max_nesting = max_nesting;
+    }
+
+    // HTML render options (set by presets) are kept in the root node,
+    // so `parse(src).render()` gives the same result as `render(src)`
+    pub(super) fn store_render_options(&self, ext: &mut RootExtSet) {
+        match self.ext.get::<HtmlRenderOptions>() {
+            Some(options) => ext.insert(options.clone()),
+            None => ext.remove::<HtmlRenderOptions>(),
+        };
+    }
+
+    // core rules in order of execution, used to re-run them on a part of the document
+    pub(super) fn core_rules(&self) -> impl Iterator<Item = (&[TypeKey], &RuleFn)> {
+        self.ruler.iter_with_marks()
+    }
+
+    // whether any of enabled core rules is marked with `mark_document_rule`
+    pub(super) fn has_document_rules(&self) -> bool {
+        self.core_rules()
+            .any(|(marks, _)| marks.iter().any(|mark| self.document_rules.contains(mark)))
+    }
 }
 
 impl Default for MarkdownThat {
@@ -79,8 +181,9 @@ impl Default for MarkdownThat {
             inline: InlineParser::new(),
             link_formatter: Box::new(MDLinkFormatter::new()),
             ext: MarkdownThatExtSet::new(),
-            max_nesting: 100,
+            limits: ParseLimits::default(),
             ruler: Ruler::new(),
+            document_rules: Vec::new(),
             max_indent: i32::MAX,
         };
 
@@ -0,0 +1,469 @@
+//! Export of the AST as [mdast](https://github.com/syntax-tree/mdast) (requires `serde` feature).
+//!
+//! mdast is the syntax tree used by the [unified](https://unifiedjs.com/) ecosystem
+//! (remark and its plugins), so documents can be parsed in Rust and processed further in JS:
+//!
+//! ```rust
+//! use markdown_that::parser::mdast::MdastExporter;
+//!
+//! let md = &mut markdown_that::MarkdownThat::new();
+//! markdown_that::plugins::cmark::add(md);
+//!
+//! let ast = md.parse("# hello");
+//! let mdast = MdastExporter::new().export(&ast);
+//! assert_eq!(mdast["children"][0]["type"], "heading");
+//! assert_eq!(mdast["children"][0]["depth"], 1);
+//! ```
+//!
+//! When the exported node is [Root], every mdast node gets `position`, calculated
+//! from its source map. Line and column are 1-based and count unicode characters,
+//! while `offset` is a byte offset into the source.
+//!
+//! Nodes without a registered handler are replaced with their children,
+//! custom nodes can be added with [MdastExporter::add].
+use serde_json::{Value, json};
+use std::collections::HashMap;
+
+use crate::Node;
+use crate::common::TypeKey;
+use crate::common::sourcemap::{SourcePos, SourceWithLineStarts};
+use crate::common::utils::normalize_reference;
+use crate::parser::core::Root;
+use crate::parser::inline::{Text, TextSpecial};
+use crate::plugins::cmark::block::blockquote::Blockquote;
+use crate::plugins::cmark::block::code::CodeBlock;
+use crate::plugins::cmark::block::fence::CodeFence;
+use crate::plugins::cmark::block::heading::ATXHeading;
+use crate::plugins::cmark::block::hr::ThematicBreak;
+use crate::plugins::cmark::block::lheading::SetextHeader;
+use crate::plugins::cmark::block::list::{BulletList, ListItem, OrderedList};
+use crate::plugins::cmark::block::paragraph::Paragraph;
+use crate::plugins::cmark::block::reference::Definition;
+use crate::plugins::cmark::inline::autolink::Autolink;
+use crate::plugins::cmark::inline::backticks::CodeInline;
+use crate::plugins::cmark::inline::emphasis::{Em, Strong};
+use crate::plugins::cmark::inline::image::Image;
+use crate::plugins::cmark::inline::link::Link;
+use crate::plugins::cmark::inline::newline::{Hardbreak, Softbreak};
+use crate::plugins::extra::footnotes::{
+    FootnoteBackref, FootnoteItem, FootnoteReference, FootnoteSection,
+};
+use crate::plugins::extra::strikethrough::Strikethrough;
+use crate::plugins::extra::tables::{
+    ColumnAlignment, Table, TableBody, TableCell, TableHead, TableRow,
+};
+use crate::plugins::gfm::tasklist::TaskListItem;
+use crate::plugins::html::html_block::HtmlBlock;
+use crate::plugins::html::html_inline::HtmlInline;
+
+/// Function that converts a node into mdast, it should return `Value::Null`
+/// if the node must be dropped.
+pub type MdastHandler = fn(&Node, &mut MdastContext) -> Value;
+
+/// Converts AST into mdast, see [module documentation](self).
+#[derive(Debug, Clone)]
+pub struct MdastExporter {
+    handlers: HashMap<TypeKey, MdastHandler>,
+}
+
+impl MdastExporter {
+    /// Create exporter with handlers for all built-in nodes.
+    pub fn new() -> Self {
+        let mut exporter = Self {
+            handlers: HashMap::new(),
+        };
+        exporter.add_builtins();
+        exporter
+    }
+
+    /// Set (or replace) a handler for the node type.
+    pub fn add<T: crate::NodeValue>(&mut self, handler: MdastHandler) {
+        self.handlers.insert(TypeKey::of::<T>(), handler);
+    }
+
+    /// Remove a handler for the node type, its children will be exported in its place.
+    pub fn remove<T: crate::NodeValue>(&mut self) {
+        self.handlers.remove(&TypeKey::of::<T>());
+    }
+
+    /// Convert AST into mdast.
+    pub fn export(&self, node: &Node) -> Value {
+        let source = node
+            .cast::<Root>()
+            .map(|root| SourceWithLineStarts::new(&root.content));
+        let mut ctx = MdastContext {
+            exporter: self,
+            source,
+        };
+        let mut result = ctx.convert(node);
+        if result.len() != 1 {
+            // root is unknown, so there's either zero or multiple nodes
+            result = vec![json!({ "type": "root", "children": result })];
+        }
+        result.pop().unwrap()
+    }
+
+    fn add_builtins(&mut self) {
+        self.add::<Root>(|node, ctx| ctx.parent(node, json!({ "type": "root" })));
+        self.add::<Text>(|node, ctx| {
+            let text = node.cast::<Text>().unwrap();
+            ctx.leaf(node, json!({ "type": "text", "value": text.content }))
+        });
+        self.add::<TextSpecial>(|node, ctx| {
+            let text = node.cast::<TextSpecial>().unwrap();
+            ctx.leaf(node, json!({ "type": "text", "value": text.content }))
+        });
+
+        self.add::<Paragraph>(|node, ctx| ctx.parent(node, json!({ "type": "paragraph" })));
+        self.add::<ATXHeading>(|node, ctx| {
+            let depth = node.cast::<ATXHeading>().unwrap().level;
+            ctx.parent(node, json!({ "type": "heading", "depth": depth }))
+        });
+        self.add::<SetextHeader>(|node, ctx| {
+            let depth = node.cast::<SetextHeader>().unwrap().level;
+            ctx.parent(node, json!({ "type": "heading", "depth": depth }))
+        });
+        self.add::<ThematicBreak>(|node, ctx| ctx.leaf(node, json!({ "type": "thematicBreak" })));
+        self.add::<Blockquote>(|node, ctx| ctx.parent(node, json!({ "type": "blockquote" })));
+        self.add::<BulletList>(|node, ctx| {
+            let spread = is_spread(node);
+            ctx.parent(
+                node,
+                json!({ "type": "list", "ordered": false, "start": null, "spread": spread }),
+            )
+        });
+        self.add::<OrderedList>(|node, ctx| {
+            let start = node.cast::<OrderedList>().unwrap().start;
+            let spread = is_spread(node);
+            ctx.parent(
+                node,
+                json!({ "type": "list", "ordered": true, "start": start, "spread": spread }),
+            )
+        });
+        self.add::<ListItem>(|node, ctx| list_item(node, ctx, Value::Null));
+        self.add::<TaskListItem>(|node, ctx| {
+            let checked = node.cast::<TaskListItem>().unwrap().checked;
+            list_item(node, ctx, checked.into())
+        });
+        self.add::<CodeBlock>(|node, ctx| {
+            let code = node.cast::<CodeBlock>().unwrap();
+            let value = code.content.strip_suffix('\n').unwrap_or(&code.content);
+            ctx.leaf(
+                node,
+                json!({ "type": "code", "lang": null, "meta": null, "value": value }),
+            )
+        });
+        self.add::<CodeFence>(|node, ctx| {
+            let fence = node.cast::<CodeFence>().unwrap();
+            let value = fence.content.strip_suffix('\n').unwrap_or(&fence.content);
+            let info = fence.info.trim();
+            let (lang, meta) = match info.split_once(char::is_whitespace) {
+                Some((lang, meta)) => (Some(lang), Some(meta.trim_start())),
+                None => (Some(info).filter(|info| !info.is_empty()), None),
+            };
+            ctx.leaf(
+                node,
+                json!({ "type": "code", "lang": lang, "meta": meta, "value": value }),
+            )
+        });
+        self.add::<Definition>(|node, ctx| {
+            let def = node.cast::<Definition>().unwrap();
+            ctx.leaf(
+                node,
+                json!({
+                    "type": "definition",
+                    "identifier": normalize_reference(&def.label).to_lowercase(),
+                    "label": def.label,
+                    "url": def.destination,
+                    "title": def.title,
+                }),
+            )
+        });
+
+        self.add::<Em>(|node, ctx| ctx.parent(node, json!({ "type": "emphasis" })));
+        self.add::<Strong>(|node, ctx| ctx.parent(node, json!({ "type": "strong" })));
+        self.add::<CodeInline>(|node, ctx| {
+            let value = node.collect_text();
+            ctx.leaf(node, json!({ "type": "inlineCode", "value": value }))
+        });
+        self.add::<Link>(|node, ctx| {
+            let link = node.cast::<Link>().unwrap();
+            let value = json!({ "type": "link", "url": link.url, "title": link.title });
+            ctx.parent(node, value)
+        });
+        self.add::<Autolink>(|node, ctx| {
+            let link = node.cast::<Autolink>().unwrap();
+            let value = json!({ "type": "link", "url": link.url, "title": null });
+            ctx.parent(node, value)
+        });
+        self.add::<Image>(|node, ctx| {
+            let image = node.cast::<Image>().unwrap();
+            let alt = node.render_text();
+            ctx.leaf(
+                node,
+                json!({ "type": "image", "url": image.url, "title": image.title, "alt": alt }),
+            )
+        });
+        self.add::<Hardbreak>(|node, ctx| ctx.leaf(node, json!({ "type": "break" })));
+        // softbreaks are kept as newlines inside text nodes in mdast
+        self.add::<Softbreak>(|node, ctx| ctx.leaf(node, json!({ "type": "text", "value": "\n" })));
+
+        self.add::<HtmlBlock>(|node, ctx| {
+            let html = node.cast::<HtmlBlock>().unwrap();
+            let value = html.content.strip_suffix('\n').unwrap_or(&html.content);
+            ctx.leaf(node, json!({ "type": "html", "value": value }))
+        });
+        self.add::<HtmlInline>(|node, ctx| {
+            let html = node.cast::<HtmlInline>().unwrap();
+            ctx.leaf(node, json!({ "type": "html", "value": html.content }))
+        });
+
+        self.add::<Strikethrough>(|node, ctx| ctx.parent(node, json!({ "type": "delete" })));
+        self.add::<Table>(|node, ctx| {
+            let table = node.cast::<Table>().unwrap();
+            let align = table
+                .alignments
+                .iter()
+                .map(|align| match align {
+                    ColumnAlignment::None => None,
+                    ColumnAlignment::Left => Some("left"),
+                    ColumnAlignment::Right => Some("right"),
+                    ColumnAlignment::Center => Some("center"),
+                })
+                .collect::<Vec<_>>();
+            ctx.parent(node, json!({ "type": "table", "align": align }))
+        });
+        // mdast has no table sections, rows are placed directly into the table
+        self.remove::<TableHead>();
+        self.remove::<TableBody>();
+        self.add::<TableRow>(|node, ctx| ctx.parent(node, json!({ "type": "tableRow" })));
+        self.add::<TableCell>(|node, ctx| ctx.parent(node, json!({ "type": "tableCell" })));
+
+        self.add::<FootnoteReference>(|node, ctx| {
+            let reference = node.cast::<FootnoteReference>().unwrap();
+            let label = footnote_label(&reference.label, &reference.generated_label, reference.id);
+            ctx.leaf(
+                node,
+                json!({
+                    "type": "footnoteReference",
+                    "identifier": normalize_reference(&label).to_lowercase(),
+                    "label": label,
+                }),
+            )
+        });
+        // definitions are placed at the end of the document, where the section was
+        self.remove::<FootnoteSection>();
+        self.add::<FootnoteItem>(|node, ctx| {
+            let item = node.cast::<FootnoteItem>().unwrap();
+            let label = footnote_label(&item.label, &item.generated_label, item.id);
+            let value = json!({
+                "type": "footnoteDefinition",
+                "identifier": normalize_reference(&label).to_lowercase(),
+                "label": label,
+            });
+            let children = flow_children(node, ctx);
+            ctx.wrap(node, value, children)
+        });
+        self.add::<FootnoteBackref>(|_, _| Value::Null);
+
+        #[cfg(feature = "linkify")]
+        self.add::<crate::plugins::extra::linkify::Linkified>(|node, ctx| {
+            let link = node
+                .cast::<crate::plugins::extra::linkify::Linkified>()
+                .unwrap();
+            let value = json!({ "type": "link", "url": link.url, "title": null });
+            ctx.parent(node, value)
+        });
+        #[cfg(feature = "syntect")]
+        self.add::<crate::plugins::extra::syntect::SyntectSnippet>(|node, ctx| {
+            // original code is lost after highlighting, so it's exported as html
+            let snippet = node
+                .cast::<crate::plugins::extra::syntect::SyntectSnippet>()
+                .unwrap();
+            ctx.leaf(node, json!({ "type": "html", "value": snippet.html }))
+        });
+    }
+}
+
+impl Default for MdastExporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// State of the export, passed to every [MdastHandler].
+pub struct MdastContext<'a> {
+    exporter: &'a MdastExporter,
+    source: Option<SourceWithLineStarts>,
+}
+
+impl MdastContext<'_> {
+    /// Convert children of the node, adjacent text nodes are merged together.
+    pub fn children(&mut self, node: &Node) -> Vec<Value> {
+        let mut result = Vec::new();
+        for child in node.children.iter() {
+            stacker::maybe_grow(64 * 1024, 1024 * 1024, || {
+                result.extend(self.convert(child));
+            });
+        }
+        result
+    }
+
+    /// Add `position` to a node without children (e.g. `text` or `code`).
+    pub fn leaf(&mut self, node: &Node, mut value: Value) -> Value {
+        if let Some(position) = self.position(node.srcmap) {
+            value["position"] = position;
+        }
+        value
+    }
+
+    /// Add `children` and `position` to a parent node (e.g. `paragraph` or `link`).
+    pub fn parent(&mut self, node: &Node, value: Value) -> Value {
+        let children = self.children(node);
+        self.wrap(node, value, children)
+    }
+
+    /// Convert a source map into mdast `position`, returns `None` if it's not available.
+    pub fn position(&self, srcmap: Option<SourcePos>) -> Option<Value> {
+        let map = self.source.as_ref()?;
+        let (start, end) = srcmap?.get_byte_offsets();
+        let point = |offset: usize| {
+            // point right after the previous char, this way newlines are handled correctly
+            let (line, column) = match offset.checked_sub(1) {
+                Some(prev) => {
+                    let (line, column) = SourcePos::new(prev, prev).get_positions(map).0;
+                    (line, column + 1)
+                }
+                None => (1, 1),
+            };
+            json!({ "line": line, "column": column, "offset": offset })
+        };
+        Some(json!({ "start": point(start), "end": point(end) }))
+    }
+
+    fn wrap(&mut self, node: &Node, mut value: Value, children: Vec<Value>) -> Value {
+        value["children"] = Value::Array(merge_text(children));
+        if let Some(position) = self.position(node.srcmap) {
+            value["position"] = position;
+        }
+        value
+    }
+
+    fn convert(&mut self, node: &Node) -> Vec<Value> {
+        match self.exporter.handlers.get(&node.node_type) {
+            Some(handler) => {
+                let value = handler(node, self);
+                if value.is_null() { vec![] } else { vec![value] }
+            }
+            None => self.children(node),
+        }
+    }
+}
+
+impl std::fmt::Debug for MdastContext<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MdastContext").finish_non_exhaustive()
+    }
+}
+
+fn is_spread(list: &Node) -> bool {
+    list.children
+        .iter()
+        .any(|item| item.children.iter().any(|child| child.is::<Paragraph>()))
+}
+
+fn list_item(node: &Node, ctx: &mut MdastContext, checked: Value) -> Value {
+    let children = flow_children(node, ctx);
+    let spread = node.children.iter().any(|child| child.is::<Paragraph>());
+    let item = json!({ "type": "listItem", "spread": spread, "checked": checked });
+    ctx.wrap(node, item, children)
+}
+
+// Convert children of a block container, inline content found directly inside
+// is wrapped into paragraphs (tight lists have their paragraphs unwrapped,
+// but mdast requires them).
+fn flow_children(node: &Node, ctx: &mut MdastContext) -> Vec<Value> {
+    let mut children = Vec::new();
+    let mut inlines = Vec::new();
+    for child in node.children.iter() {
+        for value in ctx.convert(child) {
+            if is_phrasing(&value) && !child.is::<HtmlBlock>() {
+                inlines.push(value);
+            } else {
+                if !inlines.is_empty() {
+                    children.push(paragraph(std::mem::take(&mut inlines)));
+                }
+                children.push(value);
+            }
+        }
+    }
+    if !inlines.is_empty() {
+        children.push(paragraph(inlines));
+    }
+    children
+}
+
+// Inline footnotes are identified by generated label, or by their number
+// if the core rule didn't generate one.
+fn footnote_label(label: &Option<String>, generated_label: &Option<String>, id: usize) -> String {
+    label
+        .as_ref()
+        .or(generated_label.as_ref())
+        .cloned()
+        .unwrap_or_else(|| (id + 1).to_string())
+}
+
+fn is_phrasing(value: &Value) -> bool {
+    matches!(
+        value["type"].as_str(),
+        Some(
+            "text"
+                | "emphasis"
+                | "strong"
+                | "delete"
+                | "inlineCode"
+                | "break"
+                | "link"
+                | "image"
+                | "html"
+                | "linkReference"
+                | "imageReference"
+                | "footnoteReference"
+        )
+    )
+}
+
+// Wrap inline content of a tight list item into a paragraph.
+fn paragraph(children: Vec<Value>) -> Value {
+    let mut result = json!({ "type": "paragraph" });
+    let start = children.first().and_then(|child| child.get("position"));
+    let end = children.last().and_then(|child| child.get("position"));
+    if let (Some(start), Some(end)) = (start, end) {
+        result["position"] = json!({ "start": start["start"], "end": end["end"] });
+    }
+    result["children"] = Value::Array(merge_text(children));
+    result
+}
+
+// Text, escapes, entities and softbreaks are separate nodes in our AST,
+// but a single `text` node in mdast.
+fn merge_text(children: Vec<Value>) -> Vec<Value> {
+    let mut result: Vec<Value> = Vec::with_capacity(children.len());
+    for child in children {
+        if child["type"] == "text" {
+            if let Some(last) = result.last_mut().filter(|last| last["type"] == "text") {
+                let value = child["value"].as_str().unwrap_or_default();
+                last["value"] =
+                    Value::String(last["value"].as_str().unwrap_or_default().to_owned() + value);
+                if let (Some(last_pos), Some(pos)) =
+                    (last.get_mut("position"), child.get("position"))
+                {
+                    last_pos["end"] = pos["end"].clone();
+                }
+                continue;
+            }
+        }
+        result.push(child);
+    }
+    result
+}
@@ -36,10 +36,21 @@
 //!
 pub mod block;
 pub mod core;
+pub mod diagnostics;
 pub mod extset;
+pub mod incremental;
 pub mod inline;
+#[cfg(feature = "serde")]
+pub mod json;
+pub mod limits;
 pub mod linkfmt;
+#[cfg(feature = "serde")]
+pub mod mdast;
+#[cfg(feature = "serde")]
+pub mod pandoc;
+pub mod presets;
+pub mod renderer;
+pub mod stream;
 
 pub(super) mod main;
 pub(super) mod node;
-pub(super) mod renderer;
@@ -1,13 +1,20 @@
 use downcast_rs::{Downcast, impl_downcast};
 use std::any::TypeId;
-use std::fmt::Debug;
+use std::fmt::{self, Debug};
+use std::io;
 
 use crate::Renderer;
 use crate::common::TypeKey;
 use crate::common::sourcemap::SourcePos;
+use crate::parser::core::Root;
 use crate::parser::extset::NodeExtSet;
 use crate::parser::inline::Text;
-use crate::parser::renderer::HTMLRenderer;
+use crate::parser::renderer::{
+    AnsiRenderOptions, AnsiRenderer, ChatRenderOptions, ChatRenderer, DocbookRenderer,
+    GemtextRenderer, HTMLRenderer, HtmlRenderOptions, IoWriter, LatexRenderOptions, LatexRenderer,
+    ManRenderOptions, ManRenderer, MarkdownFormatOptions, MarkdownRenderer, TextRenderer,
+    TypstRenderer,
+};
 use crate::plugins::cmark::inline::newline::Softbreak;
 
 /// Single node in the CommonMark AST.
@@ -83,17 +90,140 @@ impl Node {
     }
 
     /// Render this node to HTML.
+    ///
+    /// If this is the root node, options of the parser are used
+    /// (see [presets](crate::parser::presets)), default ones otherwise.
     pub fn render(&self) -> String {
-        let mut fmt = HTMLRenderer::<false>::new();
+        self.render_with(&self.html_options())
+    }
+
+    /// Render this node to HTML, writing it directly into `out` (e.g. a file or a socket).
+    ///
+    /// Output is written in many small chunks, so `out` should be buffered
+    /// (e.g. wrapped in [std::io::BufWriter]).
+    pub fn render_to<W: io::Write>(&self, out: W) -> io::Result<()> {
+        let mut fmt = HTMLRenderer::with_output(IoWriter::new(out), self.html_options());
         fmt.render(self);
-        fmt.into()
+        let (out, result) = fmt.finish();
+        match out.error {
+            Some(err) => Err(err),
+            None => result.map_err(io::Error::other),
+        }
+    }
+
+    /// Render this node to HTML, writing it into [std::fmt::Write] (e.g. `fmt::Formatter`).
+    pub fn render_fmt<W: fmt::Write>(&self, out: W) -> fmt::Result {
+        let mut fmt = HTMLRenderer::with_output(out, self.html_options());
+        fmt.render(self);
+        fmt.finish().1
     }
 
     /// Render this node to XHTML, it adds slash to self-closing tags like this: `<img />`.
     ///
     /// This mode exists for compatibility with CommonMark tests.
     pub fn xrender(&self) -> String {
-        let mut fmt = HTMLRenderer::<true>::new();
+        self.render_with(&HtmlRenderOptions {
+            xhtml: true,
+            ..self.html_options()
+        })
+    }
+
+    // options stored in the root node by the parser
+    fn html_options(&self) -> HtmlRenderOptions {
+        self.cast::<Root>()
+            .and_then(|root| root.ext.get::<HtmlRenderOptions>())
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Render this node to HTML with custom options.
+    pub fn render_with(&self, options: &HtmlRenderOptions) -> String {
+        let mut fmt = HTMLRenderer::new(options.clone());
+        fmt.render(self);
+        fmt.into()
+    }
+
+    /// Render this node to LaTeX (document body only, without preamble).
+    pub fn render_latex(&self, options: &LatexRenderOptions) -> String {
+        let mut fmt = LatexRenderer::new(options.clone());
+        fmt.render(self);
+        fmt.into()
+    }
+
+    /// Render this node to DocBook XML, headings in the root node start nested sections.
+    pub fn render_docbook(&self) -> String {
+        let mut fmt = DocbookRenderer::new();
+        fmt.render(self);
+        fmt.into()
+    }
+
+    /// Render this node to Typst markup.
+    pub fn render_typst(&self) -> String {
+        let mut fmt = TypstRenderer::new();
+        fmt.render(self);
+        fmt.into()
+    }
+
+    /// Render this node to Gemtext, links are moved to separate lines after each block.
+    pub fn render_gemtext(&self) -> String {
+        let mut fmt = GemtextRenderer::new();
+        fmt.render(self);
+        fmt.into()
+    }
+
+    /// Render this node to a chat message (Slack or Discord markup).
+    pub fn render_chat(&self, options: &ChatRenderOptions) -> String {
+        let mut fmt = ChatRenderer::new(options.clone());
+        fmt.render(self);
+        fmt.into()
+    }
+
+    /// Render this node to a man page (roff with `man` macros).
+    pub fn render_man(&self, options: &ManRenderOptions) -> String {
+        let mut fmt = ManRenderer::new(options.clone());
+        fmt.render(self);
+        fmt.into()
+    }
+
+    /// Render this node back to Markdown source.
+    ///
+    /// If this is the root node, unmodified text is copied from the original
+    /// source as is, so parsing the result yields an equivalent AST.
+    pub fn render_markdown(&self) -> String {
+        let source = self.cast::<Root>().map(|root| root.content.as_str());
+        let mut fmt = MarkdownRenderer::new(source);
+        fmt.render(self);
+        fmt.into()
+    }
+
+    /// Format this node as Markdown, normalizing it according to `options`
+    /// (e.g. to run as a pre-commit hook).
+    ///
+    /// Besides the [options](MarkdownFormatOptions), ordered lists are renumbered,
+    /// and table columns are padded to align pipes. If this is the root node,
+    /// text between top-level blocks (blank lines, end of file) and syntax that
+    /// isn't normalized (reference links, thematic breaks, ATX headings) are
+    /// copied from the original source, so formatted documents stay the same.
+    pub fn format_markdown(&self, options: &MarkdownFormatOptions) -> String {
+        let source = self.cast::<Root>().map(|root| root.content.as_str());
+        let mut fmt = MarkdownRenderer::with_format(source, options.clone());
+        fmt.render(self);
+        fmt.into()
+    }
+
+    /// Render this node to plain text, e.g. for search indexing or `text/plain` emails.
+    ///
+    /// Unlike [collect_text](Node::collect_text), it keeps text of code spans and escapes,
+    /// and separates blocks, list items and table cells.
+    pub fn render_text(&self) -> String {
+        let mut fmt = TextRenderer::new();
+        fmt.render(self);
+        fmt.into()
+    }
+
+    /// Render this node to text styled with ANSI escape sequences, e.g. to print it in terminal.
+    pub fn render_ansi(&self, options: &AnsiRenderOptions) -> String {
+        let mut fmt = AnsiRenderer::new(options.clone());
         fmt.render(self);
         fmt.into()
     }
@@ -209,6 +339,9 @@ impl Default for Node {
 pub trait NodeValue: Debug + Downcast {
     /// Output HTML corresponding to this node using Renderer API.
     ///
+    /// This and other `render_*` methods are fallbacks of the renderers, handlers
+    /// added to their [registry](crate::parser::renderer::RenderRegistry) take precedence.
+    ///
     /// Example implementation looks like this:
     /// ```rust
     /// # const IGNORE : &str = stringify! {
@@ -224,6 +357,61 @@ pub trait NodeValue: Debug + Downcast {
         let _ = fmt;
         unimplemented!("{} doesn't implement render", node.name());
     }
+
+    /// Output Markdown source corresponding to this node using MarkdownRenderer API.
+    ///
+    /// Default implementation only renders children, so syntax of the node itself is lost
+    /// (the [formatter](Node::format_markdown) copies the block with it from the source).
+    /// Example implementation looks like this:
+    /// ```rust
+    /// # const IGNORE : &str = stringify! {
+    /// fn render_markdown(&self, node: &Node, fmt: &mut MarkdownRenderer) {
+    ///    fmt.text_raw("==");
+    ///    fmt.contents(&node.children);
+    ///    fmt.text_raw("==");
+    /// }
+    /// # };
+    /// ```
+    fn render_markdown(&self, node: &Node, fmt: &mut MarkdownRenderer) {
+        fmt.fallback(node);
+    }
+
+    /// Output plain text corresponding to this node using TextRenderer API.
+    ///
+    /// Default implementation only renders children, so it works for inline
+    /// markup like emphasis. Block nodes should separate themselves from
+    /// their siblings like this:
+    /// ```rust
+    /// # const IGNORE : &str = stringify! {
+    /// fn render_text(&self, node: &Node, fmt: &mut TextRenderer) {
+    ///    fmt.open_block();
+    ///    fmt.contents(&node.children);
+    ///    fmt.close_block();
+    /// }
+    /// # };
+    /// ```
+    fn render_text(&self, node: &Node, fmt: &mut TextRenderer) {
+        fmt.contents(&node.children);
+    }
+
+    /// Output text styled for terminals using AnsiRenderer API.
+    ///
+    /// Default implementation only renders children. Block nodes should
+    /// separate themselves from their siblings like this:
+    /// ```rust
+    /// # const IGNORE : &str = stringify! {
+    /// fn render_ansi(&self, node: &Node, fmt: &mut AnsiRenderer) {
+    ///    fmt.open_block();
+    ///    fmt.push_style("1"); // bold
+    ///    fmt.contents(&node.children);
+    ///    fmt.pop_style();
+    ///    fmt.close_block();
+    /// }
+    /// # };
+    /// ```
+    fn render_ansi(&self, node: &Node, fmt: &mut AnsiRenderer) {
+        fmt.contents(&node.children);
+    }
 }
 
 impl_downcast!(NodeValue);
@@ -0,0 +1,515 @@
+//! Export of the AST as [pandoc](https://pandoc.org/) JSON (requires `serde` feature).
+//!
+//! Output can be converted into any format pandoc supports with `pandoc -f json`,
+//! this way documents are parsed exactly as this crate does it, and pandoc's own
+//! Markdown reader isn't involved:
+//!
+//! ```rust
+//! use markdown_that::parser::pandoc::PandocExporter;
+//!
+//! let md = &mut markdown_that::MarkdownThat::new();
+//! markdown_that::plugins::cmark::add(md);
+//!
+//! let ast = md.parse("# hello");
+//! let doc = PandocExporter::new().export(&ast);
+//! assert_eq!(doc["blocks"][0]["t"], "Header");
+//! println!("{}", serde_json::to_string(&doc).unwrap());
+//! ```
+//!
+//! Node attributes (e.g. ids added by [heading_anchors](crate::plugins::extra::heading_anchors))
+//! are exported as pandoc `Attr`: `id` and `class` are mapped to identifier and classes,
+//! everything else to key-value pairs.
+//!
+//! Nodes without a registered handler are replaced with their children,
+//! custom nodes can be added with [PandocExporter::add].
+use serde_json::{Value, json};
+use std::collections::{HashMap, HashSet};
+
+use crate::Node;
+use crate::common::TypeKey;
+use crate::parser::core::Root;
+use crate::parser::inline::{Text, TextSpecial};
+use crate::plugins::cmark::block::blockquote::Blockquote;
+use crate::plugins::cmark::block::code::CodeBlock;
+use crate::plugins::cmark::block::fence::CodeFence;
+use crate::plugins::cmark::block::heading::ATXHeading;
+use crate::plugins::cmark::block::hr::ThematicBreak;
+use crate::plugins::cmark::block::lheading::SetextHeader;
+use crate::plugins::cmark::block::list::{BulletList, ListItem, OrderedList};
+use crate::plugins::cmark::block::paragraph::Paragraph;
+use crate::plugins::cmark::block::reference::Definition;
+use crate::plugins::cmark::inline::autolink::Autolink;
+use crate::plugins::cmark::inline::backticks::CodeInline;
+use crate::plugins::cmark::inline::emphasis::{Em, Strong};
+use crate::plugins::cmark::inline::image::Image;
+use crate::plugins::cmark::inline::link::Link;
+use crate::plugins::cmark::inline::newline::{Hardbreak, Softbreak};
+use crate::plugins::extra::footnotes::{
+    FootnoteBackref, FootnoteItem, FootnoteReference, FootnoteSection,
+};
+use crate::plugins::extra::strikethrough::Strikethrough;
+use crate::plugins::extra::tables::{ColumnAlignment, Table, TableBody, TableHead, TableRow};
+use crate::plugins::gfm::tasklist::TaskListItem;
+use crate::plugins::html::html_block::HtmlBlock;
+use crate::plugins::html::html_inline::HtmlInline;
+
+/// Version of pandoc types the output is compatible with (pandoc 3.x).
+pub const PANDOC_API_VERSION: [u32; 3] = [1, 23, 1];
+
+/// Function that converts a node into zero or more pandoc elements (blocks or inlines).
+pub type PandocHandler = fn(&Node, &mut PandocContext) -> Vec<Value>;
+
+/// Converts AST into pandoc JSON, see [module documentation](self).
+#[derive(Debug, Clone)]
+pub struct PandocExporter {
+    handlers: HashMap<TypeKey, PandocHandler>,
+}
+
+impl PandocExporter {
+    /// Create exporter with handlers for all built-in nodes.
+    pub fn new() -> Self {
+        let mut exporter = Self {
+            handlers: HashMap::new(),
+        };
+        exporter.add_builtins();
+        exporter
+    }
+
+    /// Set (or replace) a handler for the node type.
+    pub fn add<T: crate::NodeValue>(&mut self, handler: PandocHandler) {
+        self.handlers.insert(TypeKey::of::<T>(), handler);
+    }
+
+    /// Remove a handler for the node type, its children will be exported in its place.
+    pub fn remove<T: crate::NodeValue>(&mut self) {
+        self.handlers.remove(&TypeKey::of::<T>());
+    }
+
+    /// Convert AST into pandoc document.
+    pub fn export(&self, node: &Node) -> Value {
+        // footnotes are written as notes at the place of their references
+        let mut notes = HashMap::new();
+        for section in node
+            .children
+            .iter()
+            .filter(|child| child.is::<FootnoteSection>())
+        {
+            for item in section.children.iter() {
+                if let Some(data) = item.cast::<FootnoteItem>() {
+                    notes.insert(data.id, item);
+                }
+            }
+        }
+
+        let mut ctx = PandocContext {
+            exporter: self,
+            notes,
+            open_notes: HashSet::new(),
+        };
+        let blocks = if node.is::<Root>() {
+            ctx.blocks(node)
+        } else {
+            let values = ctx.convert(node);
+            wrap_inlines(values)
+        };
+        json!({
+            "pandoc-api-version": PANDOC_API_VERSION,
+            "meta": {},
+            "blocks": blocks,
+        })
+    }
+
+    fn add_builtins(&mut self) {
+        self.add::<Text>(|node, _| {
+            let text = node.cast::<Text>().unwrap();
+            vec![json!({ "t": "Str", "c": text.content })]
+        });
+        self.add::<TextSpecial>(|node, _| {
+            let text = node.cast::<TextSpecial>().unwrap();
+            vec![json!({ "t": "Str", "c": text.content })]
+        });
+
+        self.add::<Paragraph>(|node, ctx| vec![json!({ "t": "Para", "c": ctx.inlines(node) })]);
+        self.add::<ATXHeading>(|node, ctx| {
+            let level = node.cast::<ATXHeading>().unwrap().level;
+            vec![json!({ "t": "Header", "c": [level, attr(node), ctx.inlines(node)] })]
+        });
+        self.add::<SetextHeader>(|node, ctx| {
+            let level = node.cast::<SetextHeader>().unwrap().level;
+            vec![json!({ "t": "Header", "c": [level, attr(node), ctx.inlines(node)] })]
+        });
+        self.add::<ThematicBreak>(|_, _| vec![json!({ "t": "HorizontalRule" })]);
+        self.add::<Blockquote>(|node, ctx| {
+            vec![json!({ "t": "BlockQuote", "c": ctx.blocks(node) })]
+        });
+        self.add::<BulletList>(|node, ctx| {
+            let items = list_items(node, ctx);
+            vec![json!({ "t": "BulletList", "c": items })]
+        });
+        self.add::<OrderedList>(|node, ctx| {
+            let list = node.cast::<OrderedList>().unwrap();
+            let delim = match list.marker {
+                ')' => "OneParen",
+                _ => "Period",
+            };
+            let attrs = json!([list.start, { "t": "Decimal" }, { "t": delim }]);
+            let items = list_items(node, ctx);
+            vec![json!({ "t": "OrderedList", "c": [attrs, items] })]
+        });
+        // list items are handled by lists, this is only used if item is found elsewhere
+        self.add::<ListItem>(|node, ctx| ctx.blocks(node));
+        self.add::<TaskListItem>(task_list_item);
+        self.add::<CodeBlock>(|node, _| {
+            let code = node.cast::<CodeBlock>().unwrap();
+            let value = code.content.strip_suffix('\n').unwrap_or(&code.content);
+            vec![json!({ "t": "CodeBlock", "c": [attr(node), value] })]
+        });
+        self.add::<CodeFence>(|node, _| {
+            let fence = node.cast::<CodeFence>().unwrap();
+            let value = fence.content.strip_suffix('\n').unwrap_or(&fence.content);
+            let mut attr = attr(node);
+            if let Some(lang) = fence.info.split_whitespace().next() {
+                attr[1].as_array_mut().unwrap().insert(0, json!(lang));
+            }
+            vec![json!({ "t": "CodeBlock", "c": [attr, value] })]
+        });
+        self.add::<Definition>(|_, _| vec![]);
+
+        self.add::<Em>(|node, ctx| vec![json!({ "t": "Emph", "c": ctx.inlines(node) })]);
+        self.add::<Strong>(|node, ctx| vec![json!({ "t": "Strong", "c": ctx.inlines(node) })]);
+        self.add::<CodeInline>(|node, _| {
+            vec![json!({ "t": "Code", "c": [attr(node), node.collect_text()] })]
+        });
+        self.add::<Link>(|node, ctx| {
+            let link = node.cast::<Link>().unwrap();
+            let target = json!([link.url, link.title.as_deref().unwrap_or_default()]);
+            vec![json!({ "t": "Link", "c": [attr(node), ctx.inlines(node), target] })]
+        });
+        self.add::<Autolink>(|node, ctx| {
+            let link = node.cast::<Autolink>().unwrap();
+            vec![autolink(node, ctx, &link.url)]
+        });
+        self.add::<Image>(|node, ctx| {
+            let image = node.cast::<Image>().unwrap();
+            let target = json!([image.url, image.title.as_deref().unwrap_or_default()]);
+            vec![json!({ "t": "Image", "c": [attr(node), ctx.inlines(node), target] })]
+        });
+        self.add::<Hardbreak>(|_, _| vec![json!({ "t": "LineBreak" })]);
+        self.add::<Softbreak>(|_, _| vec![json!({ "t": "SoftBreak" })]);
+
+        self.add::<HtmlBlock>(|node, _| {
+            let html = node.cast::<HtmlBlock>().unwrap();
+            vec![json!({ "t": "RawBlock", "c": ["html", html.content] })]
+        });
+        self.add::<HtmlInline>(|node, _| {
+            let html = node.cast::<HtmlInline>().unwrap();
+            vec![json!({ "t": "RawInline", "c": ["html", html.content] })]
+        });
+
+        self.add::<Strikethrough>(|node, ctx| {
+            vec![json!({ "t": "Strikeout", "c": ctx.inlines(node) })]
+        });
+        self.add::<Table>(|node, ctx| {
+            let table = node.cast::<Table>().unwrap();
+            let alignments = table
+                .alignments
+                .iter()
+                .map(|align| match align {
+                    ColumnAlignment::None => "AlignDefault",
+                    ColumnAlignment::Left => "AlignLeft",
+                    ColumnAlignment::Right => "AlignRight",
+                    ColumnAlignment::Center => "AlignCenter",
+                })
+                .collect::<Vec<_>>();
+            let colspecs = alignments
+                .iter()
+                .map(|align| json!([{ "t": align }, { "t": "ColWidthDefault" }]))
+                .collect::<Vec<_>>();
+
+            let mut head = Vec::new();
+            let mut body = Vec::new();
+            for child in node.children.iter() {
+                let rows = child
+                    .children
+                    .iter()
+                    .filter(|row| row.is::<TableRow>())
+                    .map(|row| table_row(row, ctx))
+                    .collect::<Vec<_>>();
+                if child.is::<TableHead>() {
+                    head.extend(rows);
+                } else if child.is::<TableBody>() {
+                    body.extend(rows);
+                }
+            }
+
+            let no_attr = json!(["", [], []]);
+            vec![json!({
+                "t": "Table",
+                "c": [
+                    attr(node),
+                    [null, []],
+                    colspecs,
+                    [no_attr, head],
+                    [[no_attr, 0, [], body]],
+                    [no_attr, []],
+                ],
+            })]
+        });
+
+        self.add::<FootnoteReference>(|node, ctx| {
+            let id = node.cast::<FootnoteReference>().unwrap().id;
+            match ctx.notes.get(&id).copied() {
+                // a note referencing itself can't be nested into itself
+                Some(item) if ctx.open_notes.insert(id) => {
+                    let blocks = ctx.blocks(item);
+                    ctx.open_notes.remove(&id);
+                    vec![json!({ "t": "Note", "c": blocks })]
+                }
+                // content of inline footnotes is still inside if core rule didn't run
+                None if !node.children.is_empty() => {
+                    vec![json!({ "t": "Note", "c": [{ "t": "Para", "c": ctx.inlines(node) }] })]
+                }
+                _ => {
+                    let number = json!({ "t": "Str", "c": (id + 1).to_string() });
+                    vec![json!({ "t": "Superscript", "c": [number] })]
+                }
+            }
+        });
+        self.add::<FootnoteSection>(|_, _| vec![]);
+        // items are handled by references, this is only used if item is found elsewhere
+        self.add::<FootnoteItem>(|node, ctx| ctx.blocks(node));
+        self.add::<FootnoteBackref>(|_, _| vec![]);
+
+        #[cfg(feature = "linkify")]
+        self.add::<crate::plugins::extra::linkify::Linkified>(|node, ctx| {
+            let link = node
+                .cast::<crate::plugins::extra::linkify::Linkified>()
+                .unwrap();
+            vec![autolink(node, ctx, &link.url)]
+        });
+        #[cfg(feature = "syntect")]
+        self.add::<crate::plugins::extra::syntect::SyntectSnippet>(|node, _| {
+            // original code is lost after highlighting, so it's exported as html
+            let snippet = node
+                .cast::<crate::plugins::extra::syntect::SyntectSnippet>()
+                .unwrap();
+            vec![json!({ "t": "RawBlock", "c": ["html", snippet.html] })]
+        });
+    }
+}
+
+impl Default for PandocExporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// State of the export, passed to every [PandocHandler].
+pub struct PandocContext<'a> {
+    exporter: &'a PandocExporter,
+    notes: HashMap<usize, &'a Node>,
+    open_notes: HashSet<usize>,
+}
+
+impl PandocContext<'_> {
+    /// Convert children of a block node (e.g. blockquote), inline content
+    /// found directly inside (e.g. in tight lists) is wrapped into `Plain`.
+    pub fn blocks(&mut self, node: &Node) -> Vec<Value> {
+        wrap_inlines(self.children(node))
+    }
+
+    /// Convert children of an inline node (e.g. paragraph or link), text is split
+    /// into words and spaces the same way pandoc readers do it.
+    pub fn inlines(&mut self, node: &Node) -> Vec<Value> {
+        normalize_inlines(self.children(node))
+    }
+
+    fn children(&mut self, node: &Node) -> Vec<Value> {
+        let mut result = Vec::new();
+        for child in node.children.iter() {
+            stacker::maybe_grow(64 * 1024, 1024 * 1024, || {
+                result.extend(self.convert(child));
+            });
+        }
+        result
+    }
+
+    fn convert(&mut self, node: &Node) -> Vec<Value> {
+        match self.exporter.handlers.get(&node.node_type) {
+            Some(handler) => handler(node, self),
+            None => self.children(node),
+        }
+    }
+}
+
+impl std::fmt::Debug for PandocContext<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PandocContext").finish_non_exhaustive()
+    }
+}
+
+/// Convert node attributes into pandoc `Attr`: `[id, [classes], [[key, value]]]`.
+pub fn attr(node: &Node) -> Value {
+    let mut id = "";
+    let mut classes = Vec::new();
+    let mut pairs = Vec::new();
+    for (name, value) in node.attrs.iter() {
+        match *name {
+            "id" => id = value,
+            "class" => classes.extend(value.split_whitespace()),
+            _ => pairs.push([name, value.as_str()]),
+        }
+    }
+    json!([id, classes, pairs])
+}
+
+fn autolink(node: &Node, ctx: &mut PandocContext, url: &str) -> Value {
+    let mut attr = attr(node);
+    attr[1].as_array_mut().unwrap().push(json!("uri"));
+    json!({ "t": "Link", "c": [attr, ctx.inlines(node), [url, ""]] })
+}
+
+fn list_items(node: &Node, ctx: &mut PandocContext) -> Vec<Value> {
+    node.children
+        .iter()
+        .filter_map(|item| {
+            if item.is::<ListItem>() {
+                Some(Value::Array(ctx.blocks(item)))
+            } else if item.is::<TaskListItem>() {
+                Some(Value::Array(task_list_item(item, ctx)))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+// pandoc writes task list items as regular ones starting with a checkbox character
+fn task_list_item(node: &Node, ctx: &mut PandocContext) -> Vec<Value> {
+    let checkbox = if node.cast::<TaskListItem>().unwrap().checked {
+        "☒"
+    } else {
+        "☐"
+    };
+    let mut blocks = ctx.blocks(node);
+    if let Some(block) = blocks
+        .first_mut()
+        .filter(|block| block["t"] == "Plain" || block["t"] == "Para")
+    {
+        if let Some(inlines) = block["c"].as_array_mut() {
+            inlines.splice(
+                0..0,
+                [
+                    json!({ "t": "Str", "c": checkbox }),
+                    json!({ "t": "Space" }),
+                ],
+            );
+        }
+    }
+    blocks
+}
+
+fn table_row(node: &Node, ctx: &mut PandocContext) -> Value {
+    let cells = node
+        .children
+        .iter()
+        .map(|cell| {
+            let blocks = ctx.blocks(cell);
+            json!([attr(cell), { "t": "AlignDefault" }, 1, 1, blocks])
+        })
+        .collect::<Vec<_>>();
+    json!([attr(node), cells])
+}
+
+fn is_inline(value: &Value) -> bool {
+    matches!(
+        value["t"].as_str(),
+        Some(
+            "Str"
+                | "Emph"
+                | "Underline"
+                | "Strong"
+                | "Strikeout"
+                | "Superscript"
+                | "Subscript"
+                | "SmallCaps"
+                | "Quoted"
+                | "Cite"
+                | "Code"
+                | "Space"
+                | "SoftBreak"
+                | "LineBreak"
+                | "Math"
+                | "RawInline"
+                | "Link"
+                | "Image"
+                | "Note"
+                | "Span"
+        )
+    )
+}
+
+// Wrap runs of inlines between blocks into `Plain` (tight lists have their paragraphs unwrapped).
+fn wrap_inlines(values: Vec<Value>) -> Vec<Value> {
+    let mut result = Vec::new();
+    let mut inlines = Vec::new();
+    for value in values {
+        if is_inline(&value) {
+            inlines.push(value);
+        } else {
+            if !inlines.is_empty() {
+                let plain = normalize_inlines(std::mem::take(&mut inlines));
+                result.push(json!({ "t": "Plain", "c": plain }));
+            }
+            result.push(value);
+        }
+    }
+    if !inlines.is_empty() {
+        let plain = normalize_inlines(inlines);
+        result.push(json!({ "t": "Plain", "c": plain }));
+    }
+    result
+}
+
+// Merge adjacent text (text, escapes and entities are separate nodes in our AST),
+// then split it into `Str` and `Space`.
+fn normalize_inlines(values: Vec<Value>) -> Vec<Value> {
+    let mut result = Vec::with_capacity(values.len());
+    let mut text = String::new();
+    for value in values {
+        if value["t"] == "Str" {
+            text.push_str(value["c"].as_str().unwrap_or_default());
+        } else {
+            split_words(&std::mem::take(&mut text), &mut result);
+            result.push(value);
+        }
+    }
+    split_words(&text, &mut result);
+    result
+}
+
+fn split_words(text: &str, result: &mut Vec<Value>) {
+    let mut word = String::new();
+    for ch in text.chars() {
+        if ch == ' ' || ch == '\n' {
+            if !word.is_empty() {
+                result.push(json!({ "t": "Str", "c": std::mem::take(&mut word) }));
+            }
+            let space = if ch == '\n' { "SoftBreak" } else { "Space" };
+            match result.last_mut() {
+                // consecutive spaces are collapsed, newline takes precedence
+                Some(last) if last["t"] == "Space" || last["t"] == "SoftBreak" => {
+                    if space == "SoftBreak" {
+                        *last = json!({ "t": space });
+                    }
+                }
+                _ => result.push(json!({ "t": space })),
+            }
+        } else {
+            word.push(ch);
+        }
+    }
+    if !word.is_empty() {
+        result.push(json!({ "t": "Str", "c": word }));
+    }
+}
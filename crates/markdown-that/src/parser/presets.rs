@@ -0,0 +1,161 @@
+//! Ready-made parser configurations, same as presets in markdown-it.
+//!
+//! ```rust
+//! use markdown_that::MarkdownThat;
+//! use markdown_that::parser::presets::{Options, Preset};
+//!
+//! let md = MarkdownThat::commonmark();
+//! assert_eq!(md.render("*hello*<br>"), "<p><em>hello</em><br></p>\n");
+//!
+//! // options are applied on top of preset syntax, like in markdown-it constructor
+//! let md = MarkdownThat::with_preset(Preset::CommonMark, &Options {
+//!     html: false,
+//!     breaks: true,
+//!     ..Preset::CommonMark.options()
+//! });
+//! assert_eq!(md.render("foo\n<br>"), "<p>foo<br />\n&lt;br&gt;</p>\n");
+//! ```
+use crate::MarkdownThat;
+use crate::parser::renderer::{HtmlRenderOptions, SoftbreakMode};
+use crate::plugins;
+
+/// Set of syntax enabled in a preset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Preset {
+    /// Paragraphs and plain text only, everything else is added by options or plugins.
+    Zero,
+    /// Strict [CommonMark](https://spec.commonmark.org/).
+    CommonMark,
+    /// CommonMark with [GitHub extensions](crate::plugins::gfm), autolinks
+    /// are enabled by [Options::linkify].
+    Gfm,
+}
+
+impl Preset {
+    /// Default options of the preset.
+    pub fn options(self) -> Options {
+        match self {
+            Self::Zero => Options::default(),
+            Self::CommonMark => Options {
+                html: true,
+                xhtml: true,
+                ..Options::default()
+            },
+            Self::Gfm => Options {
+                html: true,
+                linkify: true,
+                xhtml: true,
+                ..Options::default()
+            },
+        }
+    }
+}
+
+/// Parser and renderer settings, mirrors markdown-it constructor options.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Options {
+    /// Enable raw HTML in the source.
+    pub html: bool,
+    /// Convert URL-like text to links (requires `linkify` feature, ignored otherwise,
+    /// [Preset::Gfm] uses its own autolinks that work without it).
+    pub linkify: bool,
+    /// Enable typographic replacements and smart quotes.
+    pub typographer: bool,
+    /// Render soft line breaks as `<br>`.
+    pub breaks: bool,
+    /// Close single tags like this: `<br />`.
+    pub xhtml: bool,
+    /// Highlight code blocks (requires `syntect` feature, ignored otherwise).
+    pub highlight: bool,
+}
+
+impl Options {
+    /// Renderer settings used by [MarkdownThat::render] and [Node::render](crate::Node::render).
+    pub fn render_options(&self) -> HtmlRenderOptions {
+        HtmlRenderOptions {
+            xhtml: self.xhtml,
+            softbreak: if self.breaks {
+                SoftbreakMode::Break
+            } else {
+                SoftbreakMode::Newline
+            },
+            ..Default::default()
+        }
+    }
+}
+
+impl MarkdownThat {
+    /// Create a parser with [Preset::Zero] syntax and default options.
+    pub fn zero() -> Self {
+        Self::with_preset(Preset::Zero, &Preset::Zero.options())
+    }
+
+    /// Create a parser with [Preset::CommonMark] syntax and default options.
+    pub fn commonmark() -> Self {
+        Self::with_preset(Preset::CommonMark, &Preset::CommonMark.options())
+    }
+
+    /// Create a parser with [Preset::Gfm] syntax and default options.
+    pub fn gfm() -> Self {
+        Self::with_preset(Preset::Gfm, &Preset::Gfm.options())
+    }
+
+    /// Create a parser with syntax of `preset`, configured with `options`.
+    pub fn with_preset(preset: Preset, options: &Options) -> Self {
+        let mut md = Self::new();
+        // plugins can change render options (e.g. tagfilter), so they're set first
+        md.ext.insert(HtmlRenderOptions {
+            gfm: preset == Preset::Gfm,
+            ..options.render_options()
+        });
+
+        match preset {
+            Preset::Zero => {
+                plugins::cmark::block::paragraph::add(&mut md);
+            }
+            Preset::CommonMark => {
+                plugins::cmark::add(&mut md);
+            }
+            Preset::Gfm => {
+                plugins::cmark::add(&mut md);
+                plugins::extra::tables::add(&mut md);
+                plugins::gfm::tasklist::add_with(&mut md, Default::default());
+                plugins::gfm::strikethrough::add(&mut md);
+                plugins::gfm::tagfilter::add(&mut md);
+            }
+        }
+
+        if options.html {
+            plugins::html::add(&mut md);
+        }
+        if options.linkify {
+            match preset {
+                Preset::Gfm => plugins::gfm::autolink::add(&mut md),
+                #[cfg(feature = "linkify")]
+                _ => plugins::extra::linkify::add(&mut md),
+                #[cfg(not(feature = "linkify"))]
+                _ => (),
+            }
+        }
+        if options.typographer {
+            plugins::extra::typographer::add(&mut md);
+            plugins::extra::smartquotes::add(&mut md);
+        }
+        #[cfg(feature = "syntect")]
+        if options.highlight {
+            plugins::extra::syntect::add(&mut md);
+        }
+        md
+    }
+
+    /// Parse `src` and render it to HTML, same as `parse(src).render()`.
+    ///
+    /// Parser created from a [preset](Preset) keeps settings from [Options] in the
+    /// root node of the AST, so [Node::render](crate::Node::render) uses them.
+    /// Parser created with [MarkdownThat::new] falls back to
+    /// [HtmlRenderOptions::default], which has `xhtml` turned off (`<br>`),
+    /// while [MarkdownThat::commonmark] renders `<br />`.
+    pub fn render(&self, src: &str) -> String {
+        self.parse(src).render()
+    }
+}
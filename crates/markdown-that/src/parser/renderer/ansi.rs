@@ -0,0 +1,400 @@
+use std::borrow::Cow;
+
+use super::block_writer::BlockWriter;
+use super::{RenderFallback, RenderFormat, RenderRegistry};
+use crate::Node;
+use crate::parser::extset::RenderExtSet;
+
+/// Settings for [AnsiRenderer].
+#[derive(Debug, Clone)]
+pub struct AnsiRenderOptions {
+    /// Wrap text to this many columns (including list and blockquote prefixes),
+    /// `None` disables wrapping. Every character is counted as one column.
+    pub width: Option<usize>,
+    /// Write links as [OSC 8](https://gist.github.com/egmontkob/eb114294efbcd5adb1944c9f3cb5feda)
+    /// hyperlinks, otherwise urls are printed after link text.
+    pub hyperlinks: bool,
+}
+
+impl Default for AnsiRenderOptions {
+    fn default() -> Self {
+        Self {
+            width: Some(80),
+            hyperlinks: true,
+        }
+    }
+}
+
+/// Each node outputs text styled for terminals using this API.
+///
+/// Styles are [SGR](https://en.wikipedia.org/wiki/ANSI_escape_code#SGR) parameters
+/// (e.g. `"1"` for bold), they are applied lazily before visible text, and reset
+/// at the end of every line, so list and blockquote prefixes are never styled.
+///
+/// Block nodes should be wrapped in [open_block](AnsiRenderer::open_block) and
+/// [close_block](AnsiRenderer::close_block).
+///
+/// Nodes are rendered with [NodeValue::render_ansi](crate::NodeValue::render_ansi),
+/// unless a handler for them is added to [registry](AnsiRenderer::registry_mut).
+#[derive(Debug)]
+pub struct AnsiRenderer {
+    out: BlockWriter,
+    options: AnsiRenderOptions,
+    // requested state
+    styles: Vec<String>,
+    link: Option<String>,
+    // state currently written to the output
+    applied_styles: Vec<String>,
+    applied_link: Option<String>,
+    pending_space: bool,
+    column: usize,
+    wrap: bool,
+    registry: RenderRegistry<AnsiRenderer>,
+    ext: RenderExtSet,
+}
+
+impl AnsiRenderer {
+    pub fn new(options: AnsiRenderOptions) -> Self {
+        Self {
+            out: BlockWriter::new(false),
+            options,
+            styles: Vec::new(),
+            link: None,
+            applied_styles: Vec::new(),
+            applied_link: None,
+            pending_space: false,
+            column: 0,
+            wrap: true,
+            registry: RenderRegistry::with_fallback(RenderFallback::Handler(|node, fmt| {
+                node.node_value.render_ansi(node, fmt);
+            })),
+            ext: RenderExtSet::new(),
+        }
+    }
+
+    /// Handlers used to render nodes, custom ones can be added here.
+    pub fn registry_mut(&mut self) -> &mut RenderRegistry<Self> {
+        &mut self.registry
+    }
+
+    /// Options this renderer was created with.
+    pub fn options(&self) -> &AnsiRenderOptions {
+        &self.options
+    }
+
+    /// Render a single node (and its children).
+    pub fn render(&mut self, node: &Node) {
+        self.render_node(node);
+    }
+
+    /// Loop through child nodes and render each one.
+    pub fn contents(&mut self, nodes: &[Node]) {
+        for node in nodes.iter() {
+            self.render(node);
+        }
+    }
+
+    /// Start a new block, separating it from the previous one in the same container.
+    pub fn open_block(&mut self) {
+        self.cr();
+        self.out.open_block();
+    }
+
+    /// Finish a block started with [open_block](AnsiRenderer::open_block).
+    pub fn close_block(&mut self) {
+        self.cr();
+    }
+
+    /// Start a container block (e.g. blockquote or list item), every line inside
+    /// will be prefixed with `first` (first line) or `rest` (the following lines).
+    /// Prefixes may contain escape sequences.
+    ///
+    /// Blocks inside are separated with blank lines unless `tight` is set.
+    pub fn open_container(&mut self, first: &str, rest: &str, tight: bool) {
+        self.cr();
+        self.out.open_container(first, rest, tight);
+    }
+
+    /// Finish a container block started with [open_container](AnsiRenderer::open_container).
+    pub fn close_container(&mut self) {
+        self.cr();
+        self.out.close_container();
+    }
+
+    /// Write line break, ignored if we're at the start of a line already.
+    pub fn cr(&mut self) {
+        if !self.out.is_line_start() {
+            self.end_line();
+            self.out.write("\n");
+        }
+        self.pending_space = false;
+    }
+
+    /// Write text with current styles, wrapping it at word boundaries.
+    /// Control characters (except tabs) are replaced with U+FFFD.
+    pub fn text(&mut self, text: &str) {
+        for (idx, line) in text.split('\n').enumerate() {
+            if idx > 0 {
+                self.newline();
+            }
+            for (idx, word) in line.split(' ').enumerate() {
+                if idx > 0 {
+                    self.pending_space = true;
+                }
+                if word.is_empty() {
+                    continue;
+                }
+                if let Some(width) = self.options.width.filter(|_| self.wrap) {
+                    let space = self.pending_space as usize;
+                    if !self.out.is_line_start()
+                        && self.column + space + Self::visible_width(word) > width
+                    {
+                        self.cr();
+                    }
+                }
+                self.emit(word);
+            }
+        }
+    }
+
+    /// Write text with current styles as is, without wrapping (e.g. code blocks).
+    pub fn text_nowrap(&mut self, text: &str) {
+        for (idx, line) in text.split('\n').enumerate() {
+            if idx > 0 {
+                self.newline();
+            }
+            if !line.is_empty() {
+                self.emit(line);
+            }
+        }
+    }
+
+    /// Write text that is already styled (e.g. captured output), it's written as is.
+    pub fn text_raw(&mut self, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+        self.sync_state(text);
+        let result = &self.out.result;
+        let last_line = &result[result.rfind('\n').map_or(0, |idx| idx + 1)..];
+        self.column = Self::visible_width(last_line);
+    }
+
+    /// Apply SGR style (e.g. `"1"` for bold, `"3"` for italic), until
+    /// matching [pop_style](AnsiRenderer::pop_style) is called.
+    pub fn push_style(&mut self, sgr: &str) {
+        self.styles.push(sgr.to_owned());
+    }
+
+    /// Remove style added with [push_style](AnsiRenderer::push_style).
+    pub fn pop_style(&mut self) {
+        self.styles.pop();
+    }
+
+    /// Make the following text a hyperlink (if enabled in options).
+    pub fn open_link(&mut self, url: &str) {
+        if self.options.hyperlinks {
+            self.link = Some(replace_control_chars(url).into_owned());
+        }
+    }
+
+    /// Finish a hyperlink started with [open_link](AnsiRenderer::open_link).
+    pub fn close_link(&mut self) {
+        self.link = None;
+    }
+
+    /// Number of columns available for content on the current line,
+    /// `None` if wrapping is disabled.
+    pub fn available_width(&self) -> Option<usize> {
+        let width = self.options.width.filter(|_| self.wrap)?;
+        let used = if self.out.is_line_start() {
+            Self::visible_width(&self.out.pending_prefix())
+        } else {
+            self.column
+        };
+        Some(width.saturating_sub(used))
+    }
+
+    /// Render into a separate buffer (without any container prefixes and wrapping)
+    /// and return the result, used to measure content, e.g. to align table columns.
+    pub fn capture(&mut self, f: impl FnOnce(&mut Self)) -> String {
+        let old_out = std::mem::replace(&mut self.out, BlockWriter::new(true));
+        let old_applied_styles = std::mem::take(&mut self.applied_styles);
+        let old_applied_link = self.applied_link.take();
+        let old_pending_space = std::mem::take(&mut self.pending_space);
+        let old_column = std::mem::take(&mut self.column);
+        let old_wrap = std::mem::replace(&mut self.wrap, false);
+
+        f(self);
+        self.end_line();
+
+        self.applied_styles = old_applied_styles;
+        self.applied_link = old_applied_link;
+        self.pending_space = old_pending_space;
+        self.column = old_column;
+        self.wrap = old_wrap;
+        std::mem::replace(&mut self.out, old_out).result
+    }
+
+    /// Extension set to store custom stuff.
+    pub fn ext(&mut self) -> &mut RenderExtSet {
+        &mut self.ext
+    }
+
+    /// Width of the text as displayed in terminal, escape sequences are skipped.
+    pub fn visible_width(text: &str) -> usize {
+        let mut width = 0;
+        let mut chars = text.chars().peekable();
+        while let Some(ch) = chars.next() {
+            if ch != '\x1b' {
+                width += 1;
+                continue;
+            }
+            match chars.next() {
+                // CSI, e.g. `ESC [ 1 m`
+                Some('[') => {
+                    for ch in chars.by_ref() {
+                        if ('\x40'..='\x7e').contains(&ch) {
+                            break;
+                        }
+                    }
+                }
+                // OSC, e.g. `ESC ] 8 ; ; url ESC \`
+                Some(']') => {
+                    while let Some(ch) = chars.next() {
+                        if ch == '\x07' {
+                            break;
+                        }
+                        if ch == '\x1b' && chars.peek() == Some(&'\\') {
+                            chars.next();
+                            break;
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        width
+    }
+
+    // Write visible text, applying pending space and styles before it.
+    fn emit(&mut self, text: &str) {
+        let text = &*replace_control_chars(text);
+        let line_start = self.out.is_line_start();
+        let space = self.pending_space && !line_start;
+        self.pending_space = false;
+
+        if line_start {
+            self.column = Self::visible_width(&self.out.pending_prefix());
+        }
+        if space {
+            // space only gets styles shared by the text on both sides of it,
+            // and none at all between different links
+            let same_link = self.link == self.applied_link;
+            let common = self
+                .styles
+                .iter()
+                .zip(self.applied_styles.iter())
+                .take_while(|(a, b)| same_link && a == b)
+                .count();
+            let styles = self.styles.split_off(common);
+            let link = if same_link { None } else { self.link.take() };
+            self.sync_state(" ");
+            self.styles.extend(styles);
+            if link.is_some() {
+                self.link = link;
+            }
+            self.column += 1;
+        }
+        self.sync_state(text);
+        self.column += Self::visible_width(text);
+    }
+
+    // Write escape sequences needed to get from applied state to requested one, then `text`.
+    fn sync_state(&mut self, text: &str) {
+        let mut result = String::new();
+        if self.applied_link != self.link {
+            if self.applied_link.is_some() {
+                result.push_str(OSC8_CLOSE);
+            }
+            if let Some(url) = &self.link {
+                result.push_str(&format!("\x1b]8;;{url}\x1b\\"));
+            }
+            self.applied_link.clone_from(&self.link);
+        }
+        if self.applied_styles != self.styles {
+            // nested styles are added on top, anything else requires a reset
+            let keep = self.styles.starts_with(&self.applied_styles);
+            if !keep {
+                result.push_str(SGR_RESET);
+            }
+            let skip = if keep { self.applied_styles.len() } else { 0 };
+            for style in self.styles.iter().skip(skip) {
+                result.push_str(&format!("\x1b[{style}m"));
+            }
+            self.applied_styles.clone_from(&self.styles);
+        }
+        result.push_str(text);
+        self.out.write(&result);
+    }
+
+    // Write line break unconditionally, so empty lines are kept.
+    fn newline(&mut self) {
+        self.end_line();
+        self.out.write("\n");
+        self.pending_space = false;
+    }
+
+    // Reset styles and links before line break, so they don't leak into prefixes.
+    fn end_line(&mut self) {
+        let mut result = String::new();
+        if !self.applied_styles.is_empty() {
+            result.push_str(SGR_RESET);
+            self.applied_styles.clear();
+        }
+        if self.applied_link.is_some() {
+            result.push_str(OSC8_CLOSE);
+            self.applied_link = None;
+        }
+        if !result.is_empty() {
+            self.out.write(&result);
+        }
+    }
+}
+
+// Control characters in the document (e.g. ESC) would be interpreted by the terminal,
+// so they are replaced with U+FFFD, only tabs are kept.
+fn replace_control_chars(text: &str) -> Cow<'_, str> {
+    let is_unsafe = |ch: char| ch.is_control() && ch != '\t';
+    if text.contains(is_unsafe) {
+        Cow::Owned(text.replace(is_unsafe, "\u{FFFD}"))
+    } else {
+        Cow::Borrowed(text)
+    }
+}
+
+const SGR_RESET: &str = "\x1b[0m";
+const OSC8_CLOSE: &str = "\x1b]8;;\x1b\\";
+
+impl RenderFormat for AnsiRenderer {
+    fn registry(&self) -> &RenderRegistry<Self> {
+        &self.registry
+    }
+
+    fn write_text(&mut self, text: &str) {
+        self.text(text);
+    }
+}
+
+impl Default for AnsiRenderer {
+    fn default() -> Self {
+        Self::new(AnsiRenderOptions::default())
+    }
+}
+
+impl From<AnsiRenderer> for String {
+    fn from(mut f: AnsiRenderer) -> Self {
+        f.end_line();
+        f.out.result
+    }
+}
@@ -0,0 +1,96 @@
+use super::{AnsiRenderer, ChatRenderer, MarkdownRenderer, TextRenderer, TypstRenderer};
+use crate::Node;
+use crate::parser::extset::RenderExtSet;
+
+/// Block-level API shared by [MarkdownRenderer], [TextRenderer], [AnsiRenderer],
+/// [TypstRenderer] and [ChatRenderer].
+///
+/// Nodes laid out the same way in all these formats (e.g. paragraphs, blockquotes,
+/// list items) can be written once, generic over this trait.
+pub trait BlockRenderer {
+    /// Loop through child nodes and render each one.
+    fn contents(&mut self, nodes: &[Node]);
+
+    /// Start a new block, separating it from the previous one in the same container.
+    fn open_block(&mut self);
+
+    /// Finish a block started with [open_block](BlockRenderer::open_block).
+    fn close_block(&mut self);
+
+    /// Start a container block, every line inside will be prefixed with `first`
+    /// (first line) or `rest` (the following lines).
+    fn open_container(&mut self, first: &str, rest: &str, tight: bool);
+
+    /// Finish a container block started with [open_container](BlockRenderer::open_container).
+    fn close_container(&mut self);
+
+    /// Write line break, ignored if we're at the start of a line already.
+    fn cr(&mut self);
+
+    /// Write plain text (escaped the way the format requires).
+    fn text(&mut self, text: &str);
+
+    /// Extension set to store custom stuff.
+    fn ext(&mut self) -> &mut RenderExtSet;
+
+    /// Render children of `node` as a single block.
+    fn block(&mut self, node: &Node) {
+        self.open_block();
+        self.contents(&node.children);
+        self.close_block();
+    }
+
+    /// Render children of `node` inside of a container block,
+    /// see [open_container](BlockRenderer::open_container).
+    fn container(&mut self, node: &Node, first: &str, rest: &str, tight: bool) {
+        self.open_container(first, rest, tight);
+        self.contents(&node.children);
+        self.close_container();
+    }
+}
+
+macro_rules! impl_block_renderer {
+    ($($ty:ty),*) => {$(
+        impl BlockRenderer for $ty {
+            fn contents(&mut self, nodes: &[Node]) {
+                <$ty>::contents(self, nodes);
+            }
+
+            fn open_block(&mut self) {
+                <$ty>::open_block(self);
+            }
+
+            fn close_block(&mut self) {
+                <$ty>::close_block(self);
+            }
+
+            fn open_container(&mut self, first: &str, rest: &str, tight: bool) {
+                <$ty>::open_container(self, first, rest, tight);
+            }
+
+            fn close_container(&mut self) {
+                <$ty>::close_container(self);
+            }
+
+            fn cr(&mut self) {
+                <$ty>::cr(self);
+            }
+
+            fn text(&mut self, text: &str) {
+                <$ty>::text(self, text);
+            }
+
+            fn ext(&mut self) -> &mut RenderExtSet {
+                <$ty>::ext(self)
+            }
+        }
+    )*};
+}
+
+impl_block_renderer!(
+    MarkdownRenderer<'_>,
+    TextRenderer,
+    AnsiRenderer,
+    TypstRenderer,
+    ChatRenderer
+);
@@ -0,0 +1,155 @@
+/// Output buffer shared by text-based renderers (Markdown, plain text).
+///
+/// It keeps track of container blocks (blockquotes, list items), writes
+/// their prefixes at the start of every line, and separates sibling blocks
+/// with blank lines (or a single newline inside tight containers).
+#[derive(Debug)]
+pub(super) struct BlockWriter {
+    pub result: String,
+    containers: Vec<Container>,
+    line_start: bool,
+}
+
+#[derive(Debug)]
+struct Container {
+    first: String,
+    rest: String,
+    first_used: bool,
+    tight: bool,
+    has_content: bool,
+    list_marker: Option<char>,
+}
+
+impl Container {
+    fn new(first: &str, rest: &str, tight: bool) -> Self {
+        Self {
+            first: first.to_owned(),
+            rest: rest.to_owned(),
+            first_used: false,
+            tight,
+            has_content: false,
+            list_marker: None,
+        }
+    }
+}
+
+impl BlockWriter {
+    pub fn new(tight: bool) -> Self {
+        Self {
+            result: String::new(),
+            containers: vec![Container::new("", "", tight)],
+            line_start: true,
+        }
+    }
+
+    pub fn is_line_start(&self) -> bool {
+        self.line_start
+    }
+
+    /// Prefixes that will be written before the next line.
+    pub fn pending_prefix(&self) -> String {
+        self.containers
+            .iter()
+            .map(|c| if c.first_used { &*c.rest } else { &*c.first })
+            .collect()
+    }
+
+    pub fn open_block(&mut self) {
+        let container = self.containers.last_mut().unwrap();
+        let separate = container.has_content;
+        let tight = container.tight;
+        container.has_content = true;
+        container.list_marker = None;
+
+        if separate {
+            self.cr();
+            if !tight {
+                self.write_prefixes(true);
+                self.result.push('\n');
+            }
+        }
+    }
+
+    pub fn close_list(&mut self, marker: char) {
+        self.containers.last_mut().unwrap().list_marker = Some(marker);
+    }
+
+    pub fn previous_list_marker(&self) -> Option<char> {
+        self.containers.last().unwrap().list_marker
+    }
+
+    pub fn open_container(&mut self, first: &str, rest: &str, tight: bool) {
+        self.open_block();
+        self.containers.push(Container::new(first, rest, tight));
+    }
+
+    /// Start a container that continues the current block instead of being separated
+    /// from it (e.g. indented body after an opening line).
+    pub fn open_nested_container(&mut self, first: &str, rest: &str, tight: bool) {
+        self.cr();
+        self.containers.push(Container::new(first, rest, tight));
+    }
+
+    pub fn close_container(&mut self) {
+        debug_assert!(self.containers.len() > 1, "unbalanced close_container");
+        let container = self.containers.last().unwrap();
+        if !container.first_used && !container.first.trim_end().is_empty() {
+            // empty container, e.g. `>` or `-`, must still be written out
+            self.cr();
+            self.write_prefixes(true);
+            self.result.push('\n');
+        }
+        self.containers.pop();
+        self.cr();
+    }
+
+    pub fn cr(&mut self) {
+        if !self.line_start {
+            self.result.push('\n');
+            self.line_start = true;
+        }
+    }
+
+    pub fn write(&mut self, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+
+        self.containers.last_mut().unwrap().has_content = true;
+
+        for (idx, line) in text.split('\n').enumerate() {
+            if idx > 0 {
+                if self.line_start {
+                    // empty line, only prefixes are written
+                    self.write_prefixes(true);
+                }
+                self.result.push('\n');
+                self.line_start = true;
+            }
+
+            if !line.is_empty() {
+                if self.line_start {
+                    self.write_prefixes(false);
+                    self.line_start = false;
+                }
+                self.result.push_str(line);
+            }
+        }
+    }
+
+    fn write_prefixes(&mut self, trim: bool) {
+        let start = self.result.len();
+        for container in self.containers.iter_mut() {
+            if container.first_used {
+                self.result.push_str(&container.rest);
+            } else {
+                self.result.push_str(&container.first);
+                container.first_used = true;
+            }
+        }
+        if trim {
+            let len = self.result[start..].trim_end().len();
+            self.result.truncate(start + len);
+        }
+    }
+}
@@ -0,0 +1,452 @@
+use super::block_writer::BlockWriter;
+use super::gemtext::table_text;
+use super::{RenderFallback, RenderFormat, RenderRegistry};
+use crate::Node;
+use crate::common::utils::{escape_discord, escape_slack, unescape_all};
+use crate::parser::extset::RenderExtSet;
+use crate::parser::inline::{Text, TextSpecial};
+use crate::plugins::cmark::block::blockquote::Blockquote;
+use crate::plugins::cmark::block::code::CodeBlock;
+use crate::plugins::cmark::block::fence::CodeFence;
+use crate::plugins::cmark::block::heading::ATXHeading;
+use crate::plugins::cmark::block::hr::ThematicBreak;
+use crate::plugins::cmark::block::lheading::SetextHeader;
+use crate::plugins::cmark::block::list::{BulletList, ListItem, ListRenderContext, OrderedList};
+use crate::plugins::cmark::block::paragraph::Paragraph;
+use crate::plugins::cmark::inline::autolink::Autolink;
+use crate::plugins::cmark::inline::backticks::CodeInline;
+use crate::plugins::cmark::inline::emphasis::{Em, Strong};
+use crate::plugins::cmark::inline::image::Image;
+use crate::plugins::cmark::inline::link::Link;
+use crate::plugins::cmark::inline::newline::{Hardbreak, Softbreak};
+use crate::plugins::extra::footnotes::{FootnoteItem, FootnoteReference, FootnoteSection};
+use crate::plugins::extra::strikethrough::Strikethrough;
+use crate::plugins::extra::tables::Table;
+use crate::plugins::gfm::tasklist::TaskListItem;
+
+/// Markup dialect used by [ChatRenderer].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ChatDialect {
+    /// Slack [mrkdwn](https://api.slack.com/reference/surfaces/formatting):
+    /// `*bold*`, `_italic_`, `~strike~`, `<url|text>` (default).
+    #[default]
+    Slack,
+    /// Discord markdown: `**bold**`, `*italic*`, `~~strike~~`, `[text](url)`,
+    /// and headings up to level 3.
+    Discord,
+}
+
+/// Settings for [ChatRenderer].
+#[derive(Debug, Default, Clone)]
+pub struct ChatRenderOptions {
+    /// Markup dialect of the chat.
+    pub dialect: ChatDialect,
+}
+
+/// Renderer producing chat messages (Slack or Discord markup).
+///
+/// Chats don't support most of the block syntax: headings are written as bold
+/// lines (except for Discord, which has 3 levels of them), lists use plain text
+/// markers, tables are written as code blocks, and images become links.
+///
+/// Every node type has a handler in [registry](ChatRenderer::registry_mut), and
+/// nodes without one are replaced with their children.
+#[derive(Debug)]
+pub struct ChatRenderer {
+    out: BlockWriter,
+    options: ChatRenderOptions,
+    // nesting level of each style, so nested styles don't repeat delimiters
+    bold: usize,
+    italic: usize,
+    strike: usize,
+    registry: RenderRegistry<ChatRenderer>,
+    ext: RenderExtSet,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Style {
+    Bold,
+    Italic,
+    Strike,
+}
+
+impl ChatRenderer {
+    pub fn new(options: ChatRenderOptions) -> Self {
+        Self {
+            out: BlockWriter::new(false),
+            options,
+            bold: 0,
+            italic: 0,
+            strike: 0,
+            registry: Self::default_registry(),
+            ext: RenderExtSet::new(),
+        }
+    }
+
+    /// Handlers for CommonMark nodes, strikethrough and tables.
+    pub fn default_registry() -> RenderRegistry<Self> {
+        let mut registry = RenderRegistry::<Self>::with_fallback(RenderFallback::Children);
+
+        registry.add::<Text>(|node, fmt| fmt.text(&node.cast::<Text>().unwrap().content));
+        registry.add::<TextSpecial>(|node, fmt| {
+            fmt.text(&node.cast::<TextSpecial>().unwrap().content);
+        });
+        // newlines are kept by chats, so paragraphs are unwrapped
+        registry.add::<Softbreak>(|_, fmt| fmt.text_raw(" "));
+        registry.add::<Hardbreak>(|_, fmt| fmt.text_raw("\n"));
+
+        registry.add::<Paragraph>(|node, fmt| {
+            fmt.open_block();
+            fmt.render_children(node);
+            fmt.close_block();
+        });
+
+        registry.add::<ATXHeading>(|node, fmt| {
+            heading(node, fmt, node.cast::<ATXHeading>().unwrap().level);
+        });
+        registry.add::<SetextHeader>(|node, fmt| {
+            heading(node, fmt, node.cast::<SetextHeader>().unwrap().level);
+        });
+
+        registry.add::<ThematicBreak>(|_, fmt| {
+            fmt.open_block();
+            fmt.text_raw("---");
+            fmt.close_block();
+        });
+
+        registry.add::<Blockquote>(|node, fmt| {
+            fmt.open_container("> ", "> ", false);
+            fmt.render_children(node);
+            fmt.close_container();
+        });
+
+        registry.add::<BulletList>(|node, fmt| {
+            let marker = match fmt.options.dialect {
+                ChatDialect::Slack => '•',
+                ChatDialect::Discord => '-',
+            };
+            list(node, fmt, marker, None);
+        });
+        registry.add::<OrderedList>(|node, fmt| {
+            let start = node.cast::<OrderedList>().unwrap().start;
+            list(node, fmt, '.', Some(start));
+        });
+        registry.add::<ListItem>(|node, fmt| {
+            ListRenderContext::render_item(node, fmt, "");
+        });
+        registry.add::<TaskListItem>(|node, fmt| {
+            let checkbox = node.cast::<TaskListItem>().unwrap().marker();
+            ListRenderContext::render_item(node, fmt, checkbox);
+        });
+
+        registry.add::<FootnoteReference>(|node, fmt| {
+            let id = node.cast::<FootnoteReference>().unwrap().id;
+            fmt.text(&format!("[{}]", id + 1));
+        });
+        // footnotes are numbered in order, so the section is an ordered list
+        registry.add::<FootnoteSection>(|node, fmt| list(node, fmt, '.', Some(1)));
+        registry.add::<FootnoteItem>(|node, fmt| {
+            ListRenderContext::render_item(node, fmt, "");
+        });
+
+        registry.add::<CodeFence>(|node, fmt| {
+            let fence = node.cast::<CodeFence>().unwrap();
+            let info = unescape_all(&fence.info);
+            fmt.code_block(&fence.content, info.split_whitespace().next());
+        });
+        registry.add::<CodeBlock>(|node, fmt| {
+            fmt.code_block(&node.cast::<CodeBlock>().unwrap().content, None);
+        });
+        #[cfg(feature = "syntect")]
+        registry.add::<crate::plugins::extra::syntect::SyntectSnippet>(|node, fmt| {
+            let snippet = node
+                .cast::<crate::plugins::extra::syntect::SyntectSnippet>()
+                .unwrap();
+            fmt.code_block(
+                snippet.code().trim_matches('\n'),
+                snippet.language.as_deref(),
+            );
+        });
+        registry.add::<Table>(|node, fmt| {
+            let alignments = &node.cast::<Table>().unwrap().alignments;
+            fmt.code_block(&table_text(node, alignments), None);
+        });
+
+        registry.add::<CodeInline>(|node, fmt| {
+            let content = node.collect_text();
+            fmt.text_raw("`");
+            fmt.code_text(&content);
+            fmt.text_raw("`");
+        });
+
+        registry.add::<Strong>(|node, fmt| fmt.styled(node, Style::Bold));
+        registry.add::<Em>(|node, fmt| fmt.styled(node, Style::Italic));
+        registry.add::<Strikethrough>(|node, fmt| fmt.styled(node, Style::Strike));
+
+        registry.add::<Link>(|node, fmt| link(node, fmt, &node.cast::<Link>().unwrap().url));
+        registry.add::<Autolink>(|node, fmt| {
+            link(node, fmt, &node.cast::<Autolink>().unwrap().url);
+        });
+        #[cfg(feature = "linkify")]
+        registry.add::<crate::plugins::extra::linkify::Linkified>(|node, fmt| {
+            let url = &node
+                .cast::<crate::plugins::extra::linkify::Linkified>()
+                .unwrap()
+                .url;
+            link(node, fmt, url);
+        });
+        registry.add::<Image>(|node, fmt| {
+            let url = &node.cast::<Image>().unwrap().url;
+            fmt.link(url, &node.collect_text());
+        });
+
+        registry
+    }
+
+    /// Handlers used to render nodes, custom ones can be added here.
+    pub fn registry_mut(&mut self) -> &mut RenderRegistry<Self> {
+        &mut self.registry
+    }
+
+    /// Options this renderer was created with.
+    pub fn options(&self) -> &ChatRenderOptions {
+        &self.options
+    }
+
+    /// Render a single node (and its children).
+    pub fn render(&mut self, node: &Node) {
+        self.render_node(node);
+    }
+
+    /// Loop through child nodes and render each one.
+    pub fn contents(&mut self, nodes: &[Node]) {
+        for node in nodes.iter() {
+            self.render(node);
+        }
+    }
+
+    /// Start a new block, separating it from the previous one in the same container.
+    pub fn open_block(&mut self) {
+        self.out.open_block();
+    }
+
+    /// Finish a block started with [open_block](ChatRenderer::open_block).
+    pub fn close_block(&mut self) {
+        self.out.cr();
+    }
+
+    /// Start a container block (e.g. blockquote or list item), every line inside
+    /// will be prefixed with `first` (first line) or `rest` (the following lines).
+    ///
+    /// Blocks inside are separated with blank lines unless `tight` is set.
+    pub fn open_container(&mut self, first: &str, rest: &str, tight: bool) {
+        self.out.open_container(first, rest, tight);
+    }
+
+    /// Finish a container block started with [open_container](ChatRenderer::open_container).
+    pub fn close_container(&mut self) {
+        self.out.close_container();
+    }
+
+    /// Write line break, ignored if we're at the start of a line already.
+    pub fn cr(&mut self) {
+        self.out.cr();
+    }
+
+    /// Write code block, language is only kept for Discord.
+    pub fn code_block(&mut self, content: &str, language: Option<&str>) {
+        self.open_block();
+        self.text_raw("```");
+        if self.options.dialect == ChatDialect::Discord {
+            self.text_raw(language.unwrap_or_default());
+        }
+        self.text_raw("\n");
+        self.code_text(content);
+        self.cr();
+        self.text_raw("```");
+        self.close_block();
+    }
+
+    /// Write a link, or just the url if it's the same as text.
+    pub fn link(&mut self, url: &str, text: &str) {
+        let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        let same = text.is_empty() || text == url;
+        match self.options.dialect {
+            ChatDialect::Slack if same => {
+                self.text_raw(&format!("<{}>", escape_slack(url)));
+            }
+            ChatDialect::Slack => {
+                // `|` can't be escaped, it would end the url
+                let text = escape_slack(&text).replace('|', "\u{FF5C}");
+                self.text_raw(&format!("<{}|{}>", escape_slack(url), text));
+            }
+            ChatDialect::Discord if same => self.text_raw(url),
+            ChatDialect::Discord => {
+                self.text_raw(&format!("[{}]({})", escape_discord(&text), url));
+            }
+        }
+    }
+
+    /// Write plain text, escaping characters that chat would take as markup.
+    pub fn text(&mut self, text: &str) {
+        match self.options.dialect {
+            ChatDialect::Slack => self.out.write(&escape_slack(text)),
+            ChatDialect::Discord => {
+                for (idx, line) in text.split('\n').enumerate() {
+                    if idx > 0 {
+                        self.out.write("\n");
+                    }
+                    let mut escaped = escape_discord(line).into_owned();
+                    if self.out.is_line_start() {
+                        escape_line_start(&mut escaped);
+                    } else if escaped.starts_with('.') && self.after_number() {
+                        escaped.insert(0, '\\');
+                    }
+                    self.out.write(&escaped);
+                }
+            }
+        }
+    }
+
+    /// Write markup as is.
+    pub fn text_raw(&mut self, text: &str) {
+        self.out.write(text);
+    }
+
+    /// Extension set to store custom stuff.
+    pub fn ext(&mut self) -> &mut RenderExtSet {
+        &mut self.ext
+    }
+
+    // Text of code spans and blocks, markup isn't parsed there, but Slack still
+    // needs its escapes.
+    fn code_text(&mut self, text: &str) {
+        match self.options.dialect {
+            ChatDialect::Slack => self.out.write(&escape_slack(text)),
+            ChatDialect::Discord => self.out.write(text),
+        }
+    }
+
+    // Check if the current line only has a number so far (after container prefixes),
+    // so `.` written next would turn it into a numbered list item.
+    fn after_number(&self) -> bool {
+        let result = &self.out.result;
+        let line = &result[result.rfind('\n').map_or(0, |idx| idx + 1)..];
+        let rest = line.trim_end_matches(|ch: char| ch.is_ascii_digit());
+        rest.len() < line.len()
+            && (rest.trim_start_matches('>').trim().is_empty()
+                || rest.ends_with("- ")
+                || rest.ends_with(". "))
+    }
+
+    fn styled(&mut self, node: &Node, style: Style) {
+        let delimiter = match (self.options.dialect, style) {
+            (ChatDialect::Slack, Style::Bold) => "*",
+            (ChatDialect::Slack, Style::Italic) => "_",
+            (ChatDialect::Slack, Style::Strike) => "~",
+            (ChatDialect::Discord, Style::Bold) => "**",
+            (ChatDialect::Discord, Style::Italic) => "*",
+            (ChatDialect::Discord, Style::Strike) => "~~",
+        };
+        let depth = match style {
+            Style::Bold => &mut self.bold,
+            Style::Italic => &mut self.italic,
+            Style::Strike => &mut self.strike,
+        };
+        *depth += 1;
+        let outer = *depth == 1;
+
+        if outer {
+            self.text_raw(delimiter);
+        }
+        self.render_children(node);
+        if outer {
+            self.text_raw(delimiter);
+        }
+
+        match style {
+            Style::Bold => self.bold -= 1,
+            Style::Italic => self.italic -= 1,
+            Style::Strike => self.strike -= 1,
+        }
+    }
+}
+
+impl RenderFormat for ChatRenderer {
+    fn registry(&self) -> &RenderRegistry<Self> {
+        &self.registry
+    }
+
+    fn write_text(&mut self, text: &str) {
+        self.text(text);
+    }
+}
+
+impl Default for ChatRenderer {
+    fn default() -> Self {
+        Self::new(ChatRenderOptions::default())
+    }
+}
+
+impl From<ChatRenderer> for String {
+    fn from(f: ChatRenderer) -> Self {
+        f.out.result
+    }
+}
+
+fn heading(node: &Node, fmt: &mut ChatRenderer, level: u8) {
+    fmt.open_block();
+    match fmt.options.dialect {
+        ChatDialect::Slack => fmt.styled(node, Style::Bold),
+        ChatDialect::Discord => {
+            fmt.text_raw(&"#".repeat(level.clamp(1, 3) as usize));
+            fmt.text_raw(" ");
+            fmt.render_children(node);
+        }
+    }
+    fmt.close_block();
+}
+
+fn list(node: &Node, fmt: &mut ChatRenderer, marker: char, start: Option<u32>) {
+    // list is loose if any of its items contains a paragraph
+    let tight = !node
+        .children
+        .iter()
+        .any(|item| item.children.iter().any(|child| child.is::<Paragraph>()));
+    ListRenderContext {
+        marker,
+        next_number: start,
+        tight,
+    }
+    .render_list(node, fmt);
+}
+
+fn link(node: &Node, fmt: &mut ChatRenderer, url: &str) {
+    // autolinks keep their text in `TextSpecial`, which `collect_text` skips
+    let mut text = String::new();
+    node.walk(|node, _| {
+        if let Some(node) = node.cast::<Text>() {
+            text.push_str(&node.content);
+        } else if let Some(node) = node.cast::<TextSpecial>() {
+            text.push_str(&node.content);
+        }
+    });
+    fmt.link(url, &text);
+}
+
+// Escape a marker that would start a list item at the start of a line.
+fn escape_line_start(line: &mut String) {
+    let indent = line.len() - line.trim_start_matches(' ').len();
+    let rest = &line[indent..];
+    if rest.starts_with(['-', '+']) {
+        line.insert(indent, '\\');
+        return;
+    }
+    let digits = rest.len()
+        - rest
+            .trim_start_matches(|ch: char| ch.is_ascii_digit())
+            .len();
+    if digits > 0 && rest[digits..].starts_with('.') {
+        line.insert(indent + digits, '\\');
+    }
+}
@@ -0,0 +1,491 @@
+use super::{RenderFallback, RenderFormat, RenderRegistry};
+use crate::Node;
+use crate::common::utils::{escape_html, unescape_all};
+use crate::parser::core::Root;
+use crate::parser::extset::RenderExtSet;
+use crate::parser::inline::{Text, TextSpecial};
+use crate::plugins::cmark::block::blockquote::Blockquote;
+use crate::plugins::cmark::block::code::CodeBlock;
+use crate::plugins::cmark::block::fence::CodeFence;
+use crate::plugins::cmark::block::heading::ATXHeading;
+use crate::plugins::cmark::block::hr::ThematicBreak;
+use crate::plugins::cmark::block::lheading::SetextHeader;
+use crate::plugins::cmark::block::list::{BulletList, ListItem, OrderedList};
+use crate::plugins::cmark::block::paragraph::Paragraph;
+use crate::plugins::cmark::inline::autolink::Autolink;
+use crate::plugins::cmark::inline::backticks::CodeInline;
+use crate::plugins::cmark::inline::emphasis::{Em, Strong};
+use crate::plugins::cmark::inline::image::Image;
+use crate::plugins::cmark::inline::link::Link;
+use crate::plugins::cmark::inline::newline::{Hardbreak, Softbreak};
+use crate::plugins::extra::footnotes::{FootnoteItem, FootnoteReference, FootnoteSection};
+use crate::plugins::extra::strikethrough::Strikethrough;
+use crate::plugins::extra::tables::{
+    ColumnAlignment, Table, TableBody, TableCell, TableHead, TableRow,
+};
+use crate::plugins::gfm::tasklist::TaskListItem;
+use crate::plugins::html::html_block::HtmlBlock;
+
+/// Renderer producing [DocBook](https://docbook.org) 4.x XML (content of `<article>`,
+/// without the element itself).
+///
+/// Headings in the root node start nested `<section>` elements, which contain
+/// everything up to the next heading of the same or higher level. Headings elsewhere
+/// (e.g. inside blockquotes) can't start sections, they are written as `<bridgehead>`.
+///
+/// Every node type has a handler in [registry](DocbookRenderer::registry_mut), and
+/// nodes without one are replaced with their children. Thematic breaks and raw HTML
+/// don't have DocBook equivalents and are dropped.
+#[derive(Debug)]
+pub struct DocbookRenderer {
+    out: String,
+    registry: RenderRegistry<DocbookRenderer>,
+    ext: RenderExtSet,
+}
+
+impl DocbookRenderer {
+    pub fn new() -> Self {
+        Self {
+            out: String::new(),
+            registry: Self::default_registry(),
+            ext: RenderExtSet::new(),
+        }
+    }
+
+    /// Handlers for CommonMark nodes, strikethrough and tables.
+    pub fn default_registry() -> RenderRegistry<Self> {
+        let mut registry = RenderRegistry::<Self>::with_fallback(RenderFallback::Children);
+
+        registry.add::<Root>(|node, fmt| {
+            for part in group_sections(&node.children) {
+                fmt.render_part(&part);
+            }
+        });
+
+        registry.add::<Text>(|node, fmt| fmt.text(&node.cast::<Text>().unwrap().content));
+        registry.add::<TextSpecial>(|node, fmt| {
+            fmt.text(&node.cast::<TextSpecial>().unwrap().content);
+        });
+        registry.add::<Softbreak>(|_, fmt| fmt.text_raw("\n"));
+        // not a part of DocBook, but supported by DocBook XSL stylesheets
+        registry.add::<Hardbreak>(|_, fmt| fmt.text_raw("<?linebreak?>\n"));
+
+        registry.add::<Paragraph>(|node, fmt| {
+            fmt.cr();
+            fmt.element("para", &[], node);
+            fmt.cr();
+        });
+
+        registry.add::<ATXHeading>(|node, fmt| {
+            bridgehead(node, fmt, node.cast::<ATXHeading>().unwrap().level);
+        });
+        registry.add::<SetextHeader>(|node, fmt| {
+            bridgehead(node, fmt, node.cast::<SetextHeader>().unwrap().level);
+        });
+
+        registry.add::<ThematicBreak>(|_, _| ());
+        registry.add::<HtmlBlock>(|_, _| ());
+
+        registry.add::<Blockquote>(|node, fmt| {
+            fmt.cr();
+            fmt.open("blockquote", &[]);
+            fmt.cr();
+            fmt.render_children(node);
+            fmt.cr();
+            fmt.close("blockquote");
+            fmt.cr();
+        });
+
+        registry.add::<BulletList>(|node, fmt| list(node, fmt, "itemizedlist", 1));
+        registry.add::<OrderedList>(|node, fmt| {
+            let start = node.cast::<OrderedList>().unwrap().start;
+            list(node, fmt, "orderedlist", start);
+        });
+        registry.add::<ListItem>(list_item);
+        registry.add::<TaskListItem>(list_item);
+
+        registry.add::<FootnoteReference>(|node, fmt| {
+            let id = node.cast::<FootnoteReference>().unwrap().id;
+            fmt.text(&format!("[{}]", id + 1));
+        });
+        // footnotes are numbered in order, so the section is an ordered list
+        registry.add::<FootnoteSection>(|node, fmt| list(node, fmt, "orderedlist", 1));
+        registry.add::<FootnoteItem>(list_item);
+
+        registry.add::<CodeFence>(|node, fmt| {
+            let fence = node.cast::<CodeFence>().unwrap();
+            let info = unescape_all(&fence.info);
+            fmt.code_block(&fence.content, info.split_whitespace().next());
+        });
+        registry.add::<CodeBlock>(|node, fmt| {
+            fmt.code_block(&node.cast::<CodeBlock>().unwrap().content, None);
+        });
+        #[cfg(feature = "syntect")]
+        registry.add::<crate::plugins::extra::syntect::SyntectSnippet>(|node, fmt| {
+            let snippet = node
+                .cast::<crate::plugins::extra::syntect::SyntectSnippet>()
+                .unwrap();
+            fmt.code_block(
+                snippet.code().trim_matches('\n'),
+                snippet.language.as_deref(),
+            );
+        });
+
+        registry.add::<CodeInline>(|node, fmt| fmt.element("literal", &[], node));
+        registry.add::<Em>(|node, fmt| fmt.element("emphasis", &[], node));
+        registry.add::<Strong>(|node, fmt| {
+            fmt.element("emphasis", &[("role", "strong".into())], node);
+        });
+        registry.add::<Strikethrough>(|node, fmt| {
+            fmt.element("emphasis", &[("role", "strikethrough".into())], node);
+        });
+
+        registry.add::<Link>(|node, fmt| {
+            let url = node.cast::<Link>().unwrap().url.clone();
+            fmt.element("ulink", &[("url", url)], node);
+        });
+        registry.add::<Autolink>(|node, fmt| {
+            let url = node.cast::<Autolink>().unwrap().url.clone();
+            fmt.element("ulink", &[("url", url)], node);
+        });
+        #[cfg(feature = "linkify")]
+        registry.add::<crate::plugins::extra::linkify::Linkified>(|node, fmt| {
+            let url = node
+                .cast::<crate::plugins::extra::linkify::Linkified>()
+                .unwrap()
+                .url
+                .clone();
+            fmt.element("ulink", &[("url", url)], node);
+        });
+
+        registry.add::<Image>(|node, fmt| {
+            let url = node.cast::<Image>().unwrap().url.clone();
+            let alt = node.collect_text();
+            fmt.open("inlinemediaobject", &[]);
+            fmt.open("imageobject", &[]);
+            fmt.self_close("imagedata", &[("fileref", url)]);
+            fmt.close("imageobject");
+            if !alt.is_empty() {
+                fmt.open("textobject", &[]);
+                fmt.open("phrase", &[]);
+                fmt.text(&alt);
+                fmt.close("phrase");
+                fmt.close("textobject");
+            }
+            fmt.close("inlinemediaobject");
+        });
+
+        registry.add::<Table>(|node, fmt| {
+            let alignments = &node.cast::<Table>().unwrap().alignments;
+            fmt.cr();
+            fmt.open("informaltable", &[]);
+            fmt.cr();
+            fmt.open("tgroup", &[("cols", alignments.len().to_string())]);
+            fmt.cr();
+            for (idx, alignment) in alignments.iter().enumerate() {
+                let mut attrs = vec![("colname", format!("c{}", idx + 1))];
+                match alignment {
+                    ColumnAlignment::None => (),
+                    ColumnAlignment::Left => attrs.push(("align", "left".into())),
+                    ColumnAlignment::Center => attrs.push(("align", "center".into())),
+                    ColumnAlignment::Right => attrs.push(("align", "right".into())),
+                }
+                fmt.self_close("colspec", &attrs);
+                fmt.cr();
+            }
+            fmt.render_children(node);
+            fmt.close("tgroup");
+            fmt.cr();
+            fmt.close("informaltable");
+            fmt.cr();
+        });
+        registry.add::<TableHead>(|node, fmt| fmt.block_element("thead", node));
+        registry.add::<TableBody>(|node, fmt| fmt.block_element("tbody", node));
+        registry.add::<TableRow>(|node, fmt| {
+            fmt.open("row", &[]);
+            fmt.render_children(node);
+            fmt.close("row");
+            fmt.cr();
+        });
+        registry.add::<TableCell>(|node, fmt| fmt.element("entry", &[], node));
+
+        registry
+    }
+
+    /// Handlers used to render nodes, custom ones can be added here.
+    pub fn registry_mut(&mut self) -> &mut RenderRegistry<Self> {
+        &mut self.registry
+    }
+
+    /// Render a single node (and its children).
+    pub fn render(&mut self, node: &Node) {
+        self.render_node(node);
+    }
+
+    /// Render nodes inside of an element that only allows block content (e.g. `<listitem>`),
+    /// consecutive inline nodes (e.g. text of tight list items) are wrapped in `<para>`.
+    pub fn render_blocks(&mut self, nodes: &[Node]) {
+        let mut in_para = false;
+        for node in nodes.iter() {
+            let block = is_block(node);
+            if block && in_para {
+                self.close("para");
+                self.cr();
+            } else if !block && !in_para {
+                self.cr();
+                self.open("para", &[]);
+            }
+            in_para = !block;
+            self.render_node(node);
+        }
+        if in_para {
+            self.close("para");
+            self.cr();
+        }
+    }
+
+    /// Write `<programlisting>`, with an optional language.
+    pub fn code_block(&mut self, content: &str, language: Option<&str>) {
+        let mut attrs = Vec::new();
+        if let Some(language) = language {
+            attrs.push(("language", language.to_owned()));
+        }
+        self.cr();
+        self.open("programlisting", &attrs);
+        self.text(content.strip_suffix('\n').unwrap_or(content));
+        self.close("programlisting");
+        self.cr();
+    }
+
+    /// Write opening tag with attributes, e.g. `<ulink url="...">`.
+    pub fn open(&mut self, tag: &str, attrs: &[(&str, String)]) {
+        self.write("<");
+        self.write(tag);
+        self.attrs(attrs);
+        self.write(">");
+    }
+
+    /// Write closing tag, e.g. `</ulink>`.
+    pub fn close(&mut self, tag: &str) {
+        self.write("</");
+        self.write(tag);
+        self.write(">");
+    }
+
+    /// Write empty element with attributes, e.g. `<imagedata fileref="..."/>`.
+    pub fn self_close(&mut self, tag: &str, attrs: &[(&str, String)]) {
+        self.write("<");
+        self.write(tag);
+        self.attrs(attrs);
+        self.write("/>");
+    }
+
+    /// Write line break, ignored if we're at the start of a line already.
+    pub fn cr(&mut self) {
+        if !self.out.is_empty() && !self.out.ends_with('\n') {
+            self.out.push('\n');
+        }
+    }
+
+    /// Write plain text, escaping XML special characters.
+    pub fn text(&mut self, text: &str) {
+        self.write(&escape_html(text));
+    }
+
+    /// Write XML as is.
+    pub fn text_raw(&mut self, text: &str) {
+        self.write(text);
+    }
+
+    /// Extension set to store custom stuff.
+    pub fn ext(&mut self) -> &mut RenderExtSet {
+        &mut self.ext
+    }
+
+    // Inline element with node children inside.
+    fn element(&mut self, tag: &str, attrs: &[(&str, String)], node: &Node) {
+        self.open(tag, attrs);
+        self.render_children(node);
+        self.close(tag);
+    }
+
+    // Element with each child on its own line.
+    fn block_element(&mut self, tag: &str, node: &Node) {
+        self.open(tag, &[]);
+        self.cr();
+        self.render_children(node);
+        self.close(tag);
+        self.cr();
+    }
+
+    fn render_part(&mut self, part: &Part) {
+        match part {
+            Part::Node(node) => self.render_node(node),
+            Part::Section { heading, parts } => {
+                self.cr();
+                self.open("section", &[]);
+                self.cr();
+                self.element("title", &[], heading);
+                self.cr();
+                for part in parts.iter() {
+                    self.render_part(part);
+                }
+                self.close("section");
+                self.cr();
+            }
+        }
+    }
+
+    fn attrs(&mut self, attrs: &[(&str, String)]) {
+        for (name, value) in attrs {
+            self.write(" ");
+            self.write(name);
+            self.write("=\"");
+            self.write(&escape_html(value));
+            self.write("\"");
+        }
+    }
+
+    fn write(&mut self, text: &str) {
+        // U+0000 isn't allowed in XML
+        if text.contains('\0') {
+            self.out.push_str(&text.replace('\0', "\u{FFFD}"));
+        } else {
+            self.out.push_str(text);
+        }
+    }
+}
+
+impl RenderFormat for DocbookRenderer {
+    fn registry(&self) -> &RenderRegistry<Self> {
+        &self.registry
+    }
+
+    fn write_text(&mut self, text: &str) {
+        self.text(text);
+    }
+}
+
+impl Default for DocbookRenderer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<DocbookRenderer> for String {
+    fn from(f: DocbookRenderer) -> Self {
+        f.out
+    }
+}
+
+// Top-level content after headings are grouped with everything that follows them.
+enum Part<'a> {
+    Node(&'a Node),
+    Section {
+        heading: &'a Node,
+        parts: Vec<Part<'a>>,
+    },
+}
+
+fn heading_level(node: &Node) -> Option<u8> {
+    if let Some(heading) = node.cast::<ATXHeading>() {
+        Some(heading.level)
+    } else {
+        node.cast::<SetextHeader>().map(|heading| heading.level)
+    }
+}
+
+// Turn flat list of blocks into sections: each heading takes the following
+// blocks, until the next heading of the same or higher level.
+fn group_sections(nodes: &[Node]) -> Vec<Part<'_>> {
+    let mut result = Vec::new();
+    // sections that are still open, from the outermost one
+    let mut open: Vec<(u8, &Node, Vec<Part>)> = Vec::new();
+
+    fn close_section<'a>(
+        open: &mut Vec<(u8, &'a Node, Vec<Part<'a>>)>,
+        result: &mut Vec<Part<'a>>,
+    ) {
+        let (_, heading, parts) = open.pop().unwrap();
+        let section = Part::Section { heading, parts };
+        match open.last_mut() {
+            Some((_, _, parent)) => parent.push(section),
+            None => result.push(section),
+        }
+    }
+
+    for node in nodes.iter() {
+        match heading_level(node) {
+            Some(level) => {
+                while open
+                    .last()
+                    .is_some_and(|(open_level, ..)| *open_level >= level)
+                {
+                    close_section(&mut open, &mut result);
+                }
+                open.push((level, node, Vec::new()));
+            }
+            None => match open.last_mut() {
+                Some((_, _, parts)) => parts.push(Part::Node(node)),
+                None => result.push(Part::Node(node)),
+            },
+        }
+    }
+
+    while !open.is_empty() {
+        close_section(&mut open, &mut result);
+    }
+
+    result
+}
+
+// Blocks that can be written as is inside of `<listitem>`.
+fn is_block(node: &Node) -> bool {
+    node.is::<Paragraph>()
+        || node.is::<ATXHeading>()
+        || node.is::<SetextHeader>()
+        || node.is::<ThematicBreak>()
+        || node.is::<HtmlBlock>()
+        || node.is::<Blockquote>()
+        || node.is::<BulletList>()
+        || node.is::<OrderedList>()
+        || node.is::<CodeFence>()
+        || node.is::<CodeBlock>()
+        || node.is::<Table>()
+}
+
+fn bridgehead(node: &Node, fmt: &mut DocbookRenderer, level: u8) {
+    fmt.cr();
+    let renderas = format!("sect{}", level.clamp(1, 5));
+    fmt.element("bridgehead", &[("renderas", renderas)], node);
+    fmt.cr();
+}
+
+fn list_item(node: &Node, fmt: &mut DocbookRenderer) {
+    fmt.open("listitem", &[]);
+    fmt.render_blocks(&node.children);
+    fmt.close("listitem");
+    fmt.cr();
+}
+
+fn list(node: &Node, fmt: &mut DocbookRenderer, tag: &str, start: u32) {
+    // list is loose if any of its items contains a paragraph
+    let tight = !node
+        .children
+        .iter()
+        .any(|item| item.children.iter().any(|child| child.is::<Paragraph>()));
+
+    let mut attrs = Vec::new();
+    if tight {
+        attrs.push(("spacing", "compact".to_owned()));
+    }
+    if start != 1 {
+        attrs.push(("startingnumber", start.to_string()));
+    }
+    fmt.cr();
+    fmt.open(tag, &attrs);
+    fmt.cr();
+    fmt.render_children(node);
+    fmt.close(tag);
+    fmt.cr();
+}
@@ -0,0 +1,402 @@
+use super::{RenderFallback, RenderFormat, RenderRegistry};
+use crate::Node;
+use crate::common::utils::unescape_all;
+use crate::parser::extset::RenderExtSet;
+use crate::parser::inline::{Text, TextSpecial};
+use crate::plugins::cmark::block::blockquote::Blockquote;
+use crate::plugins::cmark::block::code::CodeBlock;
+use crate::plugins::cmark::block::fence::CodeFence;
+use crate::plugins::cmark::block::heading::ATXHeading;
+use crate::plugins::cmark::block::hr::ThematicBreak;
+use crate::plugins::cmark::block::lheading::SetextHeader;
+use crate::plugins::cmark::block::list::{BulletList, ListItem, ListRenderContext, OrderedList};
+use crate::plugins::cmark::block::paragraph::Paragraph;
+use crate::plugins::cmark::inline::autolink::Autolink;
+use crate::plugins::cmark::inline::image::Image;
+use crate::plugins::cmark::inline::link::Link;
+use crate::plugins::cmark::inline::newline::{Hardbreak, Softbreak};
+use crate::plugins::extra::footnotes::{FootnoteItem, FootnoteReference, FootnoteSection};
+use crate::plugins::extra::tables::{ColumnAlignment, Table, TableHead};
+use crate::plugins::gfm::tasklist::TaskListItem;
+
+/// Renderer producing [Gemtext](https://geminiprotocol.net/docs/gemtext.gmi),
+/// the line-based format of Gemini protocol.
+///
+/// Gemtext has no inline markup, so only the text of inline nodes is kept. Links
+/// can't be inline either, links (and images) are collected inside of each block
+/// and written as `=> url text` lines after it.
+///
+/// Headings deeper than level 3 are written as level 3, nested lists are flattened,
+/// and tables are written as preformatted text. Text lines that would be taken
+/// for another line type (e.g. starting with `#` or `=>`) are prefixed with a space.
+#[derive(Debug)]
+pub struct GemtextRenderer {
+    out: String,
+    // text of the line being written, and the prefix of its line type (e.g. `# `)
+    line: String,
+    marker: String,
+    // nesting level of blockquotes, lines inside are prefixed with `> `
+    quote: usize,
+    // inside of a tight list, items aren't separated with blank lines
+    tight: bool,
+    // blank line is needed before the next block
+    separate: bool,
+    // link lines collected in the current block
+    links: Vec<String>,
+    registry: RenderRegistry<GemtextRenderer>,
+    ext: RenderExtSet,
+}
+
+impl GemtextRenderer {
+    pub fn new() -> Self {
+        Self {
+            out: String::new(),
+            line: String::new(),
+            marker: String::new(),
+            quote: 0,
+            tight: false,
+            separate: false,
+            links: Vec::new(),
+            registry: Self::default_registry(),
+            ext: RenderExtSet::new(),
+        }
+    }
+
+    /// Handlers for CommonMark nodes and tables.
+    pub fn default_registry() -> RenderRegistry<Self> {
+        let mut registry = RenderRegistry::<Self>::with_fallback(RenderFallback::Children);
+
+        registry.add::<Text>(|node, fmt| fmt.text(&node.cast::<Text>().unwrap().content));
+        registry.add::<TextSpecial>(|node, fmt| {
+            fmt.text(&node.cast::<TextSpecial>().unwrap().content);
+        });
+        registry.add::<Softbreak>(|_, fmt| fmt.text(" "));
+        registry.add::<Hardbreak>(|_, fmt| fmt.line_break());
+
+        registry.add::<Paragraph>(|node, fmt| {
+            fmt.open_block();
+            fmt.render_children(node);
+            fmt.close_block();
+        });
+
+        registry.add::<ATXHeading>(|node, fmt| {
+            heading(node, fmt, node.cast::<ATXHeading>().unwrap().level);
+        });
+        registry.add::<SetextHeader>(|node, fmt| {
+            heading(node, fmt, node.cast::<SetextHeader>().unwrap().level);
+        });
+
+        registry.add::<ThematicBreak>(|_, fmt| {
+            fmt.open_block();
+            fmt.text("---");
+            fmt.close_block();
+        });
+
+        registry.add::<Blockquote>(|node, fmt| {
+            fmt.open_block();
+            fmt.quote += 1;
+            fmt.render_children(node);
+            fmt.quote -= 1;
+            fmt.close_block();
+        });
+
+        registry.add::<BulletList>(|node, fmt| list(node, fmt, None));
+        registry.add::<OrderedList>(|node, fmt| {
+            list(node, fmt, Some(node.cast::<OrderedList>().unwrap().start));
+        });
+        registry.add::<ListItem>(|node, fmt| list_item(node, fmt, ""));
+        registry.add::<TaskListItem>(|node, fmt| {
+            let checkbox = node.cast::<TaskListItem>().unwrap().marker();
+            list_item(node, fmt, checkbox);
+        });
+
+        registry.add::<FootnoteReference>(|node, fmt| {
+            let id = node.cast::<FootnoteReference>().unwrap().id;
+            fmt.text(&format!("[{}]", id + 1));
+        });
+        // footnotes are numbered in order, so the section is an ordered list
+        registry.add::<FootnoteSection>(|node, fmt| list(node, fmt, Some(1)));
+        registry.add::<FootnoteItem>(|node, fmt| list_item(node, fmt, ""));
+
+        registry.add::<CodeFence>(|node, fmt| {
+            let fence = node.cast::<CodeFence>().unwrap();
+            fmt.preformatted(&fence.content, unescape_all(&fence.info).trim());
+        });
+        registry.add::<CodeBlock>(|node, fmt| {
+            fmt.preformatted(&node.cast::<CodeBlock>().unwrap().content, "");
+        });
+        #[cfg(feature = "syntect")]
+        registry.add::<crate::plugins::extra::syntect::SyntectSnippet>(|node, fmt| {
+            let snippet = node
+                .cast::<crate::plugins::extra::syntect::SyntectSnippet>()
+                .unwrap();
+            fmt.preformatted(
+                snippet.code().trim_matches('\n'),
+                snippet.language.as_deref().unwrap_or_default(),
+            );
+        });
+
+        registry.add::<Link>(|node, fmt| link(node, fmt, &node.cast::<Link>().unwrap().url));
+        registry.add::<Autolink>(|node, fmt| {
+            link(node, fmt, &node.cast::<Autolink>().unwrap().url);
+        });
+        #[cfg(feature = "linkify")]
+        registry.add::<crate::plugins::extra::linkify::Linkified>(|node, fmt| {
+            let url = &node
+                .cast::<crate::plugins::extra::linkify::Linkified>()
+                .unwrap()
+                .url;
+            link(node, fmt, url);
+        });
+        registry.add::<Image>(|node, fmt| {
+            let alt = node.collect_text();
+            fmt.text(&alt);
+            fmt.link(&node.cast::<Image>().unwrap().url, &alt);
+        });
+
+        registry.add::<Table>(|node, fmt| {
+            let alignments = &node.cast::<Table>().unwrap().alignments;
+            fmt.preformatted(&table_text(node, alignments), "");
+        });
+
+        registry
+    }
+
+    /// Handlers used to render nodes, custom ones can be added here.
+    pub fn registry_mut(&mut self) -> &mut RenderRegistry<Self> {
+        &mut self.registry
+    }
+
+    /// Render a single node (and its children).
+    pub fn render(&mut self, node: &Node) {
+        self.render_node(node);
+    }
+
+    /// Start a new block, finishing the current line, and separating it
+    /// from the previous block with a blank line.
+    pub fn open_block(&mut self) {
+        self.flush();
+        if std::mem::take(&mut self.separate) && !self.tight && !self.out.is_empty() {
+            self.out.push('\n');
+        }
+    }
+
+    /// Finish a block, writing its last line and links collected inside.
+    pub fn close_block(&mut self) {
+        self.flush();
+        self.marker.clear();
+    }
+
+    /// Finish the current line and start another one of the same block.
+    pub fn line_break(&mut self) {
+        self.finish_line();
+    }
+
+    /// Write plain text to the current line.
+    pub fn text(&mut self, text: &str) {
+        self.line.push_str(&text.replace('\n', " "));
+    }
+
+    /// Add `=> url text` line written after the current block.
+    pub fn link(&mut self, url: &str, text: &str) {
+        let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        if text.is_empty() || text == url {
+            self.links.push(format!("=> {url}"));
+        } else {
+            self.links.push(format!("=> {url} {text}"));
+        }
+    }
+
+    /// Write preformatted block, `alt` is written after the opening ```` ``` ````.
+    pub fn preformatted(&mut self, content: &str, alt: &str) {
+        self.open_block();
+        self.out.push_str("```");
+        self.out.push_str(alt);
+        self.out.push('\n');
+        self.out.push_str(content);
+        if !content.is_empty() && !content.ends_with('\n') {
+            self.out.push('\n');
+        }
+        self.out.push_str("```\n");
+        self.separate = true;
+    }
+
+    /// Extension set to store custom stuff.
+    pub fn ext(&mut self) -> &mut RenderExtSet {
+        &mut self.ext
+    }
+
+    fn flush(&mut self) {
+        self.finish_line();
+        for link in std::mem::take(&mut self.links) {
+            self.out.push_str(&link);
+            self.out.push('\n');
+            self.separate = true;
+        }
+    }
+
+    // Write the current line, line type marker is kept until there's some text for it.
+    fn finish_line(&mut self) {
+        let line = std::mem::take(&mut self.line);
+        let line = line.trim();
+        if line.is_empty() {
+            return;
+        }
+
+        if self.quote > 0 {
+            self.out.push_str("> ");
+        } else if self.marker.is_empty()
+            && ["=>", "```", "#", "* ", ">"]
+                .iter()
+                .any(|prefix| line.starts_with(prefix))
+        {
+            // only text lines can start with a space
+            self.out.push(' ');
+        }
+        self.out.push_str(&std::mem::take(&mut self.marker));
+        self.out.push_str(line);
+        self.out.push('\n');
+        self.separate = true;
+    }
+}
+
+impl RenderFormat for GemtextRenderer {
+    fn registry(&self) -> &RenderRegistry<Self> {
+        &self.registry
+    }
+
+    fn write_text(&mut self, text: &str) {
+        self.text(text);
+    }
+}
+
+impl Default for GemtextRenderer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<GemtextRenderer> for String {
+    fn from(mut f: GemtextRenderer) -> Self {
+        f.close_block();
+        f.out
+    }
+}
+
+fn heading(node: &Node, fmt: &mut GemtextRenderer, level: u8) {
+    fmt.open_block();
+    fmt.marker = format!("{} ", "#".repeat(level.clamp(1, 3) as usize));
+    fmt.render_children(node);
+    fmt.close_block();
+}
+
+fn list(node: &Node, fmt: &mut GemtextRenderer, start: Option<u32>) {
+    // list is loose if any of its items contains a paragraph
+    let tight = !node
+        .children
+        .iter()
+        .any(|item| item.children.iter().any(|child| child.is::<Paragraph>()));
+    let ctx = ListRenderContext {
+        marker: if start.is_some() { '.' } else { '-' },
+        next_number: start,
+        tight,
+    };
+    let old_context = fmt.ext().remove::<ListRenderContext>();
+    fmt.ext().insert(ctx);
+
+    fmt.open_block();
+    let old_tight = std::mem::replace(&mut fmt.tight, tight);
+    fmt.render_children(node);
+    fmt.close_block();
+    fmt.tight = old_tight;
+
+    old_context.map(|ctx| fmt.ext().insert(ctx));
+}
+
+// Gemtext only has flat bullet lists, so numbers of ordered lists are written
+// after the bullet, followed by `extra` (e.g. task list checkbox)
+fn list_item(node: &Node, fmt: &mut GemtextRenderer, extra: &str) {
+    let number = fmt
+        .ext()
+        .get_or_insert_default::<ListRenderContext>()
+        .next_marker();
+
+    fmt.open_block();
+    fmt.marker = match number.trim() {
+        "-" => format!("* {extra}"),
+        number => format!("* {number} {extra}"),
+    };
+    fmt.render_children(node);
+    fmt.close_block();
+}
+
+fn link(node: &Node, fmt: &mut GemtextRenderer, url: &str) {
+    // autolinks keep their text in `TextSpecial`, which `collect_text` skips
+    let mut text = String::new();
+    node.walk(|node, _| {
+        if let Some(node) = node.cast::<Text>() {
+            text.push_str(&node.content);
+        } else if let Some(node) = node.cast::<TextSpecial>() {
+            text.push_str(&node.content);
+        }
+    });
+
+    fmt.render_children(node);
+    fmt.link(url, &text);
+}
+
+// Table as text with aligned columns, and a line under the header.
+pub(super) fn table_text(node: &Node, alignments: &[ColumnAlignment]) -> String {
+    let mut rows = Vec::new();
+    let mut head_rows = 0;
+    for section in node.children.iter() {
+        for row in section.children.iter() {
+            let cells = row
+                .children
+                .iter()
+                .map(|cell| cell.render_text().trim().replace('\n', " "))
+                .collect::<Vec<_>>();
+            rows.push(cells);
+            if section.is::<TableHead>() {
+                head_rows += 1;
+            }
+        }
+    }
+
+    let mut widths = vec![0; alignments.len()];
+    for row in rows.iter() {
+        for (idx, cell) in row.iter().enumerate() {
+            if let Some(width) = widths.get_mut(idx) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+    }
+
+    let mut result = String::new();
+    for (row_idx, row) in rows.iter().enumerate() {
+        let cells = widths
+            .iter()
+            .enumerate()
+            .map(|(idx, width)| {
+                let cell = row.get(idx).map(String::as_str).unwrap_or_default();
+                match alignments[idx] {
+                    ColumnAlignment::Center => format!("{cell:^width$}"),
+                    ColumnAlignment::Right => format!("{cell:>width$}"),
+                    _ => format!("{cell:<width$}"),
+                }
+            })
+            .collect::<Vec<_>>();
+        result.push_str(cells.join(" | ").trim_end());
+        result.push('\n');
+
+        if row_idx + 1 == head_rows {
+            let rule = widths
+                .iter()
+                .map(|width| "-".repeat(*width))
+                .collect::<Vec<_>>();
+            result.push_str(&rule.join("-+-"));
+            result.push('\n');
+        }
+    }
+    result
+}
@@ -0,0 +1,448 @@
+use super::{RenderFallback, RenderFormat, RenderRegistry, Renderer};
+use crate::Node;
+use crate::common::utils::{escape_latex, unescape_all};
+use crate::parser::extset::RenderExtSet;
+use crate::plugins::cmark::block::code::CodeBlock;
+use crate::plugins::cmark::block::fence::CodeFence;
+use crate::plugins::extra::tables::{ColumnAlignment, TableRenderContext};
+use crate::plugins::html::html_block::HtmlBlock;
+use crate::plugins::html::html_inline::HtmlInline;
+
+/// Settings for [LatexRenderer].
+#[derive(Debug, Default, Clone)]
+pub struct LatexRenderOptions {
+    /// Write code blocks as `lstlisting` with the language taken from info string
+    /// (requires `listings` package), instead of `verbatim`.
+    pub listings: bool,
+}
+
+/// Renderer producing LaTeX document body.
+///
+/// It implements [Renderer], so it works with any node, HTML tags written by nodes
+/// are translated to LaTeX commands (unknown tags are dropped, keeping their content).
+/// Code blocks and raw HTML have their own handlers in [registry](LatexRenderer::registry_mut),
+/// since HTML output loses their language or can't be translated at all.
+///
+/// Output uses `hyperref` (links), `graphicx` (images) and `ulem` (strikethrough)
+/// packages, they should be included in the preamble.
+#[derive(Debug)]
+pub struct LatexRenderer {
+    out: String,
+    options: LatexRenderOptions,
+    // text written when each open tag is closed
+    closers: Vec<String>,
+    // paragraph break is needed before the next block
+    need_par: bool,
+    // `[` at the start of the text would be taken as an optional argument (e.g. of `\item`)
+    guard_bracket: bool,
+    // nesting level of `verbatim` environments, text is written as is inside
+    verbatim: usize,
+    // nesting level of `enumerate` environments, used to pick the counter
+    enumerate: usize,
+    // index of the next cell in the current table row, `None` outside of rows
+    cell: Option<usize>,
+    registry: RenderRegistry<LatexRenderer>,
+    ext: RenderExtSet,
+}
+
+impl LatexRenderer {
+    pub fn new(options: LatexRenderOptions) -> Self {
+        Self {
+            out: String::new(),
+            options,
+            closers: Vec::new(),
+            need_par: false,
+            guard_bracket: false,
+            verbatim: 0,
+            enumerate: 0,
+            cell: None,
+            registry: Self::default_registry(),
+            ext: RenderExtSet::new(),
+        }
+    }
+
+    /// Handlers for nodes that can't be rendered through [Renderer] trait,
+    /// everything else is rendered with [NodeValue::render](crate::NodeValue::render).
+    pub fn default_registry() -> RenderRegistry<Self> {
+        let mut registry =
+            RenderRegistry::with_fallback(RenderFallback::Handler(|node, fmt: &mut Self| {
+                node.node_value.render(node, fmt);
+            }));
+
+        registry.add::<CodeFence>(|node, fmt| {
+            let fence = node.cast::<CodeFence>().unwrap();
+            let info = unescape_all(&fence.info);
+            fmt.code_block(&fence.content, info.split_whitespace().next());
+        });
+
+        registry.add::<CodeBlock>(|node, fmt| {
+            let code = node.cast::<CodeBlock>().unwrap();
+            fmt.code_block(&code.content, None);
+        });
+
+        #[cfg(feature = "syntect")]
+        registry.add::<crate::plugins::extra::syntect::SyntectSnippet>(|node, fmt| {
+            let snippet = node
+                .cast::<crate::plugins::extra::syntect::SyntectSnippet>()
+                .unwrap();
+            fmt.code_block(
+                snippet.code().trim_matches('\n'),
+                snippet.language.as_deref(),
+            );
+        });
+
+        // raw html can't be translated
+        registry.add::<HtmlBlock>(|_, _| ());
+        registry.add::<HtmlInline>(|_, _| ());
+
+        registry
+    }
+
+    /// Handlers used to render nodes, custom ones can be added here.
+    pub fn registry_mut(&mut self) -> &mut RenderRegistry<Self> {
+        &mut self.registry
+    }
+
+    /// Options this renderer was created with.
+    pub fn options(&self) -> &LatexRenderOptions {
+        &self.options
+    }
+
+    /// Render a single node (and its children).
+    pub fn render(&mut self, node: &Node) {
+        self.render_node(node);
+    }
+
+    /// Write code block as `verbatim` or `lstlisting` environment.
+    pub fn code_block(&mut self, content: &str, language: Option<&str>) {
+        let env = if self.options.listings {
+            "lstlisting"
+        } else {
+            "verbatim"
+        };
+
+        self.open_block();
+        self.write(&format!("\\begin{{{env}}}"));
+        let language = language
+            .filter(|_| self.options.listings)
+            .map(listings_language)
+            .filter(|language| !language.is_empty());
+        if let Some(language) = language {
+            self.write(&format!("[language={language}]"));
+        }
+        self.write("\n");
+        self.write(content);
+        self.cr();
+        self.write(&format!("\\end{{{env}}}"));
+        self.close_block();
+    }
+
+    fn open_block(&mut self) {
+        self.cr();
+        if self.need_par {
+            self.write("\n");
+        }
+        self.need_par = false;
+    }
+
+    fn close_block(&mut self) {
+        self.cr();
+        self.need_par = true;
+    }
+
+    fn open_env(&mut self, env: &str) -> String {
+        self.open_block();
+        self.write(&format!("\\begin{{{env}}}\n"));
+        format!("\\end{{{env}}}")
+    }
+
+    // list item content (even a paragraph) starts on the same line as `\item`
+    fn is_line_start(&self) -> bool {
+        self.out.is_empty() || self.out.ends_with('\n') || self.out.ends_with("\\item ")
+    }
+
+    fn write(&mut self, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+        self.guard_bracket = false;
+        self.out.push_str(text);
+    }
+}
+
+impl RenderFormat for LatexRenderer {
+    fn registry(&self) -> &RenderRegistry<Self> {
+        &self.registry
+    }
+
+    fn write_text(&mut self, text: &str) {
+        self.text(text);
+    }
+}
+
+impl Default for LatexRenderer {
+    fn default() -> Self {
+        Self::new(LatexRenderOptions::default())
+    }
+}
+
+impl From<LatexRenderer> for String {
+    fn from(f: LatexRenderer) -> Self {
+        f.out
+    }
+}
+
+// Tags rendered as separate blocks, paragraph breaks are inserted between them.
+fn is_block_tag(tag: &str) -> bool {
+    matches!(
+        tag,
+        "p" | "h1"
+            | "h2"
+            | "h3"
+            | "h4"
+            | "h5"
+            | "h6"
+            | "blockquote"
+            | "ul"
+            | "ol"
+            | "pre"
+            | "table"
+    )
+}
+
+// Escape characters that are special inside of `\href` url.
+fn escape_url(url: &str) -> String {
+    let mut result = String::with_capacity(url.len());
+    for ch in url.chars() {
+        if matches!(ch, '\\' | '#' | '%' | '{' | '}') {
+            result.push('\\');
+        }
+        result.push(ch);
+    }
+    result
+}
+
+// `lstlisting` options are a comma-separated list in brackets,
+// so only characters used in language names are kept (e.g. `C++`, `c_sharp`)
+fn listings_language(language: &str) -> String {
+    language
+        .chars()
+        .filter(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '+' | '-' | '_' | '.'))
+        .collect()
+}
+
+fn get_attr<'a>(attrs: &'a [(&str, String)], name: &str) -> Option<&'a str> {
+    attrs
+        .iter()
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value.as_str())
+}
+
+impl Renderer for LatexRenderer {
+    fn open(&mut self, tag: &str, attrs: &[(&str, String)]) {
+        let closer = match tag {
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                static COMMANDS: [&str; 6] = [
+                    "section",
+                    "subsection",
+                    "subsubsection",
+                    "paragraph",
+                    "subparagraph",
+                    "subparagraph",
+                ];
+                let level = tag[1..].parse::<usize>().unwrap();
+                self.open_block();
+                self.write(&format!("\\{}{{", COMMANDS[level - 1]));
+                "}".to_owned()
+            }
+            "p" => {
+                self.open_block();
+                String::new()
+            }
+            "blockquote" => self.open_env("quote"),
+            "ul" => self.open_env("itemize"),
+            "ol" => {
+                let closer = self.open_env("enumerate");
+                self.enumerate += 1;
+                if let Some(start) = get_attr(attrs, "start").and_then(|s| s.parse::<u32>().ok()) {
+                    let counter =
+                        ["enumi", "enumii", "enumiii", "enumiv"][self.enumerate.clamp(1, 4) - 1];
+                    self.write(&format!(
+                        "\\setcounter{{{counter}}}{{{}}}\n",
+                        start.saturating_sub(1)
+                    ));
+                }
+                closer
+            }
+            "li" => {
+                self.cr();
+                self.write("\\item ");
+                self.guard_bracket = true;
+                self.need_par = false;
+                String::new()
+            }
+            "pre" => {
+                let closer = self.open_env("verbatim");
+                self.verbatim += 1;
+                closer
+            }
+            "table" => {
+                let spec = self
+                    .ext
+                    .get::<TableRenderContext>()
+                    .map(|ctx| {
+                        ctx.alignments
+                            .iter()
+                            .map(|alignment| match alignment {
+                                ColumnAlignment::None | ColumnAlignment::Left => 'l',
+                                ColumnAlignment::Right => 'r',
+                                ColumnAlignment::Center => 'c',
+                            })
+                            .collect::<String>()
+                    })
+                    .unwrap_or_default();
+                self.open_block();
+                self.write(&format!("\\begin{{tabular}}{{{spec}}}\n\\hline\n"));
+                "\\hline\n\\end{tabular}".to_owned()
+            }
+            "thead" => "\\hline".to_owned(),
+            "tr" => {
+                self.cr();
+                self.cell = Some(0);
+                " \\\\".to_owned()
+            }
+            "th" | "td" => {
+                if let Some(cell) = self.cell {
+                    if cell > 0 {
+                        self.write(" & ");
+                    }
+                    self.cell = Some(cell + 1);
+                }
+                if tag == "th" {
+                    self.write("\\textbf{");
+                    "}".to_owned()
+                } else {
+                    String::new()
+                }
+            }
+            "code" if self.verbatim > 0 => String::new(),
+            "code" => {
+                self.write("\\texttt{");
+                "}".to_owned()
+            }
+            "em" | "i" => {
+                self.write("\\emph{");
+                "}".to_owned()
+            }
+            "strong" | "b" => {
+                self.write("\\textbf{");
+                "}".to_owned()
+            }
+            "s" | "del" => {
+                self.write("\\sout{");
+                "}".to_owned()
+            }
+            "sup" => {
+                self.write("\\textsuperscript{");
+                "}".to_owned()
+            }
+            "sub" => {
+                self.write("\\textsubscript{");
+                "}".to_owned()
+            }
+            "a" => match get_attr(attrs, "href") {
+                Some(href) => {
+                    self.write(&format!("\\href{{{}}}{{", escape_url(href)));
+                    "}".to_owned()
+                }
+                None => String::new(),
+            },
+            _ => String::new(),
+        };
+        self.closers.push(closer);
+    }
+
+    fn close(&mut self, tag: &str) {
+        let closer = self.closers.pop().unwrap_or_default();
+        match tag {
+            "tr" => {
+                self.write(&closer);
+                self.cell = None;
+                self.cr();
+            }
+            "pre" => {
+                self.verbatim = self.verbatim.saturating_sub(1);
+                self.cr();
+                self.write(&closer);
+            }
+            "ol" | "ul" | "blockquote" | "table" => {
+                if tag == "ol" {
+                    self.enumerate = self.enumerate.saturating_sub(1);
+                }
+                self.cr();
+                self.write(&closer);
+            }
+            _ => self.write(&closer),
+        }
+
+        if is_block_tag(tag) {
+            self.close_block();
+        }
+    }
+
+    fn self_close(&mut self, tag: &str, attrs: &[(&str, String)]) {
+        match tag {
+            "br" => {
+                self.write("\\\\");
+                self.guard_bracket = true;
+            }
+            "hr" => {
+                self.open_block();
+                self.write("\\noindent\\rule{\\linewidth}{0.4pt}");
+                self.close_block();
+            }
+            "img" => {
+                if let Some(src) = get_attr(attrs, "src") {
+                    self.write(&format!("\\includegraphics{{{}}}", escape_url(src)));
+                }
+            }
+            "input" if get_attr(attrs, "type") == Some("checkbox") => {
+                let checked = get_attr(attrs, "checked").is_some();
+                self.text(if checked { "[x]" } else { "[ ]" });
+            }
+            _ => (),
+        }
+    }
+
+    fn contents(&mut self, nodes: &[Node]) {
+        for node in nodes.iter() {
+            self.render(node);
+        }
+    }
+
+    fn cr(&mut self) {
+        // table rows are kept on a single line
+        if self.cell.is_none() && !self.is_line_start() {
+            self.out.push('\n');
+        }
+    }
+
+    fn text(&mut self, text: &str) {
+        if self.verbatim > 0 {
+            self.write(text);
+            return;
+        }
+        if self.guard_bracket && text.starts_with('[') {
+            self.write("{}");
+        }
+        self.write(&escape_latex(text));
+    }
+
+    fn text_raw(&mut self, text: &str) {
+        self.write(text);
+    }
+
+    fn ext(&mut self) -> &mut RenderExtSet {
+        &mut self.ext
+    }
+}
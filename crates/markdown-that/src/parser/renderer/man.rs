@@ -0,0 +1,420 @@
+use super::{RenderFallback, RenderFormat, RenderRegistry, Renderer};
+use crate::Node;
+use crate::common::utils::escape_roff;
+use crate::parser::extset::RenderExtSet;
+use crate::plugins::html::html_block::HtmlBlock;
+use crate::plugins::html::html_inline::HtmlInline;
+
+/// Settings for [ManRenderer].
+#[derive(Debug, Clone)]
+pub struct ManRenderOptions {
+    /// Page title, `.TH` header is only written if it's set.
+    pub title: Option<String>,
+    /// Manual section, e.g. `1` for commands.
+    pub section: String,
+    /// Date of the last change, shown in the footer.
+    pub date: Option<String>,
+    /// Source of the command, e.g. `myapp 1.0`.
+    pub source: Option<String>,
+    /// Title of the manual, e.g. `User Commands`.
+    pub manual: Option<String>,
+}
+
+impl Default for ManRenderOptions {
+    fn default() -> Self {
+        Self {
+            title: None,
+            section: "1".to_owned(),
+            date: None,
+            source: None,
+            manual: None,
+        }
+    }
+}
+
+/// Renderer producing man pages (roff with `man` macros).
+///
+/// It implements [Renderer], so it works with any node, HTML tags written by nodes
+/// are translated to macros and font changes (unknown tags are dropped, keeping
+/// their content), raw HTML is dropped. Custom handlers can be added to its
+/// [registry](ManRenderer::registry_mut).
+///
+/// Level 1 and 2 headings become `.SH` and `.SS` sections, deeper ones are bold
+/// paragraphs.
+#[derive(Debug)]
+pub struct ManRenderer {
+    out: String,
+    options: ManRenderOptions,
+    // list and blockquote nesting, used to pick paragraph macros
+    containers: Vec<Container>,
+    // `.IP` of the current list item is written, but its content isn't yet
+    item_start: bool,
+    // inside of `.SH` or `.SS` line, everything must be kept on it
+    heading: bool,
+    // nesting level of `<pre>`, filling is disabled inside
+    pre: usize,
+    bold: usize,
+    italic: usize,
+    code: usize,
+    // position in the output where each open link starts, and its url
+    links: Vec<(usize, String)>,
+    registry: RenderRegistry<ManRenderer>,
+    ext: RenderExtSet,
+}
+
+#[derive(Debug)]
+enum Container {
+    BulletList,
+    OrderedList(u32),
+    Blockquote,
+}
+
+impl ManRenderer {
+    pub fn new(options: ManRenderOptions) -> Self {
+        let mut out = String::new();
+        if let Some(title) = &options.title {
+            let args = [
+                Some(title),
+                Some(&options.section),
+                options.date.as_ref(),
+                options.source.as_ref(),
+                options.manual.as_ref(),
+            ];
+            let len = args.iter().rposition(Option::is_some).unwrap_or_default() + 1;
+            out.push_str(".TH");
+            for arg in args[..len].iter() {
+                let arg = arg.map(String::as_str).unwrap_or_default();
+                out.push_str(&format!(" \"{}\"", escape_roff(arg).replace('"', "\\(dq")));
+            }
+            out.push('\n');
+        }
+
+        Self {
+            out,
+            options,
+            containers: Vec::new(),
+            item_start: false,
+            heading: false,
+            pre: 0,
+            bold: 0,
+            italic: 0,
+            code: 0,
+            links: Vec::new(),
+            registry: Self::default_registry(),
+            ext: RenderExtSet::new(),
+        }
+    }
+
+    /// Handlers for nodes that can't be rendered through [Renderer] trait,
+    /// everything else is rendered with [NodeValue::render](crate::NodeValue::render).
+    pub fn default_registry() -> RenderRegistry<Self> {
+        let mut registry =
+            RenderRegistry::with_fallback(RenderFallback::Handler(|node, fmt: &mut Self| {
+                node.node_value.render(node, fmt);
+            }));
+
+        // highlighted html is written as plain code block
+        #[cfg(feature = "syntect")]
+        registry.add::<crate::plugins::extra::syntect::SyntectSnippet>(|node, fmt| {
+            let snippet = node
+                .cast::<crate::plugins::extra::syntect::SyntectSnippet>()
+                .unwrap();
+            fmt.open("pre", &[]);
+            fmt.text(snippet.code().trim_matches('\n'));
+            fmt.close("pre");
+        });
+
+        // raw html can't be translated
+        registry.add::<HtmlBlock>(|_, _| ());
+        registry.add::<HtmlInline>(|_, _| ());
+
+        registry
+    }
+
+    /// Handlers used to render nodes, custom ones can be added here.
+    pub fn registry_mut(&mut self) -> &mut RenderRegistry<Self> {
+        &mut self.registry
+    }
+
+    /// Options this renderer was created with.
+    pub fn options(&self) -> &ManRenderOptions {
+        &self.options
+    }
+
+    /// Render a single node (and its children).
+    pub fn render(&mut self, node: &Node) {
+        self.render_node(node);
+    }
+
+    // Start a paragraph, inside of list items it keeps their indentation.
+    fn paragraph(&mut self) {
+        self.cr();
+        if self.item_start {
+            self.item_start = false;
+        } else if matches!(
+            self.containers.last(),
+            Some(Container::BulletList | Container::OrderedList(_))
+        ) {
+            self.write(".IP\n");
+        } else {
+            self.write(".PP\n");
+        }
+    }
+
+    fn font(&mut self) {
+        let font = match (self.code > 0, self.bold > 0, self.italic > 0) {
+            (true, true, _) => "\\f(CB",
+            (true, false, _) => "\\f(CR",
+            (false, true, true) => "\\f(BI",
+            (false, true, false) => "\\fB",
+            (false, false, true) => "\\fI",
+            (false, false, false) => "\\fR",
+        };
+        self.write(font);
+    }
+
+    fn is_line_start(&self) -> bool {
+        self.out.is_empty() || self.out.ends_with('\n')
+    }
+
+    fn write(&mut self, text: &str) {
+        self.out.push_str(text);
+    }
+}
+
+impl RenderFormat for ManRenderer {
+    fn registry(&self) -> &RenderRegistry<Self> {
+        &self.registry
+    }
+
+    fn write_text(&mut self, text: &str) {
+        self.text(text);
+    }
+}
+
+impl Default for ManRenderer {
+    fn default() -> Self {
+        Self::new(ManRenderOptions::default())
+    }
+}
+
+impl From<ManRenderer> for String {
+    fn from(f: ManRenderer) -> Self {
+        f.out
+    }
+}
+
+impl Renderer for ManRenderer {
+    fn open(&mut self, tag: &str, attrs: &[(&str, String)]) {
+        match tag {
+            "h1" | "h2" => {
+                self.cr();
+                self.write(if tag == "h1" { ".SH " } else { ".SS " });
+                self.heading = true;
+            }
+            "h3" | "h4" | "h5" | "h6" => {
+                self.paragraph();
+                self.bold += 1;
+                self.font();
+            }
+            "p" => self.paragraph(),
+            "ul" | "ol" => {
+                // nested lists are indented relative to the parent item
+                if matches!(
+                    self.containers.last(),
+                    Some(Container::BulletList | Container::OrderedList(_))
+                ) {
+                    self.cr();
+                    self.write(".RS\n");
+                }
+                self.item_start = false;
+                self.containers.push(if tag == "ul" {
+                    Container::BulletList
+                } else {
+                    let start = attrs
+                        .iter()
+                        .find(|(name, _)| *name == "start")
+                        .and_then(|(_, value)| value.parse().ok())
+                        .unwrap_or(1);
+                    Container::OrderedList(start)
+                });
+            }
+            "li" => {
+                let marker = match self.containers.last_mut() {
+                    Some(Container::OrderedList(number)) => {
+                        *number += 1;
+                        format!(".IP \"{}.\" 4\n", *number - 1)
+                    }
+                    _ => ".IP \\(bu 2\n".to_owned(),
+                };
+                self.cr();
+                self.write(&marker);
+                self.item_start = true;
+            }
+            "blockquote" => {
+                self.cr();
+                self.write(".RS\n");
+                self.item_start = false;
+                self.containers.push(Container::Blockquote);
+            }
+            "pre" => {
+                self.paragraph();
+                self.write(".RS 4\n.nf\n");
+                self.pre += 1;
+            }
+            "code" if self.pre > 0 => (),
+            "code" => {
+                self.code += 1;
+                self.font();
+            }
+            "em" | "i" => {
+                self.italic += 1;
+                self.font();
+            }
+            "strong" | "b" | "th" => {
+                self.bold += 1;
+                self.font();
+            }
+            "tr" => self.cr(),
+            "a" => {
+                let url = attrs
+                    .iter()
+                    .find(|(name, _)| *name == "href")
+                    .map(|(_, value)| value.clone())
+                    .unwrap_or_default();
+                self.links.push((self.out.len(), url));
+            }
+            _ => (),
+        }
+    }
+
+    fn close(&mut self, tag: &str) {
+        match tag {
+            "h1" | "h2" => {
+                self.heading = false;
+                self.cr();
+            }
+            "h3" | "h4" | "h5" | "h6" => {
+                self.bold = self.bold.saturating_sub(1);
+                self.font();
+                self.cr();
+            }
+            "ul" | "ol" | "blockquote" => {
+                self.containers.pop();
+                self.item_start = false;
+                if tag == "blockquote"
+                    || matches!(
+                        self.containers.last(),
+                        Some(Container::BulletList | Container::OrderedList(_))
+                    )
+                {
+                    self.cr();
+                    self.write(".RE\n");
+                }
+            }
+            "li" => {
+                self.item_start = false;
+                self.cr();
+            }
+            "pre" => {
+                self.pre = self.pre.saturating_sub(1);
+                self.cr();
+                self.write(".fi\n.RE\n");
+            }
+            "code" if self.pre > 0 => (),
+            "code" => {
+                self.code = self.code.saturating_sub(1);
+                self.font();
+            }
+            "em" | "i" => {
+                self.italic = self.italic.saturating_sub(1);
+                self.font();
+            }
+            "strong" | "b" | "th" => {
+                self.bold = self.bold.saturating_sub(1);
+                self.font();
+            }
+            "tr" => {
+                self.cr();
+                self.write(".br\n");
+            }
+            "a" => {
+                let Some((start, url)) = self.links.pop() else {
+                    return;
+                };
+                // url is printed after link text, unless it's the same (e.g. autolinks)
+                let url = escape_roff(&url).into_owned();
+                if self.out[start..] != *url && !url.is_empty() {
+                    self.write(" <");
+                    self.write(&url);
+                    self.write(">");
+                }
+            }
+            _ => (),
+        }
+    }
+
+    fn self_close(&mut self, tag: &str, attrs: &[(&str, String)]) {
+        match tag {
+            "br" => {
+                self.cr();
+                self.write(".br\n");
+            }
+            "hr" => {
+                self.paragraph();
+                self.write("* * *\n");
+            }
+            "img" => {
+                if let Some((_, alt)) = attrs.iter().find(|(name, _)| *name == "alt") {
+                    self.text(&alt.clone());
+                }
+            }
+            "input" if attrs.contains(&("type", "checkbox".to_owned())) => {
+                let checked = attrs.iter().any(|(name, _)| *name == "checked");
+                self.text(if checked { "[x]" } else { "[ ]" });
+            }
+            _ => (),
+        }
+    }
+
+    fn contents(&mut self, nodes: &[Node]) {
+        for node in nodes.iter() {
+            self.render(node);
+        }
+    }
+
+    fn cr(&mut self) {
+        if self.heading {
+            if !self.out.ends_with(' ') {
+                self.write(" ");
+            }
+        } else if !self.is_line_start() {
+            self.write("\n");
+        }
+    }
+
+    fn text(&mut self, text: &str) {
+        for (idx, line) in text.split('\n').enumerate() {
+            if idx > 0 {
+                if self.heading {
+                    self.cr();
+                } else {
+                    self.write("\n");
+                }
+            }
+            // lines starting with these characters are control lines
+            if self.is_line_start() && (line.starts_with('.') || line.starts_with('\'')) {
+                self.write("\\&");
+            }
+            self.write(&escape_roff(line));
+        }
+    }
+
+    fn text_raw(&mut self, text: &str) {
+        self.write(text);
+    }
+
+    fn ext(&mut self) -> &mut RenderExtSet {
+        &mut self.ext
+    }
+}
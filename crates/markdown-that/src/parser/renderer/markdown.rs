@@ -0,0 +1,628 @@
+use super::block_writer::BlockWriter;
+use super::{RenderFallback, RenderFormat, RenderRegistry};
+use crate::Node;
+use crate::parser::extset::RenderExtSet;
+use std::mem;
+use std::ops::Range;
+
+/// Settings for [format_markdown](crate::Node::format_markdown), an opinionated
+/// formatter that normalizes Markdown source.
+///
+/// Nodes can access them with [format_options](MarkdownRenderer::format_options),
+/// it returns `None` if the document is only round-tripped.
+///
+/// ```rust
+/// use markdown_that::parser::renderer::MarkdownFormatOptions;
+///
+/// let md = &mut markdown_that::MarkdownThat::new();
+/// markdown_that::plugins::cmark::add(md);
+///
+/// let options = MarkdownFormatOptions {
+///     bullet_marker: Some('*'),
+///     ..Default::default()
+/// };
+///
+/// let source = "Title\n=====\n\n- foo\n- bar\n";
+/// let formatted = md.parse(source).format_markdown(&options);
+/// assert_eq!(formatted, "# Title\n\n* foo\n* bar\n");
+/// ```
+#[derive(Debug, Clone)]
+pub struct MarkdownFormatOptions {
+    /// Marker used for all bullet lists (`-`, `+` or `*`), `None` keeps the original ones.
+    ///
+    /// A list directly following another one gets a different marker, so they aren't merged.
+    pub bullet_marker: Option<char>,
+    /// Write Setext headings (text underlined with `===` or `---`) as ATX headings (`# text`).
+    pub atx_headings: bool,
+    /// Marker used for all fenced code blocks (`` ` `` or `~`), `None` keeps the original ones.
+    /// Fences are shortened to three characters unless the code requires more.
+    pub fence_marker: Option<char>,
+    /// Re-wrap paragraphs to fit into this many columns (container prefixes included),
+    /// `None` keeps line breaks as written.
+    pub wrap_width: Option<usize>,
+}
+
+impl Default for MarkdownFormatOptions {
+    fn default() -> Self {
+        Self {
+            bullet_marker: Some('-'),
+            atx_headings: true,
+            fence_marker: Some('`'),
+            wrap_width: None,
+        }
+    }
+}
+
+/// Each node outputs its Markdown source using this API.
+///
+/// It keeps track of container blocks (blockquotes, list items) and writes
+/// their prefixes at the start of every line, so nodes only need to write
+/// their own syntax.
+///
+/// Block nodes should be wrapped in [open_block](MarkdownRenderer::open_block) and
+/// [close_block](MarkdownRenderer::close_block), which insert a blank line between
+/// sibling blocks (or a single newline inside tight lists).
+///
+/// Nodes are rendered with [NodeValue::render_markdown](crate::NodeValue::render_markdown),
+/// unless a handler for them is added to [registry](MarkdownRenderer::registry_mut).
+#[derive(Debug)]
+pub struct MarkdownRenderer<'a> {
+    out: BlockWriter,
+    source: Option<&'a str>,
+    format: Option<MarkdownFormatOptions>,
+    // byte offsets of spaces in plain text, collected while rendering a paragraph to re-wrap it
+    breaks: Option<Vec<usize>>,
+    // set when a node can't write its source back, see `keep_source`
+    lossy: bool,
+    // source ranges of top-level blocks, text between them is copied when formatting
+    blocks: Vec<Range<usize>>,
+    registry: RenderRegistry<MarkdownRenderer<'a>>,
+    ext: RenderExtSet,
+}
+
+impl<'a> MarkdownRenderer<'a> {
+    /// Create a new renderer; `source` is the original document, if available,
+    /// it is used by nodes to copy unmodified parts verbatim.
+    pub fn new(source: Option<&'a str>) -> Self {
+        Self {
+            out: BlockWriter::new(false),
+            source,
+            format: None,
+            breaks: None,
+            lossy: false,
+            blocks: Vec::new(),
+            registry: RenderRegistry::with_fallback(RenderFallback::Handler(|node, fmt| {
+                node.node_value.render_markdown(node, fmt);
+            })),
+            ext: RenderExtSet::new(),
+        }
+    }
+
+    /// Create a renderer that normalizes the document according to `options`,
+    /// see [format_markdown](crate::Node::format_markdown).
+    pub fn with_format(source: Option<&'a str>, options: MarkdownFormatOptions) -> Self {
+        Self {
+            format: Some(options),
+            ..Self::new(source)
+        }
+    }
+
+    /// Formatter settings, `None` if the document is only round-tripped.
+    pub fn format_options(&self) -> Option<&MarkdownFormatOptions> {
+        self.format.as_ref()
+    }
+
+    /// Handlers used to render nodes, custom ones can be added here.
+    pub fn registry_mut(&mut self) -> &mut RenderRegistry<Self> {
+        &mut self.registry
+    }
+
+    /// Render a single node (and its children).
+    pub fn render(&mut self, node: &Node) {
+        self.render_node(node);
+    }
+
+    /// Render children of a node that can't write its own syntax, used by the default
+    /// [render_markdown](crate::parser::node::NodeValue::render_markdown).
+    ///
+    /// When formatting, the top-level block containing such node is copied
+    /// from the source as is instead, so it isn't lost.
+    pub fn fallback(&mut self, node: &Node) {
+        self.keep_source();
+        self.contents(&node.children);
+    }
+
+    /// Mark that the node being rendered doesn't have all the information needed to
+    /// write its source back (e.g. highlighted code), so the formatter copies
+    /// the top-level block containing it from the source as is.
+    pub fn keep_source(&mut self) {
+        self.lossy = true;
+    }
+
+    /// Loop through child nodes and render each one.
+    pub fn contents(&mut self, nodes: &[Node]) {
+        for node in nodes.iter() {
+            self.render(node);
+        }
+    }
+
+    /// Render top-level blocks when formatting: text between blocks is copied from
+    /// the source as is, so blank lines and the end of file are kept, and blocks
+    /// added after parsing are separated with a blank line.
+    pub fn format_contents(&mut self, nodes: &[Node]) {
+        fn separate(result: &mut String) {
+            if !result.is_empty() && !result.ends_with('\n') {
+                result.push_str("\n\n");
+            }
+        }
+
+        // first and last parts are the ends of lines with blocks on them
+        fn has_blank_line(gap: &str) -> bool {
+            let lines = gap.split('\n').collect::<Vec<_>>();
+            lines.len() > 2
+                && lines[1..lines.len() - 1]
+                    .iter()
+                    .any(|line| line.trim().is_empty())
+        }
+
+        let Some(source) = self.source else {
+            self.contents(nodes);
+            return;
+        };
+
+        self.blocks = nodes
+            .iter()
+            .filter_map(|node| node.srcmap)
+            .map(|srcmap| {
+                let (start, end) = srcmap.get_byte_offsets();
+                start..end
+            })
+            .collect();
+
+        let start = self.out.result.len();
+        let mut result = String::new();
+        let mut pos = 0;
+        let mut changed = false;
+        // columns of the blocks written so far might differ from the source
+        let mut shifted = false;
+
+        for node in nodes.iter() {
+            let original = node
+                .srcmap
+                .map(|srcmap| srcmap.get_byte_offsets())
+                .filter(|(block_start, _)| *block_start >= pos)
+                .and_then(|(block_start, end)| Some((block_start, source.get(block_start..end)?)));
+
+            let before = self.out.result.len();
+            self.lossy = false;
+            self.render(node);
+            let rendered = match original {
+                Some((_, original)) if self.lossy => original.trim_end_matches('\n'),
+                _ => self.out.result[before..].trim_matches('\n'),
+            };
+
+            match original {
+                Some((block_start, original)) => {
+                    // rewritten blocks are separated with a blank line: source could've
+                    // ended with one that belonged to the block (e.g. `>`), and a paragraph
+                    // could continue the previous block (e.g. title of a definition)
+                    let mut gap = &source[pos..block_start];
+                    let original = original.trim_end_matches('\n');
+                    let rewritten = rendered != original;
+                    let line_start = gap.rfind('\n').map_or(0, |idx| idx + 1);
+                    let indented = line_start < gap.len();
+                    let dedent = shifted || changed || rewritten;
+                    if dedent {
+                        // indentation of the first line is a part of the rendered block,
+                        // and it could make the block a continuation of a list before it
+                        // that starts at a different column now
+                        gap = &gap[..line_start];
+                    }
+                    shifted = rewritten || (dedent && indented);
+                    if (changed || rewritten) && !gap.is_empty() && !has_blank_line(gap) {
+                        result.push('\n');
+                    }
+                    if gap.contains('\n') || result.is_empty() {
+                        result.push_str(gap);
+                    } else {
+                        // block starts on the same line as the previous one ended
+                        separate(&mut result);
+                    }
+                    result.push_str(rendered);
+                    changed = rewritten;
+                    pos = block_start + original.len();
+                }
+                // e.g. footnotes moved to the end, that are already copied from the source
+                None if rendered.is_empty() => {}
+                None => {
+                    separate(&mut result);
+                    result.push_str(rendered);
+                    changed = true;
+                }
+            }
+        }
+
+        result.push_str(&source[pos..]);
+        self.out.result.truncate(start);
+        self.out.result.push_str(&result);
+    }
+
+    /// Return the original source of the node if it's known.
+    pub fn source_of(&self, node: &Node) -> Option<&'a str> {
+        let (start, end) = node.srcmap?.get_byte_offsets();
+        self.source?.get(start..end)
+    }
+
+    /// When formatting, whether the source of the node is outside of top-level blocks,
+    /// so it's already copied as a part of text between them. Used by nodes moved
+    /// in the AST (e.g. footnote definitions), so they aren't written twice.
+    pub fn is_source_kept(&self, node: &Node) -> bool {
+        if self.format.is_none() || self.source.is_none() {
+            return false;
+        }
+        node.srcmap.is_some_and(|srcmap| {
+            let (start, end) = srcmap.get_byte_offsets();
+            !self
+                .blocks
+                .iter()
+                .any(|block| block.start < end && start < block.end)
+        })
+    }
+
+    /// When formatting, write the original source of a node if it fits on one line and
+    /// return `true`. Used by nodes that the formatter doesn't normalize, so their
+    /// syntax (e.g. reference links) isn't rewritten.
+    pub fn verbatim_line(&mut self, node: &Node) -> bool {
+        if self.format.is_none() {
+            return false;
+        }
+        match self.source_of(node).filter(|source| !source.contains('\n')) {
+            Some(source) => {
+                self.text_raw(source.trim());
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Start a new block, separating it from the previous one in the same container.
+    pub fn open_block(&mut self) {
+        self.out.open_block();
+    }
+
+    /// Finish a block started with [open_block](MarkdownRenderer::open_block).
+    pub fn close_block(&mut self) {
+        self.out.cr();
+    }
+
+    /// Mark that the last block written was a list with the given marker,
+    /// see [previous_list_marker](MarkdownRenderer::previous_list_marker).
+    pub fn close_list(&mut self, marker: char) {
+        self.out.close_list(marker);
+    }
+
+    /// If the previous block was a list, return its marker. The next block must be
+    /// written carefully, because lists absorb indented content and lists with
+    /// the same marker following them.
+    pub fn previous_list_marker(&self) -> Option<char> {
+        self.out.previous_list_marker()
+    }
+
+    /// Start a container block (e.g. blockquote or list item), every line inside
+    /// will be prefixed with `first` (first line) or `rest` (the following lines).
+    ///
+    /// Blocks inside are separated with blank lines unless `tight` is set.
+    pub fn open_container(&mut self, first: &str, rest: &str, tight: bool) {
+        self.out.open_container(first, rest, tight);
+    }
+
+    /// Finish a container block started with [open_container](MarkdownRenderer::open_container).
+    pub fn close_container(&mut self) {
+        self.out.close_container();
+    }
+
+    /// Write line break, ignored if we're at the start of a line already.
+    pub fn cr(&mut self) {
+        self.out.cr();
+    }
+
+    /// Write plain text, escaping characters that could be parsed as Markdown syntax.
+    pub fn text(&mut self, text: &str) {
+        for (idx, line) in text.split('\n').enumerate() {
+            if idx > 0 {
+                self.out.write("\n");
+            }
+            let escaped = escape_markdown(line, self.out.is_line_start());
+            self.push_text(&escaped);
+        }
+    }
+
+    /// Write plain text that was taken from the original source, only escaping
+    /// block markers in case it's moved to the start of a line.
+    pub fn text_verbatim(&mut self, text: &str) {
+        for (idx, line) in text.split('\n').enumerate() {
+            if idx > 0 {
+                self.out.write("\n");
+            }
+            match block_marker_range(line).filter(|_| self.out.is_line_start()) {
+                Some(range) => {
+                    let mut escaped = line[..range.start].to_owned();
+                    for ch in line[range.clone()].chars() {
+                        escaped.push('\\');
+                        escaped.push(ch);
+                    }
+                    escaped.push_str(&line[range.end..]);
+                    self.push_text(&escaped);
+                }
+                None => self.push_text(line),
+            }
+        }
+    }
+
+    // spaces in plain text are where a paragraph can be re-wrapped
+    fn push_text(&mut self, text: &str) {
+        if let Some(breaks) = &mut self.breaks {
+            let start = self.out.result.len();
+            breaks.extend(text.match_indices(' ').map(|(idx, _)| start + idx));
+        }
+        self.out.write(text);
+    }
+
+    /// Write Markdown source as is.
+    pub fn text_raw(&mut self, text: &str) {
+        self.out.write(text);
+    }
+
+    /// Render into a separate buffer (without any container prefixes) and return the result,
+    /// used to measure content, e.g. to align table columns.
+    pub fn capture(&mut self, f: impl FnOnce(&mut Self)) -> String {
+        let old_out = mem::replace(&mut self.out, BlockWriter::new(true));
+        let old_breaks = self.breaks.take();
+        f(self);
+        self.breaks = old_breaks;
+        mem::replace(&mut self.out, old_out).result
+    }
+
+    /// Render inline content and write it re-wrapped to fit into `width` columns
+    /// (including container prefixes).
+    ///
+    /// Lines are only broken at spaces of plain text and at soft line breaks, and never
+    /// before a word that would start a block (e.g. `-` or `1.`), so words longer than
+    /// the width are left as is.
+    pub fn write_wrapped(&mut self, width: usize, f: impl FnOnce(&mut Self)) {
+        let old_out = mem::replace(&mut self.out, BlockWriter::new(true));
+        let old_breaks = self.breaks.replace(Vec::new());
+        f(self);
+        let breaks = mem::replace(&mut self.breaks, old_breaks).unwrap_or_default();
+        let content = mem::replace(&mut self.out, old_out).result;
+
+        let prefix_width = self.out.pending_prefix().chars().count();
+        let wrapped = wrap_lines(&content, &breaks, width.saturating_sub(prefix_width));
+        self.text_raw(&wrapped);
+    }
+
+    /// Extension set to store custom stuff.
+    pub fn ext(&mut self) -> &mut RenderExtSet {
+        &mut self.ext
+    }
+}
+
+impl RenderFormat for MarkdownRenderer<'_> {
+    fn registry(&self) -> &RenderRegistry<Self> {
+        &self.registry
+    }
+
+    fn write_text(&mut self, text: &str) {
+        self.text(text);
+    }
+}
+
+impl From<MarkdownRenderer<'_>> for String {
+    fn from(f: MarkdownRenderer<'_>) -> Self {
+        f.out.result
+    }
+}
+
+/// Join words of rendered inline content (split at `breaks` and newlines) into lines
+/// of at most `width` chars, hard line breaks (newline after a backslash) are kept,
+/// and so are line breaks after an unclosed `<`.
+fn wrap_lines(content: &str, breaks: &[usize], width: usize) -> String {
+    let mut words = Vec::new();
+    let mut start = 0;
+    let mut hard_break = false;
+    for (idx, ch) in content.char_indices() {
+        let split = match ch {
+            ' ' => breaks.binary_search(&idx).is_ok(),
+            '\n' => true,
+            _ => false,
+        };
+        if !split {
+            continue;
+        }
+        if start < idx {
+            words.push((&content[start..idx], hard_break));
+            hard_break = false;
+        }
+        if ch == '\n' {
+            let line = &content[content[..idx].rfind('\n').map_or(0, |pos| pos + 1)..idx];
+            let backslashes = line.len() - line.trim_end_matches('\\').len();
+            // autolinks and `<url>` destinations can't span lines, so joining lines
+            // of text that failed to parse as those could make it valid
+            let unclosed = line
+                .match_indices('<')
+                .rfind(|(pos, _)| !line[..*pos].ends_with('\\'))
+                .is_some_and(|(pos, _)| !line[pos..].contains('>'));
+            hard_break |= backslashes % 2 == 1 || unclosed;
+        }
+        start = idx + 1;
+    }
+    if start < content.len() {
+        words.push((&content[start..], hard_break));
+    }
+
+    let mut result = String::with_capacity(content.len());
+    let mut line_start = 0;
+    let mut line_width = 0;
+    for (idx, (word, hard_break)) in words.into_iter().enumerate() {
+        let word_width = word.chars().count();
+        if idx > 0 {
+            // moving a word like `-` or `#` to the start of a line would turn it into a block,
+            // and so would leaving a line with just `***`
+            let too_long = line_width + 1 + word_width > width;
+            let can_break = block_marker_range(word).is_none()
+                && block_marker_range(&result[line_start..]).is_none();
+            if hard_break || (too_long && can_break) {
+                result.push('\n');
+                line_start = result.len();
+                line_width = 0;
+            } else {
+                result.push(' ');
+                line_width += 1;
+            }
+        }
+        result.push_str(word);
+        line_width += word_width;
+    }
+    result
+}
+
+/// Find characters that would start a block (heading, list, blockquote, etc.)
+/// if this line was at the start of a line, all of them need to be escaped.
+///
+/// Markers are ascii and only preceded by spaces, so returned range is both
+/// byte and char offsets.
+fn block_marker_range(line: &str) -> Option<Range<usize>> {
+    let pos = line.find(|ch| ch != ' ' && ch != '\t')?;
+    let rest = &line[pos..];
+    let first = rest.chars().next().filter(char::is_ascii)?;
+    let run = rest.chars().take_while(|ch| *ch == first).count();
+    let count = rest.chars().filter(|ch| *ch == first).count();
+    let after_run = rest[run..].chars().next();
+    let only_marker = rest
+        .chars()
+        .all(|ch| ch == first || ch == ' ' || ch == '\t');
+
+    let is_marker = match first {
+        // delimiter row of a table: `| --- | :-: |`
+        '|' | ':' | '-' if is_table_delimiter_row(rest) => true,
+        '>' | '<' => true,
+        '#' => run <= 6 && matches!(after_run, None | Some(' ' | '\t')),
+        '-' | '+' | '*' if matches!(after_run, None | Some(' ' | '\t')) && run == 1 => true,
+        // thematic break or setext heading underline
+        '-' | '*' | '_' | '=' => only_marker && (count >= 3 || matches!(first, '-' | '=')),
+        '`' | '~' => run >= 3,
+        '0'..='9' => {
+            let digits = rest.chars().take_while(char::is_ascii_digit).count();
+            let mut after = rest[digits..].chars();
+            digits < 10
+                && matches!(after.next(), Some('.' | ')'))
+                && matches!(after.next(), None | Some(' ' | '\t'))
+        }
+        _ => false,
+    };
+
+    if !is_marker {
+        None
+    } else if first.is_ascii_digit() {
+        // escape the delimiter after the number: `1\. foo`
+        rest.find(['.', ')']).map(|idx| pos + idx..pos + idx + 1)
+    } else if matches!(first, '`' | '~') {
+        // escaping just one backtick would leave a shorter code span opener
+        Some(pos..pos + run)
+    } else {
+        Some(pos..pos + 1)
+    }
+}
+
+fn is_table_delimiter_row(line: &str) -> bool {
+    line.contains('-')
+        && line.contains(['|', ':'])
+        && line
+            .chars()
+            .all(|ch| matches!(ch, '|' | ':' | '-' | ' ' | '\t'))
+}
+
+/// Escape characters that could start Markdown syntax, `line_start` tells
+/// if the text is written at the beginning of the line (so block markers
+/// like `#` or `-` should be escaped as well).
+fn escape_markdown(text: &str, line_start: bool) -> String {
+    let chars = text.chars().collect::<Vec<_>>();
+    let mut result = String::with_capacity(text.len());
+    let block_marker = block_marker_range(text).filter(|_| line_start);
+
+    for (idx, &ch) in chars.iter().enumerate() {
+        let prev = idx.checked_sub(1).map(|idx| chars[idx]);
+        let next = chars.get(idx + 1).copied();
+
+        let escape = match ch {
+            _ if block_marker
+                .as_ref()
+                .is_some_and(|range| range.contains(&idx)) =>
+            {
+                true
+            }
+            '\\' | '`' | '*' | '[' | ']' | '~' => true,
+            '_' => {
+                !matches!((prev, next), (Some(p), Some(n)) if p.is_alphanumeric() && n.is_alphanumeric())
+            }
+            '<' => matches!(next, None | Some('a'..='z' | 'A'..='Z' | '/' | '!' | '?')),
+            '&' => matches!(next, Some('a'..='z' | 'A'..='Z' | '0'..='9' | '#')),
+            '!' => matches!(next, None | Some('[')),
+            // closing sequence of ATX heading: `# foo #`
+            '#' if next.is_none() => {
+                let hashes = chars.iter().rev().take_while(|ch| **ch == '#').count();
+                matches!(
+                    chars.len().checked_sub(hashes + 1).map(|idx| chars[idx]),
+                    None | Some(' ' | '\t')
+                )
+            }
+            _ => false,
+        };
+
+        if escape {
+            result.push('\\');
+        }
+        result.push(ch);
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::escape_markdown;
+
+    #[test]
+    fn escape_inline() {
+        assert_eq!(escape_markdown("*foo* [bar]", false), r"\*foo\* \[bar\]");
+        assert_eq!(
+            escape_markdown("snake_case _foo_", false),
+            r"snake_case \_foo\_"
+        );
+        assert_eq!(escape_markdown("a < b, <div>", false), r"a < b, \<div>");
+        assert_eq!(escape_markdown("&amp; & co", false), r"\&amp; & co");
+        assert_eq!(escape_markdown("hi! ![img]", false), r"hi! \!\[img\]");
+    }
+
+    #[test]
+    fn escape_line_start() {
+        assert_eq!(escape_markdown("# foo", true), r"\# foo");
+        assert_eq!(escape_markdown("# foo", false), r"# foo");
+        assert_eq!(escape_markdown("#5 foo", true), r"#5 foo");
+        assert_eq!(escape_markdown("- foo", true), r"\- foo");
+        assert_eq!(escape_markdown("-5 foo", true), r"-5 foo");
+        assert_eq!(escape_markdown("===", true), r"\===");
+        assert_eq!(escape_markdown("- a | b", true), r"\- a | b");
+        assert_eq!(escape_markdown("--|:-:", true), r"\--|:-:");
+        assert_eq!(escape_markdown("> foo", true), r"\> foo");
+        assert_eq!(escape_markdown("2023. year", true), r"2023\. year");
+        assert_eq!(escape_markdown("2023. year", false), r"2023. year");
+    }
+
+    #[test]
+    fn escape_heading_closing_sequence() {
+        assert_eq!(escape_markdown("foo #", false), r"foo \#");
+        assert_eq!(escape_markdown("foo ##", false), r"foo #\#");
+        assert_eq!(escape_markdown("C#", false), r"C#");
+    }
+}
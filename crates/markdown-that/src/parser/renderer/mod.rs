@@ -0,0 +1,434 @@
+//! Renderers turning the AST into HTML, DocBook, LaTeX, Typst, man pages, Gemtext,
+//! chat messages, plain text, terminal output, or back into Markdown source.
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt::{self, Debug};
+use std::io;
+
+use crate::Node;
+use crate::common::utils::escape_html;
+use crate::parser::extset::{MarkdownThatExt, RenderExt, RenderExtSet, RootExt};
+
+mod ansi;
+mod block;
+mod block_writer;
+mod chat;
+mod docbook;
+mod gemtext;
+mod latex;
+mod man;
+mod markdown;
+mod registry;
+mod text;
+mod typst;
+pub use ansi::*;
+pub use block::*;
+pub use chat::*;
+pub use docbook::*;
+pub use gemtext::*;
+pub use latex::*;
+pub use man::*;
+pub use markdown::*;
+pub use registry::*;
+pub use text::*;
+pub use typst::*;
+
+/// Each node outputs its HTML using this API.
+///
+/// Renderer is a struct that walks through AST and collects HTML from each node
+/// into an internal buffer.
+pub trait Renderer {
+    /// Write opening HTML tag with attributes, e.g. `<a href="url">`.
+    fn open(&mut self, tag: &str, attrs: &[(&str, String)]);
+    /// Write closing HTML tag, e.g. `</a>`.
+    fn close(&mut self, tag: &str);
+    /// Write a self-closing HTML tag with attributes, e.g. `<img src="url"/>`.
+    fn self_close(&mut self, tag: &str, attrs: &[(&str, String)]);
+    /// Loop through child nodes and render each one.
+    fn contents(&mut self, nodes: &[Node]);
+    /// Write line break (`\n`). The default renderer ignores it if the last char in the buffer is `\n` already.
+    fn cr(&mut self);
+    /// Write plain text with escaping, `<div>` -> `&lt;div&gt;`.
+    fn text(&mut self, text: &str);
+    /// Write plain text without escaping, `<div>` -> `<div>`.
+    fn text_raw(&mut self, text: &str);
+    /// Extension set to store custom stuff.
+    fn ext(&mut self) -> &mut RenderExtSet;
+}
+
+/// How soft line breaks (newlines inside paragraphs) are rendered.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SoftbreakMode {
+    /// Keep as newline (default).
+    #[default]
+    Newline,
+    /// Render as hard break `<br>`, same as `breaks` option in markdown-it.
+    Break,
+    /// Replace with a single space.
+    Space,
+}
+
+/// Quotes used around attribute values.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum AttrQuote {
+    /// `<a href="url">` (default).
+    #[default]
+    Double,
+    /// `<a href='url'>`.
+    Single,
+}
+
+/// Settings for [HTMLRenderer].
+///
+/// Renderer puts a copy of these options into its [ext](Renderer::ext), so nodes
+/// can access them with `fmt.ext().get::<HtmlRenderOptions>()`.
+///
+/// ```rust
+/// use markdown_that::parser::renderer::{HtmlRenderOptions, SoftbreakMode};
+///
+/// let md = &mut markdown_that::MarkdownThat::new();
+/// markdown_that::plugins::cmark::add(md);
+///
+/// let options = HtmlRenderOptions {
+///     xhtml: true,
+///     softbreak: SoftbreakMode::Break,
+///     ..Default::default()
+/// };
+///
+/// let html = md.parse("foo\nbar").render_with(&options);
+/// assert_eq!(html, "<p>foo<br />\nbar</p>\n");
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct HtmlRenderOptions {
+    /// Write self-closing tags like this: `<br />` (required for CommonMark tests).
+    pub xhtml: bool,
+    /// How soft line breaks are rendered.
+    pub softbreak: SoftbreakMode,
+    /// CSS class prefix for fenced code blocks, overrides the one set with
+    /// [set_lang_prefix](crate::plugins::cmark::block::fence::set_lang_prefix).
+    pub lang_prefix: Option<String>,
+    /// Indent nested block tags (lists, blockquotes, tables) by this many spaces,
+    /// 0 disables pretty-printing. Contents of `<pre>` and raw HTML are never changed.
+    pub indent: usize,
+    /// Quotes used around attribute values.
+    pub attr_quote: AttrQuote,
+    /// Write extensions the way GitHub does: `<del>` for strikethrough,
+    /// `align` attribute for table cells (instead of `<s>` and `style`),
+    /// and task list checkboxes without closing slash.
+    pub gfm: bool,
+    /// Escape tags disallowed by GFM in raw HTML (`<script>`, `<style>`, etc.),
+    /// see [tagfilter](crate::plugins::gfm::tagfilter).
+    pub tagfilter: bool,
+}
+
+impl RenderExt for HtmlRenderOptions {}
+impl MarkdownThatExt for HtmlRenderOptions {}
+impl RootExt for HtmlRenderOptions {}
+
+/// Default HTML/XHTML renderer.
+///
+/// Output is written into any [fmt::Write] (`String` by default), after the first
+/// write error all further output is discarded, and the error is returned by
+/// [finish](HTMLRenderer::finish).
+///
+/// Nodes are rendered with [NodeValue::render](crate::NodeValue::render), unless
+/// a handler for them is added to [registry](HTMLRenderer::registry_mut).
+/// Custom renderers can wrap this one and forward [Renderer] calls to it.
+#[derive(Debug)]
+pub struct HTMLRenderer<W: fmt::Write = String> {
+    out: W,
+    options: HtmlRenderOptions,
+    failed: bool,
+    // true if nothing is written yet, or output ends with `\n`
+    line_start: bool,
+    // nesting level of block tags, used for pretty-printing
+    depth: usize,
+    // nesting level of `<pre>` tags, indentation is disabled inside
+    pre_depth: usize,
+    registry: RenderRegistry<HTMLRenderer<W>>,
+    ext: RenderExtSet,
+}
+
+impl HTMLRenderer {
+    /// Create renderer that writes into a `String`, use `String::from(renderer)` to get it.
+    pub fn new(options: HtmlRenderOptions) -> Self {
+        Self::with_output(String::new(), options)
+    }
+}
+
+impl Default for HTMLRenderer {
+    fn default() -> Self {
+        Self::new(HtmlRenderOptions::default())
+    }
+}
+
+impl<W: fmt::Write> HTMLRenderer<W> {
+    /// Create renderer that writes into `out`.
+    pub fn with_output(out: W, options: HtmlRenderOptions) -> Self {
+        let mut ext = RenderExtSet::new();
+        ext.insert(options.clone());
+        Self {
+            out,
+            options,
+            failed: false,
+            line_start: true,
+            depth: 0,
+            pre_depth: 0,
+            registry: RenderRegistry::with_fallback(RenderFallback::Handler(|node, fmt| {
+                node.node_value.render(node, fmt);
+            })),
+            ext,
+        }
+    }
+
+    /// Handlers used to render nodes, custom ones can be added here.
+    pub fn registry_mut(&mut self) -> &mut RenderRegistry<Self> {
+        &mut self.registry
+    }
+
+    /// Render a single node (and its children).
+    pub fn render(&mut self, node: &Node) {
+        self.render_node(node);
+    }
+
+    /// Options this renderer was created with.
+    pub fn options(&self) -> &HtmlRenderOptions {
+        &self.options
+    }
+
+    /// Return the output, and an error if any write has failed.
+    pub fn finish(self) -> (W, fmt::Result) {
+        let result = if self.failed { Err(fmt::Error) } else { Ok(()) };
+        (self.out, result)
+    }
+
+    /// Write attributes (with leading spaces), merged with [merge_attrs].
+    pub fn make_attrs(&mut self, attrs: &[(&str, String)]) {
+        for (name, value) in merge_attrs(attrs) {
+            self.make_attr(name, &value);
+        }
+    }
+
+    fn make_attr(&mut self, name: &str, value: &str) {
+        self.write(" ");
+        self.write(&escape_html(name));
+        match self.options.attr_quote {
+            AttrQuote::Double => {
+                self.write("=\"");
+                self.write(&escape_html(value));
+                self.write("\"");
+            }
+            AttrQuote::Single => {
+                self.write("='");
+                self.write(&html_escape::encode_single_quoted_attribute(value));
+                self.write("'");
+            }
+        }
+    }
+
+    fn write(&mut self, str: &str) {
+        if self.failed || str.is_empty() {
+            return;
+        }
+
+        if self.line_start
+            && self.options.indent > 0
+            && self.depth > 0
+            && self.pre_depth == 0
+            && !str.starts_with('\n')
+        {
+            let indent = " ".repeat(self.options.indent * self.depth);
+            if self.out.write_str(&indent).is_err() {
+                self.failed = true;
+                return;
+            }
+        }
+
+        self.write_unindented(str);
+    }
+
+    // same as `write`, but without pretty-printing indentation (used for raw HTML)
+    fn write_unindented(&mut self, str: &str) {
+        #[cold]
+        fn write_replacing_null(out: &mut impl fmt::Write, str: &str) -> fmt::Result {
+            for (idx, chunk) in str.split('\0').enumerate() {
+                if idx > 0 {
+                    out.write_char('\u{FFFD}')?;
+                }
+                out.write_str(chunk)?;
+            }
+            Ok(())
+        }
+
+        if self.failed || str.is_empty() {
+            return;
+        }
+
+        self.line_start = str.ends_with('\n');
+
+        let result = if str.contains('\0') {
+            // U+0000 must be replaced with U+FFFD as per commonmark spec,
+            // we do it on output to avoid messing with byte offsets
+            // for source maps (since "\0".len() != "\u{FFFD}".len())
+            write_replacing_null(&mut self.out, str)
+        } else {
+            self.out.write_str(str)
+        };
+
+        if result.is_err() {
+            self.failed = true;
+        }
+    }
+}
+
+impl From<HTMLRenderer> for String {
+    fn from(f: HTMLRenderer) -> Self {
+        f.out
+    }
+}
+
+/// Merge attributes the same way default renderer does it: all `class` values are
+/// joined with spaces, all `style` values are joined with semicolons, and the rest
+/// are kept as is. Attributes are returned in order of their first occurrence.
+pub fn merge_attrs<'a>(attrs: &'a [(&'a str, String)]) -> Vec<(&'a str, Cow<'a, str>)> {
+    let mut attr_hash = HashMap::new();
+    let mut attr_order = Vec::with_capacity(attrs.len());
+    let mut result = Vec::with_capacity(attrs.len());
+
+    for (name, value) in attrs {
+        let entry = attr_hash.entry(*name).or_insert(Vec::new());
+        entry.push(value.as_str());
+        attr_order.push(*name);
+    }
+
+    for name in attr_order {
+        let Some(value) = attr_hash.remove(name) else {
+            continue;
+        };
+
+        if name == "class" {
+            result.push((name, Cow::Owned(value.join(" "))));
+        } else if name == "style" {
+            result.push((name, Cow::Owned(value.join(";"))));
+        } else {
+            for v in value {
+                result.push((name, Cow::Borrowed(v)));
+            }
+        }
+    }
+
+    result
+}
+
+// Tags that are indented when pretty-printing, only containers are listed,
+// so paragraphs and headings are kept on one line.
+fn is_indented_tag(tag: &str) -> bool {
+    matches!(
+        tag,
+        "blockquote"
+            | "ul"
+            | "ol"
+            | "li"
+            | "dl"
+            | "table"
+            | "thead"
+            | "tbody"
+            | "tfoot"
+            | "tr"
+            | "div"
+            | "section"
+            | "details"
+            | "figure"
+    )
+}
+
+/// Adapter that allows [HTMLRenderer] to write into [io::Write],
+/// it keeps the original I/O error, so it can be returned to the caller.
+pub(crate) struct IoWriter<W: io::Write> {
+    inner: W,
+    pub error: Option<io::Error>,
+}
+
+impl<W: io::Write> IoWriter<W> {
+    pub fn new(inner: W) -> Self {
+        Self { inner, error: None }
+    }
+}
+
+impl<W: io::Write> fmt::Write for IoWriter<W> {
+    fn write_str(&mut self, str: &str) -> fmt::Result {
+        self.inner.write_all(str.as_bytes()).map_err(|err| {
+            self.error = Some(err);
+            fmt::Error
+        })
+    }
+}
+
+impl<W: fmt::Write> RenderFormat for HTMLRenderer<W> {
+    fn registry(&self) -> &RenderRegistry<Self> {
+        &self.registry
+    }
+
+    fn write_text(&mut self, text: &str) {
+        Renderer::text(self, text);
+    }
+}
+
+impl<W: fmt::Write> Renderer for HTMLRenderer<W> {
+    fn open(&mut self, tag: &str, attrs: &[(&str, String)]) {
+        self.write("<");
+        self.write(tag);
+        self.make_attrs(attrs);
+        self.write(">");
+
+        if tag == "pre" {
+            self.pre_depth += 1;
+        } else if is_indented_tag(tag) {
+            self.depth += 1;
+        }
+    }
+
+    fn close(&mut self, tag: &str) {
+        if tag == "pre" {
+            self.pre_depth = self.pre_depth.saturating_sub(1);
+        } else if is_indented_tag(tag) {
+            self.depth = self.depth.saturating_sub(1);
+        }
+
+        self.write("</");
+        self.write(tag);
+        self.write(">");
+    }
+
+    fn self_close(&mut self, tag: &str, attrs: &[(&str, String)]) {
+        self.write("<");
+        self.write(tag);
+        self.make_attrs(attrs);
+        self.write(if self.options.xhtml { " />" } else { ">" });
+    }
+
+    fn contents(&mut self, nodes: &[Node]) {
+        for node in nodes.iter() {
+            self.render(node);
+        }
+    }
+
+    fn cr(&mut self) {
+        // only push '\n' if last character isn't it
+        if !self.line_start {
+            self.write("\n");
+        }
+    }
+
+    fn text(&mut self, text: &str) {
+        self.write(&escape_html(text));
+    }
+
+    fn text_raw(&mut self, text: &str) {
+        self.write_unindented(text);
+    }
+
+    fn ext(&mut self) -> &mut RenderExtSet {
+        &mut self.ext
+    }
+}
@@ -0,0 +1,176 @@
+use std::collections::HashMap;
+use std::fmt::{self, Debug};
+
+use crate::common::TypeKey;
+use crate::{Node, NodeValue};
+
+/// Function that renders a node (and usually its children) in a specific format.
+pub type RenderHandler<F> = fn(&Node, &mut F);
+
+/// What to do with nodes that don't have a handler registered.
+pub enum RenderFallback<F> {
+    /// Render children in place of the node (default).
+    Children,
+    /// Write [collect_text](Node::collect_text) of the node.
+    Text,
+    /// Call this function, e.g. to use [NodeValue] method of the format.
+    Handler(RenderHandler<F>),
+}
+
+impl<F> Clone for RenderFallback<F> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<F> Copy for RenderFallback<F> {}
+
+impl<F> Debug for RenderFallback<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Children => write!(f, "Children"),
+            Self::Text => write!(f, "Text"),
+            Self::Handler(_) => write!(f, "Handler"),
+        }
+    }
+}
+
+/// Render functions for output format `F`, keyed by node type.
+///
+/// Every renderer dispatches nodes through its registry, so this is the one place
+/// to change how a node type is rendered in any format. Formats that have a
+/// [NodeValue] method ([render](NodeValue::render) for HTML, LaTeX and man pages,
+/// [render_markdown](NodeValue::render_markdown), [render_text](NodeValue::render_text),
+/// [render_ansi](NodeValue::render_ansi)) use it as the fallback, so a registered
+/// handler takes precedence over the method of the node.
+///
+/// It allows renderers to support nodes without touching node types, and
+/// plugins to support several output formats (by exporting handlers for them):
+///
+/// ```rust
+/// use markdown_that::{Node, Renderer};
+/// use markdown_that::parser::renderer::{LatexRenderer, RenderFormat};
+/// use markdown_that::plugins::cmark::inline::link::Link;
+///
+/// // write links as footnotes instead of `\href`
+/// fn link(node: &Node, fmt: &mut LatexRenderer) {
+///     let url = &node.cast::<Link>().unwrap().url;
+///     fmt.render_children(node);
+///     fmt.text_raw(&format!("\\footnote{{\\url{{{url}}}}}"));
+/// }
+///
+/// let md = &mut markdown_that::MarkdownThat::new();
+/// markdown_that::plugins::cmark::add(md);
+///
+/// let mut fmt = LatexRenderer::default();
+/// fmt.registry_mut().add::<Link>(link);
+/// fmt.render(&md.parse("[foo](http://bar)"));
+/// assert_eq!(String::from(fmt), "foo\\footnote{\\url{http://bar}}\n");
+/// ```
+pub struct RenderRegistry<F> {
+    handlers: HashMap<TypeKey, RenderHandler<F>>,
+    fallback: RenderFallback<F>,
+}
+
+impl<F> RenderRegistry<F> {
+    /// Create empty registry, nodes are replaced with their children.
+    pub fn new() -> Self {
+        Self::with_fallback(RenderFallback::Children)
+    }
+
+    /// Create empty registry with custom fallback.
+    pub fn with_fallback(fallback: RenderFallback<F>) -> Self {
+        Self {
+            handlers: HashMap::new(),
+            fallback,
+        }
+    }
+
+    /// Set (or replace) a handler for the node type.
+    pub fn add<T: NodeValue>(&mut self, handler: RenderHandler<F>) {
+        self.handlers.insert(TypeKey::of::<T>(), handler);
+    }
+
+    /// Remove a handler for the node type, fallback is used for it instead.
+    pub fn remove<T: NodeValue>(&mut self) {
+        self.handlers.remove(&TypeKey::of::<T>());
+    }
+
+    /// Check if a handler for the node type is registered.
+    pub fn contains<T: NodeValue>(&self) -> bool {
+        self.handlers.contains_key(&TypeKey::of::<T>())
+    }
+
+    /// Return a handler for the node, if any.
+    pub fn get(&self, node: &Node) -> Option<RenderHandler<F>> {
+        self.handlers.get(&node.node_type).copied()
+    }
+
+    /// Return fallback used for nodes without a handler.
+    pub fn fallback(&self) -> RenderFallback<F> {
+        self.fallback
+    }
+
+    /// Change fallback used for nodes without a handler.
+    pub fn set_fallback(&mut self, fallback: RenderFallback<F>) {
+        self.fallback = fallback;
+    }
+}
+
+impl<F> Default for RenderRegistry<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F> Clone for RenderRegistry<F> {
+    fn clone(&self) -> Self {
+        Self {
+            handlers: self.handlers.clone(),
+            fallback: self.fallback,
+        }
+    }
+}
+
+impl<F> Debug for RenderRegistry<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RenderRegistry")
+            .field("handlers", &self.handlers.keys().collect::<Vec<_>>())
+            .field("fallback", &self.fallback)
+            .finish()
+    }
+}
+
+/// Output format that dispatches nodes through [RenderRegistry].
+///
+/// Renderer keeps the registry and its output state, handlers receive
+/// the renderer itself, and call [render_node](RenderFormat::render_node)
+/// (or a method of the renderer that uses it) to render children.
+pub trait RenderFormat: Sized {
+    /// Handlers used by this renderer.
+    fn registry(&self) -> &RenderRegistry<Self>;
+
+    /// Write plain text, escaped for this format, used by [RenderFallback::Text].
+    fn write_text(&mut self, text: &str);
+
+    /// Render a node with the registered handler or the fallback.
+    fn render_node(&mut self, node: &Node) {
+        if let Some(handler) = self.registry().get(node) {
+            handler(node, self);
+            return;
+        }
+
+        match self.registry().fallback() {
+            RenderFallback::Children => self.render_children(node),
+            RenderFallback::Text => self.write_text(&node.collect_text()),
+            RenderFallback::Handler(handler) => handler(node, self),
+        }
+    }
+
+    /// Render all children of a node.
+    fn render_children(&mut self, node: &Node) {
+        for child in node.children.iter() {
+            self.render_node(child);
+        }
+    }
+}
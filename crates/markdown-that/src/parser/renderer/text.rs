@@ -0,0 +1,129 @@
+use super::block_writer::BlockWriter;
+use super::{RenderFallback, RenderFormat, RenderRegistry};
+use crate::Node;
+use crate::parser::extset::RenderExtSet;
+
+/// Each node outputs its plain text representation using this API.
+///
+/// Markup is dropped, blocks are separated with blank lines, list items
+/// and blockquotes are prefixed the same way they'd look in an email.
+///
+/// Block nodes should be wrapped in [open_block](TextRenderer::open_block) and
+/// [close_block](TextRenderer::close_block).
+///
+/// Nodes are rendered with [NodeValue::render_text](crate::NodeValue::render_text),
+/// unless a handler for them is added to [registry](TextRenderer::registry_mut).
+#[derive(Debug)]
+pub struct TextRenderer {
+    out: BlockWriter,
+    cell_separator: String,
+    registry: RenderRegistry<TextRenderer>,
+    ext: RenderExtSet,
+}
+
+impl TextRenderer {
+    pub fn new() -> Self {
+        Self {
+            out: BlockWriter::new(false),
+            cell_separator: "\t".to_owned(),
+            registry: RenderRegistry::with_fallback(RenderFallback::Handler(|node, fmt| {
+                node.node_value.render_text(node, fmt);
+            })),
+            ext: RenderExtSet::new(),
+        }
+    }
+
+    /// Set string written between table cells, tab by default (`" | "` is another common choice).
+    pub fn set_cell_separator(&mut self, separator: &str) {
+        separator.clone_into(&mut self.cell_separator);
+    }
+
+    /// String written between table cells.
+    pub fn cell_separator(&self) -> &str {
+        &self.cell_separator
+    }
+
+    /// Handlers used to render nodes, custom ones can be added here.
+    pub fn registry_mut(&mut self) -> &mut RenderRegistry<Self> {
+        &mut self.registry
+    }
+
+    /// Render a single node (and its children).
+    pub fn render(&mut self, node: &Node) {
+        self.render_node(node);
+    }
+
+    /// Loop through child nodes and render each one.
+    pub fn contents(&mut self, nodes: &[Node]) {
+        for node in nodes.iter() {
+            self.render(node);
+        }
+    }
+
+    /// Start a new block, separating it from the previous one in the same container.
+    pub fn open_block(&mut self) {
+        self.out.open_block();
+    }
+
+    /// Finish a block started with [open_block](TextRenderer::open_block).
+    pub fn close_block(&mut self) {
+        self.out.cr();
+    }
+
+    /// Start a container block (e.g. blockquote or list item), every line inside
+    /// will be prefixed with `first` (first line) or `rest` (the following lines).
+    ///
+    /// Blocks inside are separated with blank lines unless `tight` is set.
+    pub fn open_container(&mut self, first: &str, rest: &str, tight: bool) {
+        self.out.open_container(first, rest, tight);
+    }
+
+    /// Finish a container block started with [open_container](TextRenderer::open_container).
+    pub fn close_container(&mut self) {
+        self.out.close_container();
+    }
+
+    /// Write line break, ignored if we're at the start of a line already.
+    pub fn cr(&mut self) {
+        self.out.cr();
+    }
+
+    /// Write text as is.
+    pub fn text(&mut self, text: &str) {
+        self.out.write(text);
+    }
+
+    /// Render into a separate buffer (without any container prefixes) and return the result.
+    pub fn capture(&mut self, f: impl FnOnce(&mut Self)) -> String {
+        let old_out = std::mem::replace(&mut self.out, BlockWriter::new(true));
+        f(self);
+        std::mem::replace(&mut self.out, old_out).result
+    }
+
+    /// Extension set to store custom stuff.
+    pub fn ext(&mut self) -> &mut RenderExtSet {
+        &mut self.ext
+    }
+}
+
+impl RenderFormat for TextRenderer {
+    fn registry(&self) -> &RenderRegistry<Self> {
+        &self.registry
+    }
+
+    fn write_text(&mut self, text: &str) {
+        self.text(text);
+    }
+}
+
+impl Default for TextRenderer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<TextRenderer> for String {
+    fn from(f: TextRenderer) -> Self {
+        f.out.result
+    }
+}
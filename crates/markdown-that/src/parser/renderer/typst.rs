@@ -0,0 +1,518 @@
+use super::block_writer::BlockWriter;
+use super::{RenderFallback, RenderFormat, RenderRegistry};
+use crate::Node;
+use crate::common::utils::{escape_typst, unescape_all};
+use crate::parser::extset::RenderExtSet;
+use crate::parser::inline::{Text, TextSpecial};
+use crate::plugins::cmark::block::blockquote::Blockquote;
+use crate::plugins::cmark::block::code::CodeBlock;
+use crate::plugins::cmark::block::fence::CodeFence;
+use crate::plugins::cmark::block::heading::ATXHeading;
+use crate::plugins::cmark::block::hr::ThematicBreak;
+use crate::plugins::cmark::block::lheading::SetextHeader;
+use crate::plugins::cmark::block::list::{BulletList, ListItem, ListRenderContext, OrderedList};
+use crate::plugins::cmark::block::paragraph::Paragraph;
+use crate::plugins::cmark::inline::autolink::Autolink;
+use crate::plugins::cmark::inline::backticks::CodeInline;
+use crate::plugins::cmark::inline::emphasis::{Em, Strong};
+use crate::plugins::cmark::inline::image::Image;
+use crate::plugins::cmark::inline::link::Link;
+use crate::plugins::cmark::inline::newline::{Hardbreak, Softbreak};
+use crate::plugins::extra::footnotes::{FootnoteItem, FootnoteReference, FootnoteSection};
+use crate::plugins::extra::strikethrough::Strikethrough;
+use crate::plugins::extra::tables::{
+    ColumnAlignment, Table, TableBody, TableCell, TableHead, TableRow,
+};
+use crate::plugins::gfm::tasklist::TaskListItem;
+
+/// Renderer producing [Typst](https://typst.app) markup.
+///
+/// Unlike LaTeX and man renderers, it doesn't go through HTML tags, every node
+/// type has a handler in [registry](TypstRenderer::registry_mut), and nodes
+/// without one are replaced with their children.
+///
+/// Handlers should wrap block nodes in [open_block](TypstRenderer::open_block) and
+/// [close_block](TypstRenderer::close_block), and write text with
+/// [text](TypstRenderer::text), so markup characters are escaped.
+#[derive(Debug)]
+pub struct TypstRenderer {
+    out: BlockWriter,
+    // inside of a heading, which must be kept on one line
+    heading: bool,
+    // start of `_emph_` or `*strong*` written last, and a function to use instead
+    // if a word continues after it (delimiters don't work inside words)
+    closed_emph: Option<(usize, &'static str)>,
+    // embedded expression (e.g. `#link(..)[..]`) written last, following text
+    // could be parsed as a part of it
+    closed_expr: bool,
+    registry: RenderRegistry<TypstRenderer>,
+    ext: RenderExtSet,
+}
+
+impl TypstRenderer {
+    pub fn new() -> Self {
+        Self {
+            out: BlockWriter::new(false),
+            heading: false,
+            closed_emph: None,
+            closed_expr: false,
+            registry: Self::default_registry(),
+            ext: RenderExtSet::new(),
+        }
+    }
+
+    /// Handlers for CommonMark nodes, strikethrough and tables.
+    pub fn default_registry() -> RenderRegistry<Self> {
+        let mut registry = RenderRegistry::<Self>::with_fallback(RenderFallback::Children);
+
+        registry.add::<Text>(|node, fmt| fmt.text(&node.cast::<Text>().unwrap().content));
+        registry.add::<TextSpecial>(|node, fmt| {
+            fmt.text(&node.cast::<TextSpecial>().unwrap().content);
+        });
+        registry.add::<Softbreak>(|_, fmt| fmt.text_raw("\n"));
+        registry.add::<Hardbreak>(|_, fmt| fmt.text_raw("\\\n"));
+
+        registry.add::<Paragraph>(|node, fmt| {
+            fmt.open_block();
+            fmt.render_children(node);
+            fmt.close_block();
+        });
+
+        registry.add::<ATXHeading>(|node, fmt| {
+            heading(node, fmt, node.cast::<ATXHeading>().unwrap().level);
+        });
+        registry.add::<SetextHeader>(|node, fmt| {
+            heading(node, fmt, node.cast::<SetextHeader>().unwrap().level);
+        });
+
+        registry.add::<ThematicBreak>(|_, fmt| {
+            fmt.open_block();
+            fmt.expression("#line(length: 100%)");
+            fmt.close_block();
+        });
+
+        registry.add::<Blockquote>(|node, fmt| {
+            fmt.open_block();
+            fmt.text_raw("#quote(block: true)[");
+            fmt.out.open_nested_container("  ", "  ", false);
+            fmt.render_children(node);
+            fmt.out.close_container();
+            fmt.expression("]");
+            fmt.close_block();
+        });
+
+        registry.add::<BulletList>(|node, fmt| {
+            list(node, fmt, None);
+        });
+        registry.add::<OrderedList>(|node, fmt| {
+            list(node, fmt, Some(node.cast::<OrderedList>().unwrap().start));
+        });
+        registry.add::<ListItem>(|node, fmt| {
+            ListRenderContext::render_item(node, fmt, "");
+        });
+        registry.add::<TaskListItem>(|node, fmt| {
+            let checkbox = if node.cast::<TaskListItem>().unwrap().checked {
+                "☒ "
+            } else {
+                "☐ "
+            };
+            ListRenderContext::render_item(node, fmt, checkbox);
+        });
+
+        registry.add::<FootnoteReference>(|node, fmt| {
+            let id = node.cast::<FootnoteReference>().unwrap().id;
+            fmt.text(&format!("[{}]", id + 1));
+        });
+        // footnotes are numbered in order, so the section is an ordered list
+        registry.add::<FootnoteSection>(|node, fmt| list(node, fmt, Some(1)));
+        registry.add::<FootnoteItem>(|node, fmt| {
+            ListRenderContext::render_item(node, fmt, "");
+        });
+
+        registry.add::<CodeFence>(|node, fmt| {
+            let fence = node.cast::<CodeFence>().unwrap();
+            let info = unescape_all(&fence.info);
+            fmt.code_block(&fence.content, info.split_whitespace().next());
+        });
+        registry.add::<CodeBlock>(|node, fmt| {
+            fmt.code_block(&node.cast::<CodeBlock>().unwrap().content, None);
+        });
+        #[cfg(feature = "syntect")]
+        registry.add::<crate::plugins::extra::syntect::SyntectSnippet>(|node, fmt| {
+            let snippet = node
+                .cast::<crate::plugins::extra::syntect::SyntectSnippet>()
+                .unwrap();
+            fmt.code_block(
+                snippet.code().trim_matches('\n'),
+                snippet.language.as_deref(),
+            );
+        });
+
+        registry.add::<CodeInline>(|node, fmt| {
+            let content = node.collect_text();
+            if content.contains('`') {
+                fmt.expression(&format!("#raw({})", typst_string(&content)));
+            } else {
+                fmt.text_raw(&format!("`{content}`"));
+            }
+        });
+
+        registry.add::<Em>(|node, fmt| emphasis(node, fmt, "_", "emph"));
+        registry.add::<Strong>(|node, fmt| emphasis(node, fmt, "*", "strong"));
+        registry.add::<Strikethrough>(|node, fmt| {
+            fmt.text_raw("#strike[");
+            fmt.render_children(node);
+            fmt.expression("]");
+        });
+
+        registry.add::<Link>(|node, fmt| link(node, fmt, &node.cast::<Link>().unwrap().url));
+        registry.add::<Autolink>(|node, fmt| {
+            link(node, fmt, &node.cast::<Autolink>().unwrap().url);
+        });
+        #[cfg(feature = "linkify")]
+        registry.add::<crate::plugins::extra::linkify::Linkified>(|node, fmt| {
+            let url = &node
+                .cast::<crate::plugins::extra::linkify::Linkified>()
+                .unwrap()
+                .url;
+            link(node, fmt, url);
+        });
+
+        registry.add::<Image>(|node, fmt| {
+            let url = &node.cast::<Image>().unwrap().url;
+            let alt = node.collect_text();
+            if alt.is_empty() {
+                fmt.expression(&format!("#image({})", typst_string(url)));
+            } else {
+                fmt.expression(&format!(
+                    "#image({}, alt: {})",
+                    typst_string(url),
+                    typst_string(&alt)
+                ));
+            }
+        });
+
+        registry.add::<Table>(|node, fmt| {
+            let alignments = &node.cast::<Table>().unwrap().alignments;
+            fmt.open_block();
+            fmt.text_raw("#table(");
+            fmt.out.open_nested_container("  ", "  ", true);
+            fmt.text_raw(&format!("columns: {},", alignments.len()));
+            if alignments
+                .iter()
+                .any(|a| !matches!(a, ColumnAlignment::None))
+            {
+                let align = alignments
+                    .iter()
+                    .map(|a| match a {
+                        ColumnAlignment::None => "auto",
+                        ColumnAlignment::Left => "left",
+                        ColumnAlignment::Center => "center",
+                        ColumnAlignment::Right => "right",
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                fmt.cr();
+                // trailing comma makes a single value an array
+                let comma = if alignments.len() == 1 { "," } else { "" };
+                fmt.text_raw(&format!("align: ({align}{comma}),"));
+            }
+            fmt.render_children(node);
+            fmt.out.close_container();
+            fmt.expression(")");
+            fmt.close_block();
+        });
+        registry.add::<TableHead>(|node, fmt| {
+            fmt.cr();
+            fmt.text_raw("table.header(");
+            fmt.render_children(node);
+            fmt.text_raw("),");
+        });
+        registry.add::<TableBody>(|node, fmt| {
+            for row in node.children.iter() {
+                fmt.cr();
+                fmt.render_node(row);
+                fmt.text_raw(",");
+            }
+        });
+        registry.add::<TableRow>(|node, fmt| {
+            for (idx, cell) in node.children.iter().enumerate() {
+                if idx > 0 {
+                    fmt.text_raw(", ");
+                }
+                fmt.render_node(cell);
+            }
+        });
+        registry.add::<TableCell>(|node, fmt| {
+            fmt.text_raw("[");
+            fmt.render_children(node);
+            fmt.text_raw("]");
+        });
+
+        registry
+    }
+
+    /// Handlers used to render nodes, custom ones can be added here.
+    pub fn registry_mut(&mut self) -> &mut RenderRegistry<Self> {
+        &mut self.registry
+    }
+
+    /// Render a single node (and its children).
+    pub fn render(&mut self, node: &Node) {
+        self.render_node(node);
+    }
+
+    /// Loop through child nodes and render each one.
+    pub fn contents(&mut self, nodes: &[Node]) {
+        for node in nodes.iter() {
+            self.render(node);
+        }
+    }
+
+    /// Start a new block, separating it from the previous one in the same container.
+    pub fn open_block(&mut self) {
+        self.out.open_block();
+    }
+
+    /// Finish a block started with [open_block](TypstRenderer::open_block).
+    pub fn close_block(&mut self) {
+        self.out.cr();
+    }
+
+    /// Start a container block (e.g. list item), every line inside will be
+    /// prefixed with `first` (first line) or `rest` (the following lines).
+    ///
+    /// Blocks inside are separated with blank lines unless `tight` is set.
+    pub fn open_container(&mut self, first: &str, rest: &str, tight: bool) {
+        self.out.open_container(first, rest, tight);
+    }
+
+    /// Finish a container block started with [open_container](TypstRenderer::open_container).
+    pub fn close_container(&mut self) {
+        self.out.close_container();
+    }
+
+    /// Write line break, ignored if we're at the start of a line already.
+    pub fn cr(&mut self) {
+        self.out.cr();
+    }
+
+    /// Write raw block with an optional language, e.g. `` ```rust ``.
+    pub fn code_block(&mut self, content: &str, language: Option<&str>) {
+        // fence must be longer than any run of backticks inside
+        let mut longest = 0;
+        let mut run = 0;
+        for ch in content.chars() {
+            run = if ch == '`' { run + 1 } else { 0 };
+            longest = longest.max(run);
+        }
+        let fence = "`".repeat(longest.max(2) + 1);
+
+        self.open_block();
+        self.text_raw(&fence);
+        self.text_raw(language.unwrap_or_default());
+        self.text_raw("\n");
+        self.text_raw(content);
+        self.cr();
+        self.text_raw(&fence);
+        self.close_block();
+    }
+
+    /// Write plain text, escaping characters that could be parsed as Typst markup.
+    pub fn text(&mut self, text: &str) {
+        for (idx, line) in text.split('\n').enumerate() {
+            if idx > 0 {
+                self.write("\n");
+            }
+            let mut escaped = escape_typst(line).into_owned();
+            if self.out.is_line_start() {
+                escape_line_start(&mut escaped);
+            } else if escaped.starts_with('.') && self.after_number() {
+                escaped.insert(0, '\\');
+            }
+            self.write(&escaped);
+        }
+    }
+
+    /// Write Typst markup as is.
+    pub fn text_raw(&mut self, text: &str) {
+        self.write(text);
+    }
+
+    /// Write embedded expression (or the end of it), e.g. `#link("url")`,
+    /// text written after it is separated if it could continue the expression.
+    pub fn expression(&mut self, text: &str) {
+        self.write(text);
+        self.closed_expr = true;
+    }
+
+    /// Extension set to store custom stuff.
+    pub fn ext(&mut self) -> &mut RenderExtSet {
+        &mut self.ext
+    }
+
+    // Check if the current line only has a number so far (after container prefixes),
+    // so `.` written next would turn it into a numbered list item.
+    fn after_number(&self) -> bool {
+        let result = &self.out.result;
+        let line = &result[result.rfind('\n').map_or(0, |idx| idx + 1)..];
+        let rest = line.trim_end_matches(|ch: char| ch.is_ascii_digit());
+        rest.len() < line.len()
+            && (rest.trim().is_empty() || rest.ends_with("- ") || rest.ends_with(". "))
+    }
+
+    fn write(&mut self, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+
+        let starts_word = text.starts_with(char::is_alphanumeric);
+        if let Some((start, function)) = self.closed_emph.take() {
+            let result = &mut self.out.result;
+            if starts_word && result.ends_with(&result[start..start + 1]) {
+                result.pop();
+                result.push(']');
+                result.replace_range(start..start + 1, &format!("#{function}["));
+            }
+        }
+
+        if std::mem::take(&mut self.closed_expr) && !self.out.is_line_start() {
+            let mut chars = text.chars();
+            let continues = match chars.next() {
+                Some('(' | '[') => true,
+                Some('.') => chars
+                    .next()
+                    .is_some_and(|ch| ch.is_alphanumeric() || ch == '_'),
+                _ => false,
+            };
+            if continues {
+                self.out.write(";");
+            }
+        }
+
+        if self.heading {
+            self.out.write(&text.replace('\n', " "));
+        } else {
+            self.out.write(text);
+        }
+    }
+}
+
+impl RenderFormat for TypstRenderer {
+    fn registry(&self) -> &RenderRegistry<Self> {
+        &self.registry
+    }
+
+    fn write_text(&mut self, text: &str) {
+        self.text(text);
+    }
+}
+
+impl Default for TypstRenderer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<TypstRenderer> for String {
+    fn from(f: TypstRenderer) -> Self {
+        f.out.result
+    }
+}
+
+fn heading(node: &Node, fmt: &mut TypstRenderer, level: u8) {
+    fmt.open_block();
+    fmt.text_raw(&"=".repeat(level as usize));
+    fmt.text_raw(" ");
+    fmt.heading = true;
+    fmt.render_children(node);
+    fmt.heading = false;
+    fmt.close_block();
+}
+
+fn list(node: &Node, fmt: &mut TypstRenderer, start: Option<u32>) {
+    // list is loose if any of its items contains a paragraph
+    let tight = !node
+        .children
+        .iter()
+        .any(|item| item.children.iter().any(|child| child.is::<Paragraph>()));
+    ListRenderContext {
+        marker: if start.is_some() { '.' } else { '-' },
+        next_number: start,
+        tight,
+    }
+    .render_list(node, fmt);
+}
+
+fn emphasis(node: &Node, fmt: &mut TypstRenderer, delimiter: &str, function: &'static str) {
+    if fmt.out.result.ends_with(char::is_alphanumeric) && !fmt.out.is_line_start() {
+        fmt.text_raw(&format!("#{function}["));
+        fmt.render_children(node);
+        fmt.expression("]");
+    } else {
+        fmt.text_raw(delimiter);
+        let start = fmt.out.result.len() - delimiter.len();
+        fmt.render_children(node);
+        fmt.text_raw(delimiter);
+        fmt.closed_emph = Some((start, function));
+    }
+}
+
+fn link(node: &Node, fmt: &mut TypstRenderer, url: &str) {
+    fmt.text_raw(&format!("#link({})", typst_string(url)));
+    // autolinks keep their text in `TextSpecial`, which `collect_text` skips
+    let mut text = String::new();
+    node.walk(|node, _| {
+        if let Some(node) = node.cast::<Text>() {
+            text.push_str(&node.content);
+        } else if let Some(node) = node.cast::<TextSpecial>() {
+            text.push_str(&node.content);
+        }
+    });
+
+    if text == url {
+        fmt.closed_expr = true;
+    } else {
+        fmt.text_raw("[");
+        fmt.render_children(node);
+        fmt.expression("]");
+    }
+}
+
+// Typst string literal.
+fn typst_string(str: &str) -> String {
+    let mut result = String::with_capacity(str.len() + 2);
+    result.push('"');
+    for ch in str.chars() {
+        match ch {
+            '\\' | '"' => {
+                result.push('\\');
+                result.push(ch);
+            }
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            '\0' => result.push('\u{FFFD}'),
+            _ => result.push(ch),
+        }
+    }
+    result.push('"');
+    result
+}
+
+// Escape a marker that would start a heading or a list item at the start of a line.
+fn escape_line_start(line: &mut String) {
+    let indent = line.len() - line.trim_start_matches(' ').len();
+    let rest = &line[indent..];
+    if rest.starts_with(['=', '-', '+', '/']) {
+        line.insert(indent, '\\');
+        return;
+    }
+    let digits = rest.len()
+        - rest
+            .trim_start_matches(|ch: char| ch.is_ascii_digit())
+            .len();
+    if digits > 0 && rest[digits..].starts_with('.') {
+        line.insert(indent + digits, '\\');
+    }
+}
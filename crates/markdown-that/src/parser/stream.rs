@@ -0,0 +1,268 @@
+//! Streaming parser, emits top-level blocks as soon as they are complete.
+//!
+//! ```rust
+//! let md = &mut markdown_that::MarkdownThat::new();
+//! markdown_that::plugins::cmark::add(md);
+//!
+//! let mut stream = md.stream();
+//! // the last block is kept until the next one starts, so nothing is emitted yet
+//! assert!(stream.push("# title\n\nfoo").is_empty());
+//!
+//! let blocks = stream.push("\nbar\n\nbaz");
+//! assert_eq!(blocks.len(), 1);
+//! assert_eq!(blocks[0].render(), "<h1>title</h1>\n");
+//!
+//! let html: String = stream.finish().iter().map(|node| node.render()).collect();
+//! assert_eq!(html, "<p>foo\nbar</p>\n<p>baz</p>\n");
+//! ```
+use crate::common::TypeKey;
+use crate::common::sourcemap::{SourcePos, SourceWithLineStarts};
+use crate::parser::core::Root;
+use crate::parser::extset::RootExtSet;
+use crate::parser::limits::ParseBudget;
+use crate::plugins::cmark::block::blockquote::Blockquote;
+use crate::plugins::cmark::block::fence::CodeFence;
+use crate::plugins::cmark::block::list::{BulletList, OrderedList};
+use crate::plugins::cmark::block::paragraph::Paragraph;
+use crate::plugins::sourcepos::{SyntaxPosRule, set_sourcepos};
+use crate::{MarkdownThat, Node};
+
+/// Push-style parser created with [MarkdownThat::stream].
+///
+/// Input is given in chunks of any size (they don't need to end on line
+/// boundaries), and each top-level block is returned when the next one starts,
+/// because until then more lines could be added to it. Returned nodes are fully
+/// parsed (including inline content and core rules), and their source maps
+/// are byte offsets in the whole input.
+///
+/// Link references are resolved with definitions seen so far, so unlike
+/// [parse](MarkdownThat::parse), a link can't refer to a definition located
+/// after it in the document.
+///
+/// Core rules that look at the whole document (e.g. [footnotes](crate::plugins::extra::footnotes)
+/// that are collected into a single section) can't run on a part of it, so if any
+/// of them is enabled (see [mark_document_rule](MarkdownThat::mark_document_rule)),
+/// all blocks are returned by [finish](BlockStream::finish).
+///
+/// [Limits](MarkdownThat::limits) on node count, nesting and time apply to each
+/// parsed part of the input separately, the size limit isn't applied.
+#[derive(Debug)]
+pub struct BlockStream<'a> {
+    md: &'a MarkdownThat,
+    // input that belongs to unfinished blocks, always starts at a line start
+    buffer: String,
+    // offset in the buffer (line start) that the next scan for block boundaries
+    // starts from, everything before it is known to belong to the last block
+    resume: usize,
+    // offset in the buffer up to which lines were seen by the last scan or check,
+    // and the last block if lines after it can be checked without a scan
+    checked: usize,
+    open: Option<OpenBlock>,
+    // byte offset and line number of the buffer in the whole input
+    offset: usize,
+    line: u32,
+    ext: RootExtSet,
+}
+
+impl MarkdownThat {
+    /// Create a [BlockStream] to parse a document chunk by chunk.
+    pub fn stream(&self) -> BlockStream<'_> {
+        BlockStream {
+            md: self,
+            buffer: String::new(),
+            resume: 0,
+            checked: 0,
+            open: None,
+            offset: 0,
+            line: 0,
+            ext: RootExtSet::new(),
+        }
+    }
+}
+
+impl BlockStream<'_> {
+    /// Add input, and return blocks that were completed by it.
+    pub fn push(&mut self, chunk: &str) -> Vec<Node> {
+        self.buffer.push_str(chunk);
+
+        // `\r` at the end might be a part of `\r\n`
+        let complete = self.buffer.strip_suffix('\r').unwrap_or(&self.buffer);
+        let complete = complete.rfind(['\n', '\r']).map_or(0, |pos| pos + 1);
+        if complete <= self.resume || self.md.has_document_rules() {
+            return Vec::new();
+        }
+
+        // scan takes time proportional to the size of the last block, so it's skipped
+        // while new lines can only continue that block (e.g. text lines of a paragraph)
+        let lines = &self.buffer[self.checked.max(self.resume)..complete];
+        if let Some(open) = self.open
+            && split_lines(lines).all(|line| open.continues(line))
+        {
+            self.checked = complete;
+            return Vec::new();
+        }
+        self.open = None;
+
+        // find where the last block starts, all blocks before it are finished;
+        // root data is thrown away here, it's collected when finished blocks are parsed again
+        let scanned = &self.buffer[self.resume..complete];
+        let mut ext = RootExtSet::new();
+        ext.insert(ParseBudget::new(&self.md.limits));
+        let blocks = self.md.block.parse(
+            scanned,
+            Node::new(Root::new(String::new())),
+            self.md,
+            &mut ext,
+        );
+        let Some(last) = blocks.children.last() else {
+            return Vec::new();
+        };
+        let line_start = |node: &Node| {
+            let (start, _) = node.srcmap?.get_byte_offsets();
+            Some(
+                self.resume
+                    + scanned[..start]
+                        .rfind(['\n', '\r'])
+                        .map_or(0, |pos| pos + 1),
+            )
+        };
+        let Some(start) = line_start(last) else {
+            return Vec::new();
+        };
+
+        // when scan starts in the middle of a list, its first block is the rest of that list
+        let continued = self.resume > 0;
+        let end = if continued && blocks.children.len() == 1 {
+            0
+        } else {
+            start
+        };
+
+        // top-level list items are parsed the same way without the items before them,
+        // so long lists are scanned from the last item instead of their first line
+        let is_list = last.is::<BulletList>() || last.is::<OrderedList>();
+        let (resume, open) = match last.children.last().filter(|_| is_list) {
+            Some(item) => (line_start(item).unwrap_or(end), None),
+            None if continued && end == 0 => (0, None),
+            None => (start, OpenBlock::new(last, scanned)),
+        };
+        self.resume = resume - end;
+        self.checked = complete - end;
+        self.open = open;
+
+        self.parse_finished(end)
+    }
+
+    /// End the input, and return all remaining blocks.
+    pub fn finish(mut self) -> Vec<Node> {
+        self.parse_finished(self.buffer.len())
+    }
+
+    // parse first `len` bytes of the buffer, they must only contain finished blocks
+    fn parse_finished(&mut self, len: usize) -> Vec<Node> {
+        if len == 0 {
+            return Vec::new();
+        }
+
+        let content = self.buffer.drain(..len).collect::<String>();
+        let mut ext = std::mem::take(&mut self.ext);
+        ext.insert(ParseBudget::new(&self.md.limits));
+        let mut root = Node::new(Root { content, ext });
+        root.srcmap = Some(SourcePos::new(0, len));
+        for rule in self.md.core_rules().map(|(_, rule)| rule) {
+            rule(&mut root, self.md);
+        }
+
+        let data = root.cast_mut::<Root>().unwrap();
+        let content = std::mem::take(&mut data.content);
+        self.ext = std::mem::take(&mut data.ext);
+        self.ext.remove::<ParseBudget>();
+        let mut blocks = std::mem::take(&mut root.children);
+
+        let sourcepos = TypeKey::of::<SyntaxPosRule>();
+        if self.line > 0
+            && self
+                .md
+                .core_rules()
+                .any(|(marks, _)| marks.contains(&sourcepos))
+        {
+            let mapping = SourceWithLineStarts::new(&content);
+            for node in blocks.iter_mut() {
+                node.walk_mut(|node, _| set_sourcepos(node, &mapping, self.line));
+            }
+        }
+
+        for node in blocks.iter_mut() {
+            node.walk_mut(|node, _| {
+                if let Some(map) = node.srcmap {
+                    let (start, end) = map.get_byte_offsets();
+                    node.srcmap = Some(SourcePos::new(start + self.offset, end + self.offset));
+                }
+            });
+        }
+
+        // same as in SourceWithLineStarts, `\r\n` is a single line break
+        let breaks = content.matches(['\n', '\r']).count() - content.matches("\r\n").count();
+        self.line += breaks as u32;
+        self.offset += len;
+        blocks
+    }
+}
+
+// Top-level block that is continued by lines of a known kind, see `OpenBlock::continues`.
+#[derive(Debug, Clone, Copy)]
+enum OpenBlock {
+    Paragraph,
+    Blockquote,
+    Fence(char),
+}
+
+impl OpenBlock {
+    fn new(node: &Node, source: &str) -> Option<Self> {
+        if node.is::<Paragraph>() {
+            return Some(Self::Paragraph);
+        }
+        if node.is::<Blockquote>() {
+            return Some(Self::Blockquote);
+        }
+
+        // closed fence is finished, any line after it starts a new block
+        let fence = node.cast::<CodeFence>()?;
+        let (start, end) = node.srcmap?.get_byte_offsets();
+        let mut lines = split_lines(source.get(start..end)?);
+        lines.next();
+        let closed = lines.last().is_some_and(|line| {
+            let marker = line.trim_start_matches(' ');
+            line.len() - marker.len() < 4
+                && marker.trim_end().chars().all(|ch| ch == fence.marker)
+                && marker.trim_end().len() >= fence.marker_len
+        });
+        (!closed).then_some(Self::Fence(fence.marker))
+    }
+
+    // Whether the line surely continues the block, i.e. it can't end the block
+    // or start a new one.
+    fn continues(self, line: &str) -> bool {
+        match self {
+            // blocks can't start with a letter, and indented code can't interrupt a paragraph
+            Self::Paragraph => line.trim_start().starts_with(char::is_alphabetic),
+            Self::Blockquote => {
+                let rest = line.trim_start_matches(' ');
+                line.len() - rest.len() < 4 && rest.starts_with('>')
+            }
+            Self::Fence(marker) => !line.trim_start_matches(' ').starts_with(marker),
+        }
+    }
+}
+
+// Lines of `text` without line breaks, `\r\n` is a single line break.
+fn split_lines(text: &str) -> impl Iterator<Item = &str> {
+    let empty = text.is_empty();
+    let text = text
+        .strip_suffix("\r\n")
+        .or_else(|| text.strip_suffix(['\n', '\r']))
+        .unwrap_or(text);
+    text.split("\r\n")
+        .flat_map(|line| line.split(['\n', '\r']))
+        .filter(move |_| !empty)
+}
@@ -5,9 +5,11 @@
 //! <https://spec.commonmark.org/0.30/#block-quotes>
 use crate::common::utils::find_indent_of;
 use crate::parser::block::{BlockRule, BlockState};
+use crate::parser::renderer::{AnsiRenderer, BlockRenderer, MarkdownRenderer, TextRenderer};
 use crate::{MarkdownThat, Node, NodeValue, Renderer};
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Blockquote;
 
 impl NodeValue for Blockquote {
@@ -20,6 +22,19 @@ impl NodeValue for Blockquote {
         fmt.close("blockquote");
         fmt.cr();
     }
+
+    fn render_markdown(&self, node: &Node, fmt: &mut MarkdownRenderer) {
+        fmt.container(node, "> ", "> ", false);
+    }
+
+    fn render_text(&self, node: &Node, fmt: &mut TextRenderer) {
+        fmt.container(node, "> ", "> ", false);
+    }
+
+    fn render_ansi(&self, node: &Node, fmt: &mut AnsiRenderer) {
+        const PREFIX: &str = "\x1b[2m│\x1b[0m ";
+        fmt.container(node, PREFIX, PREFIX, false);
+    }
 }
 
 pub fn add(md: &mut MarkdownThat) {
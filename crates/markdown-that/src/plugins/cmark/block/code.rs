@@ -4,11 +4,14 @@
 //!
 //! <https://spec.commonmark.org/0.30/#indented-code-block>
 use crate::parser::block::{BlockRule, BlockState};
+use crate::parser::renderer::{AnsiRenderer, MarkdownRenderer, TextRenderer};
+use crate::plugins::cmark::block::fence::CodeFence;
 use crate::{MarkdownThat, Node, NodeValue, Renderer};
 
 const CODE_INDENT: i32 = 4;
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CodeBlock {
     pub content: String,
 }
@@ -23,6 +26,53 @@ impl NodeValue for CodeBlock {
         fmt.close("pre");
         fmt.cr();
     }
+
+    fn render_markdown(&self, node: &Node, fmt: &mut MarkdownRenderer) {
+        if fmt.previous_list_marker().is_some() {
+            // indented code would be parsed as a continuation of the list item
+            let fence = CodeFence {
+                info: String::new(),
+                marker: '`',
+                marker_len: 3,
+                content: self.content.clone(),
+                lang_prefix: "".into(),
+            };
+            fence.render_markdown(node, fmt);
+            return;
+        }
+
+        let content = self
+            .content
+            .trim_end_matches('\n')
+            .split('\n')
+            .map(|line| {
+                if line.is_empty() {
+                    String::new()
+                } else {
+                    " ".repeat(CODE_INDENT as usize) + line
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        fmt.open_block();
+        fmt.text_raw(&content);
+        fmt.close_block();
+    }
+
+    fn render_text(&self, _: &Node, fmt: &mut TextRenderer) {
+        fmt.open_block();
+        fmt.text(self.content.trim_end_matches('\n'));
+        fmt.close_block();
+    }
+
+    fn render_ansi(&self, _: &Node, fmt: &mut AnsiRenderer) {
+        fmt.open_container("    ", "    ", true);
+        fmt.push_style("2");
+        fmt.text_nowrap(self.content.trim_end_matches('\n'));
+        fmt.pop_style();
+        fmt.close_container();
+    }
 }
 
 pub fn add(md: &mut MarkdownThat) {
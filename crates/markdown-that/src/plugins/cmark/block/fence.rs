@@ -3,18 +3,22 @@
 //! ` ```lang ` or `~~~lang`
 //!
 //! <https://spec.commonmark.org/0.30/#code-fence>
+use std::borrow::Cow;
+
 use crate::common::utils::unescape_all;
 use crate::parser::block::{BlockRule, BlockState};
 use crate::parser::extset::MarkdownThatExt;
+use crate::parser::renderer::{AnsiRenderer, HtmlRenderOptions, MarkdownRenderer, TextRenderer};
 use crate::{MarkdownThat, Node, NodeValue, Renderer};
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CodeFence {
     pub info: String,
     pub marker: char,
     pub marker_len: usize,
     pub content: String,
-    pub lang_prefix: &'static str,
+    pub lang_prefix: Cow<'static, str>,
 }
 
 impl NodeValue for CodeFence {
@@ -26,7 +30,11 @@ impl NodeValue for CodeFence {
         let class;
 
         if !lang_name.is_empty() {
-            class = format!("{}{}", self.lang_prefix, lang_name);
+            let lang_prefix = fmt
+                .ext()
+                .get::<HtmlRenderOptions>()
+                .and_then(|options| options.lang_prefix.as_deref());
+            class = format!("{}{}", lang_prefix.unwrap_or(&self.lang_prefix), lang_name);
             attrs.push(("class", class));
         }
 
@@ -38,6 +46,59 @@ impl NodeValue for CodeFence {
         fmt.close("pre");
         fmt.cr();
     }
+
+    fn render_markdown(&self, _: &Node, fmt: &mut MarkdownRenderer) {
+        // formatter uses the same marker for all fences and makes them as short as possible,
+        // but info string of a backtick fence can't contain backticks
+        let (marker, mut marker_len) = match fmt.format_options() {
+            Some(options) => match options.fence_marker {
+                Some('`') if self.info.contains('`') => (self.marker, 3),
+                Some(marker) => (marker, 3),
+                None => (self.marker, 3),
+            },
+            None => (self.marker, self.marker_len.max(3)),
+        };
+
+        // closing fence must be longer than anything that looks like a fence inside
+        for line in self.content.lines() {
+            let len = line
+                .trim_start()
+                .chars()
+                .take_while(|ch| *ch == marker)
+                .count();
+            if len >= marker_len {
+                marker_len = len + 1;
+            }
+        }
+        let fence = marker.to_string().repeat(marker_len);
+        let info = match fmt.format_options() {
+            Some(_) => self.info.trim_start(),
+            None => &self.info,
+        };
+
+        fmt.open_block();
+        fmt.text_raw(&fence);
+        fmt.text_raw(info);
+        fmt.cr();
+        fmt.text_raw(&self.content);
+        fmt.cr();
+        fmt.text_raw(&fence);
+        fmt.close_block();
+    }
+
+    fn render_text(&self, _: &Node, fmt: &mut TextRenderer) {
+        fmt.open_block();
+        fmt.text(self.content.trim_end_matches('\n'));
+        fmt.close_block();
+    }
+
+    fn render_ansi(&self, _: &Node, fmt: &mut AnsiRenderer) {
+        fmt.open_container("    ", "    ", true);
+        fmt.push_style("2");
+        fmt.text_nowrap(self.content.trim_end_matches('\n'));
+        fmt.pop_style();
+        fmt.close_container();
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -176,7 +237,7 @@ impl BlockRule for FenceScanner {
             marker,
             marker_len: len,
             content,
-            lang_prefix,
+            lang_prefix: Cow::Borrowed(lang_prefix),
         });
         Some((
             node,
@@ -5,9 +5,11 @@
 //! <https://spec.commonmark.org/0.30/#atx-heading>
 use crate::parser::block::{BlockRule, BlockState};
 use crate::parser::inline::InlineRoot;
+use crate::parser::renderer::{AnsiRenderer, BlockRenderer, MarkdownRenderer, TextRenderer};
 use crate::{MarkdownThat, Node, NodeValue, Renderer};
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ATXHeading {
     pub level: u8,
 }
@@ -23,6 +25,37 @@ impl NodeValue for ATXHeading {
         fmt.close(TAG[self.level as usize - 1]);
         fmt.cr();
     }
+
+    fn render_markdown(&self, node: &Node, fmt: &mut MarkdownRenderer) {
+        fmt.open_block();
+        if fmt.verbatim_line(node) {
+            fmt.close_block();
+            return;
+        }
+        fmt.text_raw(&"#".repeat(self.level as usize));
+        if !node.children.is_empty() {
+            fmt.text_raw(" ");
+            fmt.contents(&node.children);
+        }
+        fmt.close_block();
+    }
+
+    fn render_text(&self, node: &Node, fmt: &mut TextRenderer) {
+        fmt.block(node);
+    }
+
+    fn render_ansi(&self, node: &Node, fmt: &mut AnsiRenderer) {
+        render_heading_ansi(self.level, node, fmt);
+    }
+}
+
+// shared with setext headings: level 1 is bold and underlined, the rest are bold
+pub(crate) fn render_heading_ansi(level: u8, node: &Node, fmt: &mut AnsiRenderer) {
+    fmt.open_block();
+    fmt.push_style(if level == 1 { "1;4" } else { "1" });
+    fmt.contents(&node.children);
+    fmt.pop_style();
+    fmt.close_block();
 }
 
 pub fn add(md: &mut MarkdownThat) {
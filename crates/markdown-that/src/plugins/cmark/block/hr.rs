@@ -4,9 +4,11 @@
 //!
 //! <https://spec.commonmark.org/0.30/#thematic-breaks>
 use crate::parser::block::{BlockRule, BlockState};
+use crate::parser::renderer::{AnsiRenderer, MarkdownRenderer, TextRenderer};
 use crate::{MarkdownThat, Node, NodeValue, Renderer};
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ThematicBreak {
     pub marker: char,
     pub marker_len: usize,
@@ -18,6 +20,31 @@ impl NodeValue for ThematicBreak {
         fmt.self_close("hr", &node.attrs);
         fmt.cr();
     }
+
+    fn render_markdown(&self, node: &Node, fmt: &mut MarkdownRenderer) {
+        fmt.open_block();
+        if fmt.verbatim_line(node) {
+            fmt.close_block();
+            return;
+        }
+        fmt.text_raw(&self.marker.to_string().repeat(self.marker_len.max(3)));
+        fmt.close_block();
+    }
+
+    fn render_text(&self, _: &Node, fmt: &mut TextRenderer) {
+        fmt.open_block();
+        fmt.text("---");
+        fmt.close_block();
+    }
+
+    fn render_ansi(&self, _: &Node, fmt: &mut AnsiRenderer) {
+        fmt.open_block();
+        let width = fmt.available_width().unwrap_or(40);
+        fmt.push_style("2");
+        fmt.text_nowrap(&"─".repeat(width));
+        fmt.pop_style();
+        fmt.close_block();
+    }
 }
 
 pub fn add(md: &mut MarkdownThat) {
@@ -5,10 +5,14 @@
 //! <https://spec.commonmark.org/0.30/#setext-headings>
 use crate::parser::block::{BlockRule, BlockState};
 use crate::parser::inline::InlineRoot;
+use crate::parser::renderer::{AnsiRenderer, BlockRenderer, MarkdownRenderer, TextRenderer};
+use crate::plugins::cmark::block::heading::render_heading_ansi;
 use crate::plugins::cmark::block::paragraph::ParagraphScanner;
+use crate::plugins::cmark::inline::newline::Hardbreak;
 use crate::{MarkdownThat, Node, NodeValue, Renderer};
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SetextHeader {
     pub level: u8,
     pub marker: char,
@@ -25,6 +29,54 @@ impl NodeValue for SetextHeader {
         fmt.close(TAG[self.level as usize - 1]);
         fmt.cr();
     }
+
+    fn render_markdown(&self, node: &Node, fmt: &mut MarkdownRenderer) {
+        let content = fmt.capture(|fmt| fmt.contents(&node.children));
+
+        // formatter writes it as ATX heading, unless there is a hard break that can't be on one line
+        let atx = fmt
+            .format_options()
+            .is_some_and(|options| options.atx_headings)
+            && !node.children.iter().any(|child| child.is::<Hardbreak>());
+        if atx {
+            let mut content = content.replace('\n', " ");
+            // trailing `#` would be parsed as a closing sequence
+            let before_hashes = content.trim_end_matches('#');
+            if before_hashes.len() < content.len()
+                && (before_hashes.is_empty() || before_hashes.ends_with([' ', '\t']))
+            {
+                content.insert(content.len() - 1, '\\');
+            }
+
+            fmt.open_block();
+            fmt.text_raw(&"#".repeat(self.level as usize));
+            fmt.text_raw(" ");
+            fmt.text_raw(&content);
+            fmt.close_block();
+            return;
+        }
+
+        let width = content
+            .lines()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or_default()
+            .max(3);
+
+        fmt.open_block();
+        fmt.text_raw(&content);
+        fmt.cr();
+        fmt.text_raw(&self.marker.to_string().repeat(width));
+        fmt.close_block();
+    }
+
+    fn render_text(&self, node: &Node, fmt: &mut TextRenderer) {
+        fmt.block(node);
+    }
+
+    fn render_ansi(&self, node: &Node, fmt: &mut AnsiRenderer) {
+        render_heading_ansi(self.level, node, fmt);
+    }
 }
 
 pub fn add(md: &mut MarkdownThat) {
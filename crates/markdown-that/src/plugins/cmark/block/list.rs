@@ -8,11 +8,14 @@
 //!  - <https://spec.commonmark.org/0.30/#list-items>
 use crate::common::utils::find_indent_of;
 use crate::parser::block::{BlockRule, BlockState};
+use crate::parser::extset::RenderExt;
+use crate::parser::renderer::{AnsiRenderer, BlockRenderer, MarkdownRenderer, TextRenderer};
 use crate::plugins::cmark::block::hr::HrScanner;
 use crate::plugins::cmark::block::paragraph::Paragraph;
 use crate::{MarkdownThat, Node, NodeValue, Renderer};
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OrderedList {
     pub start: u32,
     pub marker: char,
@@ -34,9 +37,37 @@ impl NodeValue for OrderedList {
         fmt.close("ol");
         fmt.cr();
     }
+
+    fn render_markdown(&self, node: &Node, fmt: &mut MarkdownRenderer) {
+        ListRenderContext {
+            marker: self.marker,
+            next_number: Some(self.start),
+            tight: is_tight(node),
+        }
+        .render_list_markdown(node, fmt);
+    }
+
+    fn render_text(&self, node: &Node, fmt: &mut TextRenderer) {
+        ListRenderContext {
+            marker: '.',
+            next_number: Some(self.start),
+            tight: is_tight(node),
+        }
+        .render_list(node, fmt);
+    }
+
+    fn render_ansi(&self, node: &Node, fmt: &mut AnsiRenderer) {
+        ListRenderContext {
+            marker: '.',
+            next_number: Some(self.start),
+            tight: is_tight(node),
+        }
+        .render_list(node, fmt);
+    }
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BulletList {
     pub marker: char,
 }
@@ -51,9 +82,40 @@ impl NodeValue for BulletList {
         fmt.close("ul");
         fmt.cr();
     }
+
+    fn render_markdown(&self, node: &Node, fmt: &mut MarkdownRenderer) {
+        ListRenderContext {
+            marker: fmt
+                .format_options()
+                .and_then(|options| options.bullet_marker)
+                .unwrap_or(self.marker),
+            next_number: None,
+            tight: is_tight(node),
+        }
+        .render_list_markdown(node, fmt);
+    }
+
+    fn render_text(&self, node: &Node, fmt: &mut TextRenderer) {
+        ListRenderContext {
+            marker: '•',
+            next_number: None,
+            tight: is_tight(node),
+        }
+        .render_list(node, fmt);
+    }
+
+    fn render_ansi(&self, node: &Node, fmt: &mut AnsiRenderer) {
+        ListRenderContext {
+            marker: '•',
+            next_number: None,
+            tight: is_tight(node),
+        }
+        .render_list(node, fmt);
+    }
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ListItem;
 
 impl NodeValue for ListItem {
@@ -63,6 +125,108 @@ impl NodeValue for ListItem {
         fmt.close("li");
         fmt.cr();
     }
+
+    fn render_markdown(&self, node: &Node, fmt: &mut MarkdownRenderer) {
+        ListRenderContext::render_item(node, fmt, "");
+    }
+
+    fn render_text(&self, node: &Node, fmt: &mut TextRenderer) {
+        ListRenderContext::render_item(node, fmt, "");
+    }
+
+    fn render_ansi(&self, node: &Node, fmt: &mut AnsiRenderer) {
+        ListRenderContext::render_item(node, fmt, "");
+    }
+}
+
+/// State of the list being rendered to Markdown, plain text or terminal,
+/// used by list items to write their markers.
+#[derive(Debug)]
+pub struct ListRenderContext {
+    /// Bullet (`-`, `+`, `*`, or `•` for plain text and terminal) or delimiter of ordered list (`.`, `)`).
+    pub marker: char,
+    /// Number of the next item in ordered list, `None` for bullet lists.
+    pub next_number: Option<u32>,
+    /// Tight lists don't have blank lines between items.
+    pub tight: bool,
+}
+
+impl ListRenderContext {
+    /// Return marker for the next list item (e.g. `- ` or `2. `) and advance the counter.
+    pub fn next_marker(&mut self) -> String {
+        match &mut self.next_number {
+            Some(number) => {
+                let marker = format!("{}{} ", number, self.marker);
+                *number += 1;
+                marker
+            }
+            None => format!("{} ", self.marker),
+        }
+    }
+
+    /// Render children of `node` as items of this list.
+    pub fn render_list(self, node: &Node, fmt: &mut impl BlockRenderer) {
+        let tight = self.tight;
+        let old_context = fmt.ext().remove::<ListRenderContext>();
+        fmt.ext().insert(self);
+
+        // items are rendered as separate blocks inside this container
+        fmt.open_container("", "", tight);
+        fmt.contents(&node.children);
+        fmt.close_container();
+
+        old_context.map(|ctx| fmt.ext().insert(ctx));
+    }
+
+    /// Same as [render_list](ListRenderContext::render_list), but makes sure that
+    /// the list won't be merged into the list before it.
+    pub fn render_list_markdown(mut self, node: &Node, fmt: &mut MarkdownRenderer) {
+        // list with the same marker right after another list would be merged into it
+        if fmt.previous_list_marker() == Some(self.marker) {
+            self.marker = match self.marker {
+                '.' => ')',
+                ')' => '.',
+                '-' => '*',
+                _ => '-',
+            };
+        }
+        let marker = self.marker;
+        self.render_list(node, fmt);
+        fmt.close_list(marker);
+    }
+
+    /// Render list item `node` with the next marker of the current list, followed
+    /// by `extra` on the first line (e.g. task list checkbox, `[x] `).
+    ///
+    /// The following lines are indented to the width of the list marker alone.
+    pub fn render_item(node: &Node, fmt: &mut impl BlockRenderer, extra: &str) {
+        let ctx = fmt.ext().get_or_insert_default::<ListRenderContext>();
+        let marker = ctx.next_marker();
+        let tight = ctx.tight;
+        let indent = " ".repeat(marker.chars().count());
+
+        fmt.container(node, &(marker + extra), &indent, tight);
+    }
+}
+
+impl Default for ListRenderContext {
+    fn default() -> Self {
+        Self {
+            marker: '-',
+            next_number: None,
+            tight: false,
+        }
+    }
+}
+
+impl RenderExt for ListRenderContext {}
+
+// list is loose if any of its items contains a paragraph (tight lists have them unwrapped)
+fn is_tight(node: &Node) -> bool {
+    !node
+        .children
+        .iter()
+        .any(|item| item.children.iter().any(|child| child.is::<Paragraph>()))
 }
 
 pub fn add(md: &mut MarkdownThat) {
@@ -314,7 +478,10 @@ impl BlockRule for ListScanner {
                 }
             } else {
                 state.line = next_line;
+                // list item content is nested one level deeper than the list
+                state.level += 1;
                 state.md.block.tokenize(state);
+                state.level -= 1;
             }
 
             // If any of list item is tight, mark list as tight
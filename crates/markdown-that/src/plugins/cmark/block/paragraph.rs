@@ -4,7 +4,8 @@
 //!
 //! <https://spec.commonmark.org/0.30/#paragraph>
 use crate::parser::block::{BlockRule, BlockState};
-use crate::parser::inline::InlineRoot;
+use crate::parser::inline::{InlineRoot, Text};
+use crate::parser::renderer::{AnsiRenderer, BlockRenderer, MarkdownRenderer, TextRenderer};
 use crate::{MarkdownThat, Node, NodeValue, Renderer};
 
 pub fn add(md: &mut MarkdownThat) {
@@ -12,6 +13,7 @@ pub fn add(md: &mut MarkdownThat) {
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Paragraph;
 
 impl NodeValue for Paragraph {
@@ -22,6 +24,37 @@ impl NodeValue for Paragraph {
         fmt.close("p");
         fmt.cr();
     }
+
+    fn render_markdown(&self, node: &Node, fmt: &mut MarkdownRenderer) {
+        fmt.open_block();
+        // joining lines of text that failed to parse as a reference definition
+        // or HTML block could make it one, so it's kept as written
+        // (`<` at the start of plain text is always escaped)
+        let starts_with_markup = node.children.first().is_some_and(|first| {
+            match (first.cast::<Text>(), fmt.source_of(first)) {
+                (Some(text), _) => text.content.starts_with('['),
+                (None, Some(source)) => {
+                    source.starts_with('<') || (source.starts_with('[') && !source.contains("]("))
+                }
+                (None, None) => false,
+            }
+        });
+        match fmt.format_options().and_then(|options| options.wrap_width) {
+            Some(width) if !starts_with_markup => {
+                fmt.write_wrapped(width, |fmt| fmt.contents(&node.children));
+            }
+            _ => fmt.contents(&node.children),
+        }
+        fmt.close_block();
+    }
+
+    fn render_text(&self, node: &Node, fmt: &mut TextRenderer) {
+        fmt.block(node);
+    }
+
+    fn render_ansi(&self, node: &Node, fmt: &mut AnsiRenderer) {
+        fmt.block(node);
+    }
 }
 
 #[doc(hidden)]
@@ -10,11 +10,13 @@
 use crate::common::utils::normalize_reference;
 use crate::generics::inline::full_link;
 use crate::parser::block::{BlockRule, BlockState};
+use crate::parser::diagnostics::{Diagnostic, Diagnostics, Severity};
 use crate::parser::extset::RootExt;
+use crate::parser::renderer::MarkdownRenderer;
 use crate::{MarkdownThat, Node, NodeValue};
 use downcast_rs::{Downcast, impl_downcast};
 use educe::Educe;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt::Debug;
 use std::ops::{Deref, DerefMut};
 
@@ -205,12 +207,29 @@ impl ReferenceMapEntry {
     }
 }
 
+/// Normalized labels of references that are used by links and images.
+///
+/// It's only collected if it's added to root data before inline parser runs
+/// (e.g. by [lint](crate::plugins::lint) plugin).
+#[derive(Debug, Default)]
+pub struct UsedReferences(pub HashSet<String>);
+
+impl RootExt for UsedReferences {}
+
+// Normalized labels of definitions seen so far, used to report duplicates;
+// reference map can be replaced with one that resolves labels differently.
+#[derive(Debug, Default)]
+struct DefinedReferences(HashSet<String>);
+
+impl RootExt for DefinedReferences {}
+
 /// Add a plugin that parses Markdown link references
 pub fn add(md: &mut MarkdownThat) {
     md.block.add_rule::<ReferenceScanner>();
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Definition {
     pub label: String,
     pub destination: String,
@@ -218,6 +237,24 @@ pub struct Definition {
 }
 impl NodeValue for Definition {
     fn render(&self, _: &Node, _: &mut dyn crate::Renderer) {}
+
+    fn render_markdown(&self, node: &Node, fmt: &mut MarkdownRenderer) {
+        fmt.open_block();
+        if fmt.verbatim_line(node) {
+            fmt.close_block();
+            return;
+        }
+        fmt.text_raw(&format!(
+            "[{}]: {}",
+            self.label,
+            full_link::format_link_destination(&self.destination)
+        ));
+        if let Some(title) = &self.title {
+            fmt.text_raw(" ");
+            fmt.text_raw(&full_link::format_link_title(title));
+        }
+        fmt.close_block();
+    }
 }
 
 #[doc(hidden)]
@@ -393,11 +430,27 @@ impl BlockRule for ReferenceScanner {
             }
         }
 
+        let label = &str[1..label_end];
         let references = state.root_ext.get_or_insert_default::<ReferenceMap>();
-        if !references.insert(str[1..label_end].to_owned(), href.clone(), title.clone()) {
+        if !references.insert(label.to_owned(), href.clone(), title.clone()) {
             return None;
         }
 
+        let defined = state.root_ext.get_or_insert_default::<DefinedReferences>();
+        if !defined.0.insert(normalize_reference(label)) {
+            // the first definition takes precedence, so this one is never used
+            let srcmap = state.get_map(start_line, start_line + lines);
+            Diagnostics::push(
+                state.root_ext,
+                Diagnostic::new(
+                    Severity::Warning,
+                    "MD053",
+                    format!("duplicate link reference definition \"{label}\""),
+                    srcmap,
+                ),
+            );
+        }
+
         Some((
             Node::new(Definition {
                 label: str[1..label_end].to_owned(),
@@ -8,9 +8,11 @@ use regex::Regex;
 use std::sync::LazyLock;
 
 use crate::parser::inline::{InlineRule, InlineState, TextSpecial};
+use crate::parser::renderer::{AnsiRenderer, MarkdownRenderer};
 use crate::{MarkdownThat, Node, NodeValue, Renderer};
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Autolink {
     pub url: String,
 }
@@ -24,6 +26,31 @@ impl NodeValue for Autolink {
         fmt.contents(&node.children);
         fmt.close("a");
     }
+
+    fn render_markdown(&self, node: &Node, fmt: &mut MarkdownRenderer) {
+        if let Some(source) = fmt.source_of(node) {
+            fmt.text_raw(source);
+            return;
+        }
+
+        // link text is what was written inside the brackets (minus `mailto:` for emails)
+        let text = node
+            .children
+            .first()
+            .and_then(|child| child.cast::<TextSpecial>());
+        match text {
+            Some(text) => fmt.text_raw(&format!("<{}>", text.markup)),
+            None => fmt.text_raw(&format!("<{}>", self.url)),
+        }
+    }
+
+    fn render_ansi(&self, node: &Node, fmt: &mut AnsiRenderer) {
+        fmt.open_link(&self.url);
+        fmt.push_style("4");
+        fmt.contents(&node.children);
+        fmt.pop_style();
+        fmt.close_link();
+    }
 }
 
 pub fn add(md: &mut MarkdownThat) {
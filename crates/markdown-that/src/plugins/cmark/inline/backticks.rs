@@ -4,9 +4,11 @@
 //!
 //! <https://spec.commonmark.org/0.30/#code-span>
 use crate::generics::inline::code_pair;
+use crate::parser::renderer::{AnsiRenderer, MarkdownRenderer};
 use crate::{MarkdownThat, Node, NodeValue, Renderer};
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CodeInline {
     pub marker: char,
     pub marker_len: usize,
@@ -18,6 +20,41 @@ impl NodeValue for CodeInline {
         fmt.contents(&node.children);
         fmt.close("code");
     }
+
+    fn render_markdown(&self, node: &Node, fmt: &mut MarkdownRenderer) {
+        let content = node.collect_text();
+
+        // find a backtick string that doesn't occur inside the content
+        let mut runs = Vec::new();
+        let mut run = 0;
+        for ch in content.chars().chain(std::iter::once('\0')) {
+            if ch == self.marker {
+                run += 1;
+            } else if run > 0 {
+                runs.push(run);
+                run = 0;
+            }
+        }
+        let mut marker_len = self.marker_len.max(1);
+        while runs.contains(&marker_len) {
+            marker_len += 1;
+        }
+        let marker = self.marker.to_string().repeat(marker_len);
+
+        // one space is stripped from both sides when parsing, so add it back
+        let pad = content.starts_with(self.marker)
+            || content.ends_with(self.marker)
+            || (content.len() > 2 && content.starts_with(' ') && content.ends_with(' '));
+        let pad = if pad { " " } else { "" };
+
+        fmt.text_raw(&format!("{marker}{pad}{content}{pad}{marker}"));
+    }
+
+    fn render_ansi(&self, node: &Node, fmt: &mut AnsiRenderer) {
+        fmt.push_style("36");
+        fmt.contents(&node.children);
+        fmt.pop_style();
+    }
 }
 
 pub fn add(md: &mut MarkdownThat) {
@@ -4,9 +4,11 @@
 //!
 //! <https://spec.commonmark.org/0.30/#emphasis-and-strong-emphasis>
 use crate::generics::inline::emph_pair;
+use crate::parser::renderer::{AnsiRenderer, MarkdownRenderer};
 use crate::{MarkdownThat, Node, NodeValue, Renderer};
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Em {
     pub marker: char,
 }
@@ -17,9 +19,23 @@ impl NodeValue for Em {
         fmt.contents(&node.children);
         fmt.close("em");
     }
+
+    fn render_markdown(&self, node: &Node, fmt: &mut MarkdownRenderer) {
+        let marker = self.marker.to_string();
+        fmt.text_raw(&marker);
+        fmt.contents(&node.children);
+        fmt.text_raw(&marker);
+    }
+
+    fn render_ansi(&self, node: &Node, fmt: &mut AnsiRenderer) {
+        fmt.push_style("3");
+        fmt.contents(&node.children);
+        fmt.pop_style();
+    }
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Strong {
     pub marker: char,
 }
@@ -30,6 +46,19 @@ impl NodeValue for Strong {
         fmt.contents(&node.children);
         fmt.close("strong");
     }
+
+    fn render_markdown(&self, node: &Node, fmt: &mut MarkdownRenderer) {
+        let marker = self.marker.to_string().repeat(2);
+        fmt.text_raw(&marker);
+        fmt.contents(&node.children);
+        fmt.text_raw(&marker);
+    }
+
+    fn render_ansi(&self, node: &Node, fmt: &mut AnsiRenderer) {
+        fmt.push_style("1");
+        fmt.contents(&node.children);
+        fmt.pop_style();
+    }
 }
 
 pub fn add(md: &mut MarkdownThat) {
@@ -4,9 +4,11 @@
 //!
 //! <https://spec.commonmark.org/0.30/#images>
 use crate::generics::inline::full_link;
+use crate::parser::renderer::{AnsiRenderer, MarkdownRenderer};
 use crate::{MarkdownThat, Node, NodeValue, Renderer};
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Image {
     pub url: String,
     pub title: Option<String>,
@@ -24,6 +26,32 @@ impl NodeValue for Image {
 
         fmt.self_close("img", &attrs);
     }
+
+    fn render_markdown(&self, node: &Node, fmt: &mut MarkdownRenderer) {
+        if fmt.verbatim_line(node) {
+            return;
+        }
+        fmt.text_raw("![");
+        fmt.contents(&node.children);
+        fmt.text_raw("](");
+        fmt.text_raw(&full_link::format_link_destination(&self.url));
+        if let Some(title) = &self.title {
+            fmt.text_raw(" ");
+            fmt.text_raw(&full_link::format_link_title(title));
+        }
+        fmt.text_raw(")");
+    }
+
+    fn render_ansi(&self, node: &Node, fmt: &mut AnsiRenderer) {
+        fmt.open_link(&self.url);
+        fmt.push_style("2");
+        fmt.text(&format!("[{}]", node.collect_text()));
+        if !fmt.options().hyperlinks {
+            fmt.text(&format!(" ({})", self.url));
+        }
+        fmt.pop_style();
+        fmt.close_link();
+    }
 }
 
 pub fn add(md: &mut MarkdownThat) {
@@ -5,9 +5,13 @@
 //!  - <https://spec.commonmark.org/0.30/#hard-line-breaks>
 //!  - <https://spec.commonmark.org/0.30/#soft-line-breaks>
 use crate::parser::inline::{InlineRule, InlineState};
+use crate::parser::renderer::{
+    AnsiRenderer, HtmlRenderOptions, MarkdownRenderer, SoftbreakMode, TextRenderer,
+};
 use crate::{MarkdownThat, Node, NodeValue, Renderer};
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Hardbreak;
 
 impl NodeValue for Hardbreak {
@@ -15,14 +19,56 @@ impl NodeValue for Hardbreak {
         fmt.self_close("br", &[]);
         fmt.cr();
     }
+
+    fn render_markdown(&self, _: &Node, fmt: &mut MarkdownRenderer) {
+        fmt.text_raw("\\\n");
+    }
+
+    fn render_text(&self, _: &Node, fmt: &mut TextRenderer) {
+        fmt.text("\n");
+    }
+
+    fn render_ansi(&self, _: &Node, fmt: &mut AnsiRenderer) {
+        fmt.text("\n");
+    }
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Softbreak;
 
 impl NodeValue for Softbreak {
     fn render(&self, _: &Node, fmt: &mut dyn Renderer) {
-        fmt.cr();
+        let mode = fmt
+            .ext()
+            .get::<HtmlRenderOptions>()
+            .map(|options| options.softbreak)
+            .unwrap_or_default();
+
+        match mode {
+            SoftbreakMode::Newline => fmt.cr(),
+            SoftbreakMode::Break => {
+                fmt.self_close("br", &[]);
+                fmt.cr();
+            }
+            SoftbreakMode::Space => fmt.text_raw(" "),
+        }
+    }
+
+    fn render_markdown(&self, _: &Node, fmt: &mut MarkdownRenderer) {
+        fmt.text_raw("\n");
+    }
+
+    fn render_text(&self, _: &Node, fmt: &mut TextRenderer) {
+        fmt.text("\n");
+    }
+
+    fn render_ansi(&self, _: &Node, fmt: &mut AnsiRenderer) {
+        // paragraphs are re-wrapped to fit the terminal, unless wrapping is disabled
+        match fmt.options().width {
+            Some(_) => fmt.text(" "),
+            None => fmt.text("\n"),
+        }
     }
 }
 
@@ -0,0 +1,576 @@
+//! Footnotes, same syntax and output as in [markdown-it-footnote](https://github.com/markdown-it/markdown-it-footnote).
+//!
+//!  - `[^label]` - reference to a footnote
+//!  - `[^label]: text` - footnote definition, following lines indented by 4 spaces belong to it
+//!  - `^[text]` - inline footnote
+//!
+//! Referenced footnotes are numbered in order of the first reference and moved
+//! into [FootnoteSection] at the end of the document, unused definitions are removed.
+//!
+//! ```rust
+//! let md = &mut markdown_that::MarkdownThat::new();
+//! markdown_that::plugins::cmark::add(md);
+//! markdown_that::plugins::extra::footnotes::add(md);
+//!
+//! let html = md.parse("foo[^1] bar^[inline]\n\n[^1]: note").render();
+//! assert_eq!(html, concat!(
+//!     "<p>foo<sup class=\"footnote-ref\"><a href=\"#fn1\" id=\"fnref1\">[1]</a></sup> ",
+//!     "bar<sup class=\"footnote-ref\"><a href=\"#fn2\" id=\"fnref2\">[2]</a></sup></p>\n",
+//!     "<hr class=\"footnotes-sep\">\n",
+//!     "<section class=\"footnotes\">\n",
+//!     "<ol class=\"footnotes-list\">\n",
+//!     "<li id=\"fn1\" class=\"footnote-item\"><p>note <a href=\"#fnref1\" class=\"footnote-backref\">↩︎</a></p>\n",
+//!     "</li>\n",
+//!     "<li id=\"fn2\" class=\"footnote-item\"><p>inline <a href=\"#fnref2\" class=\"footnote-backref\">↩︎</a></p>\n",
+//!     "</li>\n",
+//!     "</ol>\n",
+//!     "</section>\n",
+//! ));
+//! ```
+use std::collections::{HashMap, HashSet};
+
+use crate::common::utils::normalize_reference;
+use crate::generics::inline::full_link;
+use crate::parser::block::{BlockRule, BlockState};
+use crate::parser::core::{CoreRule, Root};
+use crate::parser::extset::RootExt;
+use crate::parser::inline::builtin::InlineParserRule;
+use crate::parser::inline::{InlineRule, InlineState, Text};
+use crate::parser::limits::{ParseBudget, ParseError, exceeds_depth};
+use crate::parser::renderer::{AnsiRenderer, BlockRenderer, MarkdownRenderer, TextRenderer};
+use crate::plugins::cmark::block::paragraph::Paragraph;
+use crate::plugins::cmark::block::reference::ReferenceScanner;
+use crate::{MarkdownThat, Node, NodeValue, Renderer};
+
+/// Footnote definition `[^label]: text`, the core rule moves its content into [FootnoteSection].
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FootnoteDefinition {
+    pub label: String,
+}
+
+impl NodeValue for FootnoteDefinition {
+    fn render(&self, _: &Node, _: &mut dyn Renderer) {}
+
+    fn render_markdown(&self, node: &Node, fmt: &mut MarkdownRenderer) {
+        fmt.open_container(&format!("[^{}]: ", self.label), "    ", false);
+        fmt.contents(&node.children);
+        fmt.close_container();
+    }
+}
+
+/// Reference to a footnote, `[^label]` or inline footnote `^[text]` (without label).
+///
+/// Content of inline footnotes is kept in children until the core rule moves it
+/// into [FootnoteSection].
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FootnoteReference {
+    pub label: Option<String>,
+    /// Label of inline footnote that doesn't match any definition, so it can be
+    /// written to Markdown as `[^label]` with a definition in [FootnoteSection].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub generated_label: Option<String>,
+    /// Index of the footnote in [FootnoteSection], starting from 0.
+    pub id: usize,
+    /// Index of this reference among all references to the same footnote.
+    pub sub_id: usize,
+}
+
+impl NodeValue for FootnoteReference {
+    fn render(&self, node: &Node, fmt: &mut dyn Renderer) {
+        let n = self.id + 1;
+        let anchor = anchor_name(self.id, self.sub_id);
+
+        let mut attrs = node.attrs.clone();
+        attrs.push(("class", "footnote-ref".into()));
+
+        fmt.open("sup", &attrs);
+        fmt.open(
+            "a",
+            &[
+                ("href", format!("#fn{n}")),
+                ("id", format!("fnref{anchor}")),
+            ],
+        );
+        fmt.text(&format!("[{anchor}]"));
+        fmt.close("a");
+        fmt.close("sup");
+    }
+
+    fn render_markdown(&self, node: &Node, fmt: &mut MarkdownRenderer) {
+        match &self.label {
+            Some(label) => fmt.text_raw(&format!("[^{label}]")),
+            None if !node.children.is_empty() => {
+                fmt.text_raw("^[");
+                fmt.contents(&node.children);
+                fmt.text_raw("]");
+            }
+            // content is moved into the footnote section, written as a definition there
+            None => fmt.text_raw(&format!(
+                "[^{}]",
+                self.generated_label.as_deref().unwrap_or_default()
+            )),
+        }
+    }
+
+    fn render_text(&self, _: &Node, fmt: &mut TextRenderer) {
+        fmt.text(&format!("[{}]", self.id + 1));
+    }
+
+    fn render_ansi(&self, _: &Node, fmt: &mut AnsiRenderer) {
+        fmt.text(&format!("[{}]", self.id + 1));
+    }
+}
+
+/// List of footnotes at the end of the document.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FootnoteSection;
+
+impl NodeValue for FootnoteSection {
+    fn render(&self, node: &Node, fmt: &mut dyn Renderer) {
+        let mut attrs = node.attrs.clone();
+        attrs.push(("class", "footnotes".into()));
+
+        fmt.cr();
+        fmt.self_close("hr", &[("class", "footnotes-sep".into())]);
+        fmt.cr();
+        fmt.open("section", &attrs);
+        fmt.cr();
+        fmt.open("ol", &[("class", "footnotes-list".into())]);
+        fmt.cr();
+        fmt.contents(&node.children);
+        fmt.cr();
+        fmt.close("ol");
+        fmt.cr();
+        fmt.close("section");
+        fmt.cr();
+    }
+
+    fn render_markdown(&self, node: &Node, fmt: &mut MarkdownRenderer) {
+        fmt.contents(&node.children);
+    }
+}
+
+/// Footnote in [FootnoteSection], `label` is empty for inline footnotes.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FootnoteItem {
+    pub label: Option<String>,
+    /// Label generated for inline footnote, see [FootnoteReference::generated_label].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub generated_label: Option<String>,
+    pub id: usize,
+}
+
+impl FootnoteItem {
+    fn marker(&self) -> String {
+        let label = self.label.as_ref().or(self.generated_label.as_ref());
+        format!("[^{}]: ", label.map_or("", String::as_str))
+    }
+
+    // `[1] text` in plain text and terminal output
+    fn render_numbered(&self, node: &Node, fmt: &mut impl BlockRenderer) {
+        let marker = format!("[{}] ", self.id + 1);
+        let indent = " ".repeat(marker.len());
+        fmt.container(node, &marker, &indent, false);
+    }
+}
+
+impl NodeValue for FootnoteItem {
+    fn render(&self, node: &Node, fmt: &mut dyn Renderer) {
+        let mut attrs = node.attrs.clone();
+        attrs.insert(0, ("id", format!("fn{}", self.id + 1)));
+        attrs.push(("class", "footnote-item".into()));
+
+        fmt.open("li", &attrs);
+        // first paragraph starts on the same line as `<li>`, like in markdown-it-footnote
+        let rest = match node.children.split_first() {
+            Some((first, rest)) if first.is::<Paragraph>() => {
+                fmt.open("p", &first.attrs);
+                fmt.contents(&first.children);
+                fmt.close("p");
+                fmt.cr();
+                rest
+            }
+            _ => &node.children[..],
+        };
+        fmt.contents(rest);
+        fmt.cr();
+        fmt.close("li");
+        fmt.cr();
+    }
+
+    fn render_markdown(&self, node: &Node, fmt: &mut MarkdownRenderer) {
+        // definition is kept where it was written
+        if fmt.is_source_kept(node) {
+            return;
+        }
+        fmt.open_container(&self.marker(), "    ", false);
+        fmt.contents(&node.children);
+        fmt.close_container();
+    }
+
+    fn render_text(&self, node: &Node, fmt: &mut TextRenderer) {
+        self.render_numbered(node, fmt);
+    }
+
+    fn render_ansi(&self, node: &Node, fmt: &mut AnsiRenderer) {
+        self.render_numbered(node, fmt);
+    }
+}
+
+/// Link from a footnote back to one of its references.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FootnoteBackref {
+    pub id: usize,
+    pub sub_id: usize,
+}
+
+impl NodeValue for FootnoteBackref {
+    fn render(&self, _: &Node, fmt: &mut dyn Renderer) {
+        let anchor = anchor_name(self.id, self.sub_id);
+        fmt.text_raw(" ");
+        fmt.open(
+            "a",
+            &[
+                ("href", format!("#fnref{anchor}")),
+                ("class", "footnote-backref".into()),
+            ],
+        );
+        // text presentation selector, so it isn't shown as emoji
+        fmt.text_raw("\u{21a9}\u{fe0e}");
+        fmt.close("a");
+    }
+
+    fn render_markdown(&self, _: &Node, _: &mut MarkdownRenderer) {}
+}
+
+// `1` for the first reference to the first footnote, `1:1` for the second one
+fn anchor_name(id: usize, sub_id: usize) -> String {
+    if sub_id > 0 {
+        format!("{}:{sub_id}", id + 1)
+    } else {
+        (id + 1).to_string()
+    }
+}
+
+/// Normalized labels of all footnote definitions, references to other labels
+/// are left as text.
+#[derive(Debug, Default)]
+pub struct DefinedFootnotes(pub HashSet<String>);
+
+impl RootExt for DefinedFootnotes {}
+
+pub fn add(md: &mut MarkdownThat) {
+    // `[^label]: text` is a valid link reference definition otherwise
+    md.block
+        .add_rule::<FootnoteDefinitionScanner>()
+        .before::<ReferenceScanner>();
+
+    md.inline.add_rule::<FootnoteReferenceScanner>();
+    md.inline.add_rule::<InlineFootnoteScanner>();
+
+    md.add_rule::<FootnoteCollectRule>()
+        .after::<InlineParserRule>();
+    // footnotes from all blocks are collected into a single section
+    md.mark_document_rule::<FootnoteCollectRule>();
+}
+
+#[doc(hidden)]
+pub struct FootnoteDefinitionScanner;
+
+impl FootnoteDefinitionScanner {
+    // returns label and position after the colon
+    fn parse_label(state: &BlockState) -> Option<(String, usize)> {
+        if state.line_indent(state.line) >= state.md.max_indent {
+            return None;
+        }
+
+        let rest = state.get_line(state.line).strip_prefix("[^")?;
+        let label_end = rest.find([' ', ']'])?;
+        if label_end == 0 || !rest[label_end..].starts_with("]:") {
+            return None;
+        }
+
+        let pos = state.line_offsets[state.line].first_nonspace + label_end + 4;
+        Some((rest[..label_end].to_owned(), pos))
+    }
+}
+
+impl BlockRule for FootnoteDefinitionScanner {
+    fn check(state: &mut BlockState) -> Option<()> {
+        Self::parse_label(state).map(|_| ())
+    }
+
+    fn run(state: &mut BlockState) -> Option<(Node, usize)> {
+        let (label, pos_after_colon) = Self::parse_label(state)?;
+
+        let normalized = normalize_reference(&label);
+        if normalized.is_empty() {
+            return None;
+        }
+        state
+            .root_ext
+            .get_or_insert_default::<DefinedFootnotes>()
+            .0
+            .insert(normalized);
+
+        let start_line = state.line;
+        let old_offsets = state.line_offsets[start_line].clone();
+
+        // indent of the content after `[^label]:`
+        let initial = old_offsets.indent_nonspace as usize
+            + state.src[old_offsets.first_nonspace..pos_after_colon]
+                .chars()
+                .count();
+        let mut offset = initial;
+        let mut pos = pos_after_colon;
+        for ch in state.src[pos_after_colon..old_offsets.line_end].chars() {
+            match ch {
+                ' ' => offset += 1,
+                '\t' => offset += 4 - offset % 4,
+                _ => break,
+            }
+            pos += 1;
+        }
+
+        // content is indented by 4 spaces relative to the definition
+        let old_blk_indent = state.blk_indent;
+        state.blk_indent += 4;
+
+        let mut indent_nonspace = offset - initial;
+        if indent_nonspace < state.blk_indent {
+            indent_nonspace += state.blk_indent;
+        }
+        state.line_offsets[start_line].first_nonspace = pos;
+        state.line_offsets[start_line].indent_nonspace = indent_nonspace as i32;
+
+        let old_node = std::mem::replace(&mut state.node, Node::new(FootnoteDefinition { label }));
+        state.md.block.tokenize(state);
+        let node = std::mem::replace(&mut state.node, old_node);
+
+        state.blk_indent = old_blk_indent;
+        state.line_offsets[start_line] = old_offsets;
+        let next_line = state.line;
+        state.line = start_line;
+
+        Some((node, next_line - start_line))
+    }
+}
+
+#[doc(hidden)]
+pub struct FootnoteReferenceScanner;
+impl InlineRule for FootnoteReferenceScanner {
+    const MARKER: char = '[';
+
+    fn run(state: &mut InlineState) -> Option<(Node, usize)> {
+        let rest = state.src[state.pos..state.pos_max].strip_prefix("[^")?;
+        let label_end = rest.find([' ', '\n', ']'])?;
+        if label_end == 0 || !rest[label_end..].starts_with(']') {
+            return None;
+        }
+
+        let label = &rest[..label_end];
+        let defined = state.root_ext.get::<DefinedFootnotes>()?;
+        if !defined.0.contains(&normalize_reference(label)) {
+            return None;
+        }
+
+        let node = Node::new(FootnoteReference {
+            label: Some(label.to_owned()),
+            generated_label: None,
+            id: 0,
+            sub_id: 0,
+        });
+        Some((node, label_end + 3))
+    }
+}
+
+#[doc(hidden)]
+pub struct InlineFootnoteScanner;
+impl InlineRule for InlineFootnoteScanner {
+    const MARKER: char = '^';
+
+    fn check(state: &mut InlineState) -> Option<usize> {
+        if !state.src[state.pos..state.pos_max].starts_with("^[") {
+            return None;
+        }
+        let label_end = full_link::parse_link_label(state, state.pos + 1, true)?;
+        Some(label_end + 1 - state.pos)
+    }
+
+    fn run(state: &mut InlineState) -> Option<(Node, usize)> {
+        if !state.src[state.pos..state.pos_max].starts_with("^[") {
+            return None;
+        }
+        let label_end = full_link::parse_link_label(state, state.pos + 1, true)?;
+
+        let start = state.pos;
+        let max = state.pos_max;
+        let old_node = std::mem::replace(
+            &mut state.node,
+            Node::new(FootnoteReference {
+                label: None,
+                generated_label: None,
+                id: 0,
+                sub_id: 0,
+            }),
+        );
+
+        state.pos = start + 2;
+        state.pos_max = label_end;
+        state.md.inline.tokenize(state);
+        state.pos = start;
+        state.pos_max = max;
+
+        let node = std::mem::replace(&mut state.node, old_node);
+        Some((node, label_end + 1 - start))
+    }
+}
+
+#[derive(Debug, Default)]
+struct Footnote {
+    label: Option<String>,
+    generated_label: Option<String>,
+    refs: usize,
+    content: Option<Node>,
+}
+
+#[doc(hidden)]
+pub struct FootnoteCollectRule;
+impl CoreRule for FootnoteCollectRule {
+    fn run(root: &mut Node, md: &MarkdownThat) {
+        let mut footnotes = Vec::<Footnote>::new();
+        let mut ids = HashMap::new();
+
+        // number footnotes in order of the first reference
+        root.walk_mut(|node, _| {
+            let Some(reference) = node.cast_mut::<FootnoteReference>() else {
+                return;
+            };
+
+            let id = match &reference.label {
+                Some(label) => *ids.entry(normalize_reference(label)).or_insert_with(|| {
+                    footnotes.push(Footnote {
+                        label: Some(label.clone()),
+                        ..Default::default()
+                    });
+                    footnotes.len() - 1
+                }),
+                None => {
+                    footnotes.push(Footnote::default());
+                    footnotes.len() - 1
+                }
+            };
+
+            reference.id = id;
+            reference.sub_id = footnotes[id].refs;
+            footnotes[id].refs += 1;
+        });
+
+        // inline footnotes are numbered, skipping labels that are already defined
+        let mut taken = root
+            .cast::<Root>()
+            .and_then(|root| root.ext.get::<DefinedFootnotes>())
+            .map(|defined| defined.0.clone())
+            .unwrap_or_default();
+        for (id, footnote) in footnotes.iter_mut().enumerate() {
+            if footnote.label.is_none() {
+                let mut n = id + 1;
+                while !taken.insert(n.to_string()) {
+                    n += 1;
+                }
+                footnote.generated_label = Some(n.to_string());
+            }
+        }
+
+        // take out definitions and content of inline footnotes, nested ones first
+        root.walk_post_mut(|node, _| {
+            if let Some(reference) = node.cast_mut::<FootnoteReference>() {
+                if reference.label.is_none() {
+                    let id = reference.id;
+                    reference.generated_label = footnotes[id].generated_label.clone();
+                    let mut paragraph = Node::new(Paragraph);
+                    paragraph.srcmap = node.srcmap;
+                    paragraph.children = std::mem::take(&mut node.children);
+                    footnotes[id].content = Some(paragraph);
+                }
+                return;
+            }
+
+            if !node
+                .children
+                .iter()
+                .any(|child| child.is::<FootnoteDefinition>())
+            {
+                return;
+            }
+
+            for child in std::mem::take(&mut node.children) {
+                let Some(definition) = child.cast::<FootnoteDefinition>() else {
+                    node.children.push(child);
+                    continue;
+                };
+                // the first definition takes precedence, same as with link references
+                if let Some(&id) = ids.get(&normalize_reference(&definition.label)) {
+                    footnotes[id].content.get_or_insert(child);
+                }
+            }
+        });
+
+        if footnotes.is_empty() {
+            return;
+        }
+
+        let limit = md.limits.max_nesting;
+        let mut too_deep = false;
+        let mut section = Node::new(FootnoteSection);
+        for (id, footnote) in footnotes.into_iter().enumerate() {
+            let mut item = Node::new(FootnoteItem {
+                label: footnote.label,
+                generated_label: footnote.generated_label,
+                id,
+            });
+
+            if let Some(mut content) = footnote.content {
+                item.srcmap = content.srcmap;
+                if content.is::<Paragraph>() {
+                    item.children.push(content);
+                } else {
+                    item.children = std::mem::take(&mut content.children);
+                }
+            }
+
+            // content is nested deeper in the section (at depth 2) than where it was
+            // parsed, keep it as plain text if it no longer fits into max_nesting
+            if exceeds_depth(&item.children, limit.saturating_sub(2)) {
+                too_deep = true;
+                let mut text = Node::new(Text {
+                    content: item.collect_text(),
+                });
+                text.srcmap = item.srcmap;
+                item.children = vec![text];
+            }
+
+            // backrefs go into the last paragraph if there is one
+            let target = match item.children.last_mut() {
+                Some(last) if last.is::<Paragraph>() => &mut last.children,
+                _ => &mut item.children,
+            };
+            for sub_id in 0..footnote.refs {
+                target.push(Node::new(FootnoteBackref { id, sub_id }));
+            }
+
+            section.children.push(item);
+        }
+        root.children.push(section);
+
+        if too_deep {
+            let data = root.cast_mut::<Root>().unwrap();
+            ParseBudget::report(&mut data.ext, ParseError::TooDeep { limit });
+        }
+    }
+}
@@ -4,6 +4,7 @@ use crate::parser::core::{CoreRule, Root};
 use crate::parser::extset::RootExt;
 use crate::parser::inline::builtin::InlineParserRule;
 use crate::parser::inline::{InlineRule, InlineState, TextSpecial};
+use crate::parser::renderer::{AnsiRenderer, MarkdownRenderer};
 use crate::{MarkdownThat, Node, NodeValue, Renderer};
 use linkify::{LinkFinder, LinkKind};
 use regex::Regex;
@@ -14,6 +15,7 @@ static SCHEME_RE: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"(?i)(?:^|[^a-z0-9.+-])([a-z][a-z0-9.+-]*)$").unwrap());
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Linkified {
     pub url: String,
 }
@@ -27,6 +29,21 @@ impl NodeValue for Linkified {
         fmt.contents(&node.children);
         fmt.close("a");
     }
+
+    fn render_markdown(&self, node: &Node, fmt: &mut MarkdownRenderer) {
+        match fmt.source_of(node) {
+            Some(source) => fmt.text_raw(source),
+            None => fmt.contents(&node.children),
+        }
+    }
+
+    fn render_ansi(&self, node: &Node, fmt: &mut AnsiRenderer) {
+        fmt.open_link(&self.url);
+        fmt.push_style("4");
+        fmt.contents(&node.children);
+        fmt.pop_style();
+        fmt.close_link();
+    }
 }
 
 pub fn add(md: &mut MarkdownThat) {
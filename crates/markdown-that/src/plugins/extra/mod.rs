@@ -19,6 +19,7 @@
 //! assert_eq!(html.trim(), r#"<p>Markdown done “The Right Way™”</p>"#);
 //! ```
 pub mod beautify_links;
+pub mod footnotes;
 pub mod heading_anchors;
 #[cfg(feature = "linkify")]
 pub mod linkify;
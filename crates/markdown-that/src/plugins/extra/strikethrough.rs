@@ -1,17 +1,38 @@
 //! Strikethrough syntax (like `~~this~~`)
 use crate::generics::inline::emph_pair;
+use crate::parser::renderer::{AnsiRenderer, HtmlRenderOptions, MarkdownRenderer};
 use crate::{MarkdownThat, Node, NodeValue, Renderer};
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Strikethrough {
     pub marker: char,
 }
 
 impl NodeValue for Strikethrough {
     fn render(&self, node: &Node, fmt: &mut dyn Renderer) {
-        fmt.open("s", &node.attrs);
+        let gfm = fmt
+            .ext()
+            .get::<HtmlRenderOptions>()
+            .is_some_and(|options| options.gfm);
+        let tag = if gfm { "del" } else { "s" };
+
+        fmt.open(tag, &node.attrs);
+        fmt.contents(&node.children);
+        fmt.close(tag);
+    }
+
+    fn render_markdown(&self, node: &Node, fmt: &mut MarkdownRenderer) {
+        let marker = self.marker.to_string().repeat(2);
+        fmt.text_raw(&marker);
+        fmt.contents(&node.children);
+        fmt.text_raw(&marker);
+    }
+
+    fn render_ansi(&self, node: &Node, fmt: &mut AnsiRenderer) {
+        fmt.push_style("9");
         fmt.contents(&node.children);
-        fmt.close("s");
+        fmt.pop_style();
     }
 }
 
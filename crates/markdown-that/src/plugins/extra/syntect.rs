@@ -3,21 +3,153 @@ use syntect::highlighting::ThemeSet;
 use syntect::html::highlighted_html_for_string;
 use syntect::parsing::SyntaxSet;
 
+use crate::common::utils::unescape_all;
 use crate::parser::core::CoreRule;
 use crate::parser::extset::MarkdownThatExt;
+use crate::parser::renderer::{AnsiRenderer, MarkdownRenderer, TextRenderer};
 use crate::plugins::cmark::block::code::CodeBlock;
 use crate::plugins::cmark::block::fence::CodeFence;
 use crate::{MarkdownThat, Node, NodeValue, Renderer};
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SyntectSnippet {
     pub html: String,
+    /// Language of the fenced code block (first word of its info string).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub language: Option<String>,
+}
+
+impl SyntectSnippet {
+    /// Source code without highlighting, original code isn't kept,
+    /// so it's recovered from highlighted html.
+    pub fn code(&self) -> String {
+        // syntect writes a newline after `<pre>`, and another one after `</pre>`
+        let html = match self.html.split_once('>') {
+            Some((pre, rest)) if pre.starts_with("<pre") => rest.strip_prefix('\n').unwrap_or(rest),
+            _ => &self.html,
+        };
+        let html = html.trim_end_matches('\n');
+        let html = html.strip_suffix("</pre>").unwrap_or(html);
+
+        let mut code = String::with_capacity(html.len());
+        let mut in_tag = false;
+        for ch in html.chars() {
+            match ch {
+                '<' => in_tag = true,
+                '>' if in_tag => in_tag = false,
+                _ if !in_tag => code.push(ch),
+                _ => (),
+            }
+        }
+        html_escape::decode_html_entities(&code).into_owned()
+    }
 }
 
 impl NodeValue for SyntectSnippet {
     fn render(&self, _: &Node, fmt: &mut dyn Renderer) {
         fmt.text_raw(&self.html);
     }
+
+    fn render_markdown(&self, node: &Node, fmt: &mut MarkdownRenderer) {
+        // written back as a fence, highlighted html isn't markdown
+        let language = self.language.clone().unwrap_or_default();
+        let fence = CodeFence {
+            marker: if language.contains('`') { '~' } else { '`' },
+            marker_len: 3,
+            info: language,
+            content: self.code(),
+            lang_prefix: "".into(),
+        };
+        fence.render_markdown(node, fmt);
+        // fence markers and indentation are lost
+        fmt.keep_source();
+    }
+
+    fn render_text(&self, _: &Node, fmt: &mut TextRenderer) {
+        fmt.open_block();
+        fmt.text(self.code().trim_matches('\n'));
+        fmt.close_block();
+    }
+
+    fn render_ansi(&self, _: &Node, fmt: &mut AnsiRenderer) {
+        // inline styles of highlighted html are converted to SGR parameters
+        fmt.open_container("    ", "    ", true);
+        let mut pushed = Vec::new();
+        let mut started = false;
+        let mut newlines = 0;
+        let mut rest = self.html.as_str();
+        while !rest.is_empty() {
+            if let Some(tag_start) = rest.strip_prefix('<') {
+                let end = tag_start.find('>').unwrap_or(tag_start.len());
+                let tag = &tag_start[..end];
+                rest = tag_start.get(end + 1..).unwrap_or_default();
+
+                if tag.starts_with("span") {
+                    let sgr = tag
+                        .split_once("style=\"")
+                        .and_then(|(_, style)| style.split('"').next())
+                        .map(css_to_sgr)
+                        .unwrap_or_default();
+                    if !sgr.is_empty() {
+                        fmt.push_style(&sgr);
+                    }
+                    pushed.push(!sgr.is_empty());
+                } else if tag == "/span" && pushed.pop() == Some(true) {
+                    fmt.pop_style();
+                }
+                continue;
+            }
+
+            let end = rest.find('<').unwrap_or(rest.len());
+            let text = html_escape::decode_html_entities(&rest[..end]);
+            rest = &rest[end..];
+
+            // blank lines around the code are trimmed, same as in render_text
+            for (idx, line) in text.split('\n').enumerate() {
+                if idx > 0 && started {
+                    newlines += 1;
+                }
+                if !line.is_empty() {
+                    fmt.text_nowrap(&"\n".repeat(newlines));
+                    fmt.text_nowrap(line);
+                    newlines = 0;
+                    started = true;
+                }
+            }
+        }
+        for _ in pushed.into_iter().filter(|pushed| *pushed) {
+            fmt.pop_style();
+        }
+        fmt.close_container();
+    }
+}
+
+// Convert inline css generated by syntect into SGR parameters (background is ignored).
+fn css_to_sgr(style: &str) -> String {
+    let mut result = Vec::new();
+    for declaration in style.split(';') {
+        let Some((name, value)) = declaration.split_once(':') else {
+            continue;
+        };
+        match (name.trim(), value.trim()) {
+            ("color", color) => {
+                let hex = color.trim_start_matches('#');
+                let channel = |idx: usize| {
+                    hex.get(idx..idx + 2)
+                        .and_then(|ch| u8::from_str_radix(ch, 16).ok())
+                };
+                if let (Some(r), Some(g), Some(b)) = (channel(0), channel(2), channel(4)) {
+                    result.push(format!("38;2;{r};{g};{b}"));
+                }
+            }
+            ("font-weight", "bold") => result.push("1".to_owned()),
+            ("font-style", "italic") => result.push("3".to_owned()),
+            ("text-decoration", "underline") => result.push("4".to_owned()),
+            _ => (),
+        }
+    }
+    result.join(";")
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -57,21 +189,24 @@ impl CoreRule for SyntectRule {
             if let Some(data) = node.cast::<CodeBlock>() {
                 content = Some(&data.content);
             } else if let Some(data) = node.cast::<CodeFence>() {
-                language = Some(data.info.clone());
+                language = unescape_all(&data.info)
+                    .split_whitespace()
+                    .next()
+                    .map(str::to_owned);
                 content = Some(&data.content);
             }
 
             if let Some(content) = content {
                 let mut syntax = None;
-                if let Some(language) = language {
-                    syntax = ss.find_syntax_by_token(&language);
+                if let Some(language) = &language {
+                    syntax = ss.find_syntax_by_token(language);
                 }
                 let syntax = syntax.unwrap_or_else(|| ss.find_syntax_plain_text());
 
                 let html = highlighted_html_for_string(content, &ss, syntax, theme);
 
                 if let Ok(html) = html {
-                    node.replace(SyntectSnippet { html });
+                    node.replace(SyntectSnippet { html, language });
                 }
             }
         });
@@ -3,13 +3,16 @@
 //! <https://github.github.com/gfm/#tables-extension->
 use crate::common::sourcemap::SourcePos;
 use crate::parser::block::{BlockRule, BlockState};
+use crate::parser::diagnostics::{Diagnostic, Diagnostics, Severity};
 use crate::parser::extset::RenderExt;
 use crate::parser::inline::InlineRoot;
+use crate::parser::renderer::{AnsiRenderer, HtmlRenderOptions, MarkdownRenderer, TextRenderer};
 use crate::plugins::cmark::block::heading::HeadingScanner;
 use crate::plugins::cmark::block::list::ListScanner;
 use crate::{MarkdownThat, Node, NodeValue, Renderer};
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Table {
     pub alignments: Vec<ColumnAlignment>,
 }
@@ -33,6 +36,172 @@ impl NodeValue for Table {
 
         old_context.map(|ctx| fmt.ext().insert(ctx));
     }
+
+    fn render_markdown(&self, node: &Node, fmt: &mut MarkdownRenderer) {
+        let rows = node
+            .children
+            .iter()
+            .flat_map(|section| section.children.iter())
+            .map(|row| {
+                // table scanner removes backslash before any pipe, even an escaped one,
+                // so every pipe inside of the cell gets one
+                row.children
+                    .iter()
+                    .map(|cell| fmt.capture(|fmt| fmt.render(cell)).replace('|', "\\|"))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let columns = rows
+            .iter()
+            .map(Vec::len)
+            .max()
+            .unwrap_or_default()
+            .max(self.alignments.len());
+        let mut widths = vec![3; columns];
+        for row in rows.iter() {
+            for (width, cell) in widths.iter_mut().zip(row.iter()) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+
+        let mut lines = Vec::with_capacity(rows.len() + 1);
+        for (idx, row) in rows.iter().enumerate() {
+            let mut line = String::from("|");
+            for (column, width) in widths.iter().enumerate() {
+                let cell = row.get(column).map(String::as_str).unwrap_or_default();
+                let padding = width - cell.chars().count();
+                let (left, right) = match self.alignments.get(column).copied().unwrap_or_default() {
+                    ColumnAlignment::Right => (padding, 0),
+                    ColumnAlignment::Center => (padding / 2, padding - padding / 2),
+                    ColumnAlignment::None | ColumnAlignment::Left => (0, padding),
+                };
+                line.push(' ');
+                line.push_str(&" ".repeat(left));
+                line.push_str(cell);
+                line.push_str(&" ".repeat(right));
+                line.push_str(" |");
+            }
+            lines.push(line);
+
+            if idx == 0 {
+                let mut line = String::from("|");
+                for (column, width) in widths.iter().enumerate() {
+                    let delimiter = match self.alignments.get(column).copied().unwrap_or_default() {
+                        ColumnAlignment::None => "-".repeat(*width),
+                        ColumnAlignment::Left => format!(":{}", "-".repeat(width - 1)),
+                        ColumnAlignment::Right => format!("{}:", "-".repeat(width - 1)),
+                        ColumnAlignment::Center => format!(":{}:", "-".repeat(width - 2)),
+                    };
+                    line.push(' ');
+                    line.push_str(&delimiter);
+                    line.push_str(" |");
+                }
+                lines.push(line);
+            }
+        }
+
+        fmt.open_block();
+        fmt.text_raw(&lines.join("\n"));
+        fmt.close_block();
+    }
+
+    fn render_text(&self, node: &Node, fmt: &mut TextRenderer) {
+        let separator = fmt.cell_separator().to_owned();
+        let rows = node
+            .children
+            .iter()
+            .flat_map(|section| section.children.iter())
+            .map(|row| {
+                row.children
+                    .iter()
+                    .map(|cell| fmt.capture(|fmt| fmt.render(cell)))
+                    .collect::<Vec<_>>()
+                    .join(&separator)
+            })
+            .collect::<Vec<_>>();
+
+        fmt.open_block();
+        fmt.text(&rows.join("\n"));
+        fmt.close_block();
+    }
+
+    fn render_ansi(&self, node: &Node, fmt: &mut AnsiRenderer) {
+        let mut rows = Vec::new();
+        for section in node.children.iter() {
+            let head = section.is::<TableHead>();
+            for row in section.children.iter() {
+                let cells = row
+                    .children
+                    .iter()
+                    .map(|cell| {
+                        fmt.capture(|fmt| {
+                            if head {
+                                fmt.push_style("1");
+                            }
+                            fmt.render(cell);
+                            if head {
+                                fmt.pop_style();
+                            }
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                rows.push((head, cells));
+            }
+        }
+
+        let columns = rows
+            .iter()
+            .map(|(_, cells)| cells.len())
+            .max()
+            .unwrap_or_default()
+            .max(self.alignments.len());
+        let mut widths = vec![1; columns];
+        for (_, cells) in rows.iter() {
+            for (width, cell) in widths.iter_mut().zip(cells.iter()) {
+                *width = (*width).max(AnsiRenderer::visible_width(cell));
+            }
+        }
+
+        let border = |left: &str, middle: &str, right: &str| {
+            let line = widths
+                .iter()
+                .map(|width| "─".repeat(width + 2))
+                .collect::<Vec<_>>()
+                .join(middle);
+            format!("{left}{line}{right}")
+        };
+
+        let mut lines = vec![border("┌", "┬", "┐")];
+        for (idx, (head, cells)) in rows.iter().enumerate() {
+            let mut line = String::from("│");
+            for (column, width) in widths.iter().enumerate() {
+                let cell = cells.get(column).map(String::as_str).unwrap_or_default();
+                let padding = width - AnsiRenderer::visible_width(cell);
+                let (left, right) = match self.alignments.get(column).copied().unwrap_or_default() {
+                    ColumnAlignment::Right => (padding, 0),
+                    ColumnAlignment::Center => (padding / 2, padding - padding / 2),
+                    ColumnAlignment::None | ColumnAlignment::Left => (0, padding),
+                };
+                line.push(' ');
+                line.push_str(&" ".repeat(left));
+                line.push_str(cell);
+                line.push_str(&" ".repeat(right));
+                line.push_str(" │");
+            }
+            lines.push(line);
+
+            let next_is_body = rows.get(idx + 1).is_some_and(|(head, _)| !head);
+            if *head && next_is_body {
+                lines.push(border("├", "┼", "┤"));
+            }
+        }
+        lines.push(border("└", "┴", "┘"));
+
+        fmt.open_block();
+        fmt.text_raw(&lines.join("\n"));
+        fmt.close_block();
+    }
 }
 
 #[derive(Debug, Default)]
@@ -45,6 +214,7 @@ pub struct TableRenderContext {
 impl RenderExt for TableRenderContext {}
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TableHead;
 
 impl NodeValue for TableHead {
@@ -66,6 +236,7 @@ impl NodeValue for TableHead {
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TableBody;
 
 impl NodeValue for TableBody {
@@ -81,6 +252,7 @@ impl NodeValue for TableBody {
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TableRow;
 
 impl NodeValue for TableRow {
@@ -99,6 +271,7 @@ impl NodeValue for TableRow {
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TableCell;
 
 impl NodeValue for TableCell {
@@ -108,20 +281,36 @@ impl NodeValue for TableCell {
 
         let mut attrs = node.attrs.clone();
 
-        match ctx.alignments.get(ctx.index).copied().unwrap_or_default() {
-            ColumnAlignment::None => (),
-            ColumnAlignment::Left => attrs.push(("style", "text-align:left".to_owned())),
-            ColumnAlignment::Right => attrs.push(("style", "text-align:right".to_owned())),
-            ColumnAlignment::Center => attrs.push(("style", "text-align:center".to_owned())),
-        }
+        let align = match ctx.alignments.get(ctx.index).copied().unwrap_or_default() {
+            ColumnAlignment::None => None,
+            ColumnAlignment::Left => Some("left"),
+            ColumnAlignment::Right => Some("right"),
+            ColumnAlignment::Center => Some("center"),
+        };
 
         ctx.index += 1;
 
+        if let Some(align) = align {
+            let gfm = fmt
+                .ext()
+                .get::<HtmlRenderOptions>()
+                .is_some_and(|options| options.gfm);
+            if gfm {
+                attrs.push(("align", align.to_owned()));
+            } else {
+                attrs.push(("style", format!("text-align:{align}")));
+            }
+        }
+
         fmt.open(tag, &attrs);
         fmt.contents(&node.children);
         fmt.close(tag);
         fmt.cr();
     }
+    // the table writes pipes around cells
+    fn render_markdown(&self, node: &Node, fmt: &mut MarkdownRenderer) {
+        fmt.contents(&node.children);
+    }
 }
 
 pub fn add(md: &mut MarkdownThat) {
@@ -141,6 +330,7 @@ struct RowContent {
 }
 
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ColumnAlignment {
     None,
     Left,
@@ -405,6 +595,18 @@ impl BlockRule for TableScanner {
                 add_cell(&mut row_node, cell.clone(), srcmap.clone());
             }
 
+            if body_row.len() != table_cell_count {
+                // extra cells are dropped, and missing ones are rendered empty
+                let message = format!(
+                    "table row has {} cells, expected {table_cell_count}",
+                    body_row.len()
+                );
+                Diagnostics::push(
+                    state.root_ext,
+                    Diagnostic::new(Severity::Warning, "MD056", message, row_node.srcmap),
+                );
+            }
+
             state.node.children.push(row_node);
             state.line += 1;
         }
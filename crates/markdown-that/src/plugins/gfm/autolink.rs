@@ -0,0 +1,256 @@
+//! Extended autolinks
+//!
+//! `www.example.com`, `https://example.com` or `foo@example.com` without angle brackets.
+//!
+//! <https://github.github.com/gfm/#autolinks-extension->
+use crate::common::sourcemap::SourcePos;
+use crate::generics::inline::emph_pair::EmphMarker;
+use crate::parser::inline::{InlineRule, InlineState, Text, TextSpecial};
+use crate::plugins::cmark::inline::autolink::Autolink;
+use crate::{MarkdownThat, Node};
+
+const SCHEMES: [&str; 3] = ["http", "https", "ftp"];
+
+pub fn add(md: &mut MarkdownThat) {
+    md.inline.add_rule::<WwwScanner>();
+    md.inline.add_rule::<SchemeScanner>();
+    md.inline.add_rule::<EmailScanner>();
+}
+
+// `www.` followed by a valid domain
+#[doc(hidden)]
+pub struct WwwScanner;
+impl InlineRule for WwwScanner {
+    const MARKER: char = '.';
+
+    fn run(state: &mut InlineState) -> Option<(Node, usize)> {
+        if !state.src[state.pos..state.pos_max].starts_with('.')
+            || state.link_level > 0
+            || !state.trailing_text_get().ends_with("www")
+        {
+            return None;
+        }
+
+        let start = state.pos - 3;
+        if !is_boundary(state, start) {
+            return None;
+        }
+
+        let len = scan_url(&state.src[start..state.pos_max])?;
+        let node = create_link(state, start, start + len, "http://")?;
+        state.trailing_text_pop(state.pos - start);
+        state.pos = start;
+        Some((node, len))
+    }
+}
+
+// `http://`, `https://` or `ftp://` followed by a valid domain
+#[doc(hidden)]
+pub struct SchemeScanner;
+impl InlineRule for SchemeScanner {
+    const MARKER: char = ':';
+
+    fn run(state: &mut InlineState) -> Option<(Node, usize)> {
+        if state.link_level > 0 || !state.src[state.pos..state.pos_max].starts_with("://") {
+            return None;
+        }
+
+        let trailing = state.trailing_text_get();
+        let scheme = SCHEMES.iter().find(|scheme| trailing.ends_with(*scheme))?;
+        let start = state.pos - scheme.len();
+        if !is_boundary(state, start) {
+            return None;
+        }
+
+        let domain_start = state.pos + 3;
+        let end = domain_start + scan_url(&state.src[domain_start..state.pos_max])?;
+        let node = create_link(state, start, end, "")?;
+        state.trailing_text_pop(state.pos - start);
+        state.pos = start;
+        Some((node, end - start))
+    }
+}
+
+// `user@domain`, where user is alphanumeric or `.-_+`, and domain
+// is alphanumeric or `-_` separated by periods
+#[doc(hidden)]
+pub struct EmailScanner;
+impl InlineRule for EmailScanner {
+    const MARKER: char = '@';
+
+    fn run(state: &mut InlineState) -> Option<(Node, usize)> {
+        if !state.src[state.pos..state.pos_max].starts_with('@') || state.link_level > 0 {
+            return None;
+        }
+
+        let user = state.src[..state.pos]
+            .bytes()
+            .rev()
+            .take_while(|b| b.is_ascii_alphanumeric() || matches!(b, b'.' | b'-' | b'_' | b'+'))
+            .count();
+        let user = previous_nodes_len(state, state.pos - user, state.pos);
+        if user == 0 {
+            return None;
+        }
+
+        let domain_start = state.pos + 1;
+        let mut domain = state.src[domain_start..state.pos_max]
+            .bytes()
+            .take_while(|&b| b.is_ascii_alphanumeric() || matches!(b, b'.' | b'-' | b'_'))
+            .count();
+        // a period at the end isn't part of the link
+        while domain > 0 && state.src.as_bytes()[domain_start + domain - 1] == b'.' {
+            domain -= 1;
+        }
+
+        let domain_str = &state.src[domain_start..domain_start + domain];
+        if !domain_str.contains('.')
+            || domain_str.starts_with('.')
+            || domain_str.ends_with(['-', '_'])
+        {
+            return None;
+        }
+
+        let start = state.pos - user;
+        let end = domain_start + domain;
+        let node = create_link(state, start, end, "mailto:")?;
+        remove_previous_nodes(state, user);
+        state.pos = start;
+        Some((node, end - start))
+    }
+}
+
+// Beginning of an email address is already added to the tree as text, and can be split
+// into several nodes (e.g. `_` is an emphasis marker), returns how much of the address
+// between `start` and `end` is in text and markers right before `end`.
+fn previous_nodes_len(state: &InlineState, start: usize, end: usize) -> usize {
+    let Some(map) = state.get_map(start, end) else {
+        return 0;
+    };
+    let (src_start, src_end) = map.get_byte_offsets();
+    let mut taken_from = src_end;
+
+    for node in state.node.children.iter().rev() {
+        let Some((node_start, node_end)) = node.srcmap.map(|map| map.get_byte_offsets()) else {
+            break;
+        };
+        if node_end != taken_from || !(node.is::<Text>() || node.is::<EmphMarker>()) {
+            break;
+        }
+
+        taken_from = node_start.max(src_start);
+        if node_start <= src_start {
+            break;
+        }
+    }
+
+    src_end - taken_from
+}
+
+// remove last `len` bytes of text and markers from the tree
+fn remove_previous_nodes(state: &mut InlineState, mut len: usize) {
+    while len > 0 {
+        let last = state.node.children.last_mut().unwrap();
+        let (node_start, node_end) = last.srcmap.unwrap().get_byte_offsets();
+        if node_end - node_start <= len {
+            len -= node_end - node_start;
+            state.node.children.pop();
+        } else {
+            // text before the address, only its end is removed
+            let text = last.cast_mut::<Text>().unwrap();
+            text.content.truncate(text.content.len() - len);
+            last.srcmap = Some(SourcePos::new(node_start, node_end - len));
+            len = 0;
+        }
+    }
+}
+
+// links can only start at the beginning of a line, after whitespace, or after `*`, `_`, `~` and `(`
+fn is_boundary(state: &InlineState, pos: usize) -> bool {
+    match state.src[..pos].chars().next_back() {
+        None => true,
+        Some(ch) => ch.is_whitespace() || matches!(ch, '*' | '_' | '~' | '('),
+    }
+}
+
+// length of a valid domain with the path after it, without trailing punctuation
+fn scan_url(src: &str) -> Option<usize> {
+    let end = src
+        .find(|ch: char| ch.is_whitespace() || ch == '<')
+        .unwrap_or(src.len());
+    let end = trim_url_end(&src[..end]);
+
+    let url = &src[..end];
+    let domain = url
+        .find(|ch: char| !(ch.is_alphanumeric() || matches!(ch, '.' | '-' | '_')))
+        .unwrap_or(end);
+    is_valid_domain(&url[..domain]).then_some(end)
+}
+
+// segments of alphanumeric characters, `_` and `-` separated by periods, there must be
+// at least one period, and no underscores in the last two segments
+fn is_valid_domain(domain: &str) -> bool {
+    let mut segments = domain.rsplit('.');
+    let last = segments.next().unwrap_or_default();
+    let Some(second_last) = segments.next() else {
+        return false;
+    };
+    !last.is_empty() && !second_last.is_empty() && !last.contains('_') && !second_last.contains('_')
+}
+
+// exclude trailing punctuation, unmatched closing parentheses and entity-like `&xxx;`
+fn trim_url_end(url: &str) -> usize {
+    let bytes = url.as_bytes();
+    let opening = bytes.iter().filter(|&&b| b == b'(').count();
+    let mut closing = bytes.iter().filter(|&&b| b == b')').count();
+    let mut end = bytes.len();
+
+    while end > 0 {
+        match bytes[end - 1] {
+            b'?' | b'!' | b'.' | b',' | b':' | b'*' | b'_' | b'~' => end -= 1,
+            b')' if closing > opening => {
+                closing -= 1;
+                end -= 1;
+            }
+            b';' => {
+                let name = bytes[..end - 1]
+                    .iter()
+                    .rev()
+                    .take_while(|b| b.is_ascii_alphanumeric())
+                    .count();
+                let amp = end - 1 - name;
+                if name > 0 && amp > 0 && bytes[amp - 1] == b'&' {
+                    end = amp - 1;
+                } else {
+                    break;
+                }
+            }
+            _ => break,
+        }
+    }
+
+    end
+}
+
+fn create_link(state: &InlineState, start: usize, end: usize, prefix: &str) -> Option<Node> {
+    let text = &state.src[start..end];
+    let full_url = state
+        .md
+        .link_formatter
+        .normalize_link(&(prefix.to_owned() + text));
+
+    state.md.link_formatter.validate_link(&full_url)?;
+
+    let content = state.md.link_formatter.normalize_link_text(text);
+
+    let mut inner_node = Node::new(TextSpecial {
+        content: content.clone(),
+        markup: content,
+        info: "autolink",
+    });
+    inner_node.srcmap = state.get_map(start, end);
+
+    let mut node = Node::new(Autolink { url: full_url });
+    node.children.push(inner_node);
+    Some(node)
+}
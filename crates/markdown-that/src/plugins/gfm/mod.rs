@@ -0,0 +1,44 @@
+//! [GitHub Flavored Markdown](https://github.github.com/gfm/) extensions, as specified in 0.29-gfm.
+//!
+//!  - tables (same as [extra::tables](crate::plugins::extra::tables))
+//!  - task list items (`- [x] done`)
+//!  - strikethrough with one or two tildes (`~foo~` or `~~foo~~`)
+//!  - extended autolinks (`www.example.com`, `https://example.com` and `foo@example.com`)
+//!  - disallowed raw HTML (`<script>`, `<iframe>` and alike are escaped)
+//!
+//! These only add extensions, CommonMark syntax and raw HTML are added separately.
+//! GitHub writes some of them differently, e.g. `<del>` instead of `<s>`, so render with
+//! [HtmlRenderOptions::gfm] enabled (it's set for [MarkdownThat::render] by this plugin):
+//!
+//! ```rust
+//! let md = &mut markdown_that::MarkdownThat::new();
+//! markdown_that::plugins::cmark::add(md);
+//! markdown_that::plugins::html::add(md);
+//! markdown_that::plugins::gfm::add(md);
+//!
+//! let html = md.render("- [x] ~done~, see www.example.com");
+//! assert_eq!(html, concat!(
+//!     "<ul>\n",
+//!     "<li><input checked=\"\" disabled=\"\" type=\"checkbox\"> <del>done</del>, ",
+//!     "see <a href=\"http://www.example.com\">www.example.com</a></li>\n",
+//!     "</ul>\n",
+//! ));
+//! ```
+pub mod autolink;
+pub mod strikethrough;
+pub mod tagfilter;
+pub mod tasklist;
+
+use crate::MarkdownThat;
+use crate::parser::renderer::HtmlRenderOptions;
+use crate::plugins::extra::tables;
+
+pub fn add(md: &mut MarkdownThat) {
+    tables::add(md);
+    // spec output has no classes
+    tasklist::add_with(md, tasklist::TaskListOptions::default());
+    strikethrough::add(md);
+    autolink::add(md);
+    tagfilter::add(md);
+    md.ext.get_or_insert_default::<HtmlRenderOptions>().gfm = true;
+}
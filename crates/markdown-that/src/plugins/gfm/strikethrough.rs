@@ -0,0 +1,119 @@
+//! Strikethrough with GFM rules (`~foo~` or `~~foo~~`)
+//!
+//! Unlike [extra::strikethrough](crate::plugins::extra::strikethrough), a run of one
+//! or two tildes only closes a run of the same length, and longer runs are never
+//! a strikethrough.
+//!
+//! <https://github.github.com/gfm/#strikethrough-extension->
+use crate::generics::inline::emph_pair::{EmphMarker, FragmentsJoin};
+use crate::parser::extset::NodeExt;
+use crate::parser::inline::builtin::InlineParserRule;
+use crate::parser::inline::{InlineRule, InlineState};
+use crate::parser::limits::{ParseBudget, ParseError, exceeds_depth};
+use crate::plugins::extra::strikethrough::Strikethrough;
+use crate::{MarkdownThat, Node};
+
+// lower bounds of opener search for each run length (previous fails)
+#[derive(Debug, Default)]
+struct OpenersBottom([usize; 2]);
+impl NodeExt for OpenersBottom {}
+
+pub fn add(md: &mut MarkdownThat) {
+    md.inline.add_rule::<StrikethroughScanner>();
+
+    if !md.has_rule::<FragmentsJoin>() {
+        md.add_rule::<FragmentsJoin>()
+            .before_all()
+            .after::<InlineParserRule>();
+    }
+}
+
+#[doc(hidden)]
+pub struct StrikethroughScanner;
+impl InlineRule for StrikethroughScanner {
+    const MARKER: char = '~';
+
+    // same as emphasis, this rule works on a closing marker
+    fn check(_: &mut InlineState) -> Option<usize> {
+        None
+    }
+
+    fn run(state: &mut InlineState) -> Option<(Node, usize)> {
+        let mut chars = state.src[state.pos..state.pos_max].chars();
+        if chars.next().unwrap() != '~' {
+            return None;
+        }
+
+        let scanned = state.scan_delims(state.pos, true);
+        let mut marker = EmphMarker {
+            marker: '~',
+            length: scanned.length,
+            remaining: scanned.length,
+            open: scanned.can_open,
+            close: scanned.can_close,
+        };
+        if scanned.length > 2 {
+            // kept as text by FragmentsJoin
+            marker.open = false;
+            marker.close = false;
+        }
+
+        if marker.close {
+            if let Some(idx) = find_opener(state, scanned.length) {
+                // don't wrap content that would end up nested deeper than allowed
+                let limit = state.md.limits.max_nesting;
+                if exceeds_depth(&state.node.children[idx + 1..], limit - state.level) {
+                    ParseBudget::report(state.root_ext, ParseError::TooDeep { limit });
+                } else {
+                    let mut node = Node::new(Strikethrough { marker: '~' });
+                    node.children = state.node.children.split_off(idx + 1);
+                    let opener = state.node.children.pop().unwrap();
+
+                    // openers after this one are gone, so are the previous fails
+                    let bottom = state.node.ext.get_or_insert_default::<OpenersBottom>();
+                    for min_idx in bottom.0.iter_mut() {
+                        *min_idx = (*min_idx).min(idx);
+                    }
+
+                    // backtrack to the opener, so source map covers both markers
+                    let start = state_pos_for(state, opener.srcmap.unwrap().get_byte_offsets().0);
+                    let token_len = state.pos + scanned.length - start;
+                    state.pos = start;
+                    return Some((node, token_len));
+                }
+            }
+        }
+
+        Some((Node::new(marker), scanned.length))
+    }
+}
+
+// find the closest unmatched opener of the same length
+fn find_opener(state: &mut InlineState, length: usize) -> Option<usize> {
+    let bottom = state.node.ext.get_or_insert_default::<OpenersBottom>();
+    let min_idx = bottom.0[length - 1].min(state.node.children.len());
+
+    let found = state.node.children[min_idx..]
+        .iter()
+        .rposition(|node| {
+            node.cast::<EmphMarker>().is_some_and(|opener| {
+                opener.marker == '~' && opener.open && opener.length == length
+            })
+        })
+        .map(|idx| idx + min_idx);
+
+    if found.is_none() {
+        // make sure the search is linear, same as in emphasis
+        let len = state.node.children.len();
+        let bottom = state.node.ext.get_or_insert_default::<OpenersBottom>();
+        bottom.0[length - 1] = len;
+    }
+
+    found
+}
+
+// position in `state.src` of a byte offset in the source, opposite of `state.get_map`
+fn state_pos_for(state: &InlineState, src_pos: usize) -> usize {
+    let line = state.srcmap.partition_point(|x| x.1 <= src_pos) - 1;
+    state.srcmap[line].0 + (src_pos - state.srcmap[line].1)
+}
@@ -0,0 +1,90 @@
+//! Disallowed raw HTML
+//!
+//! Tags that change how the rest of the page is interpreted (`<script>`, `<style>`,
+//! `<iframe>`, etc.) are escaped in raw HTML, other tags are kept as is.
+//!
+//! Raw HTML nodes are left unchanged (so Markdown output keeps them), tags are
+//! escaped when rendering to HTML with [HtmlRenderOptions::tagfilter] enabled.
+//! This plugin enables it in options used by [Node::render](crate::Node::render),
+//! options passed to [render_with](crate::Node::render_with) must enable it explicitly.
+//!
+//! ```rust
+//! let md = &mut markdown_that::MarkdownThat::new();
+//! markdown_that::plugins::cmark::add(md);
+//! markdown_that::plugins::html::add(md);
+//! markdown_that::plugins::gfm::tagfilter::add(md);
+//!
+//! let ast = md.parse("<script>alert(1)</script>");
+//! assert_eq!(ast.render(), "&lt;script>alert(1)&lt;/script>\n");
+//! assert_eq!(ast.render_markdown(), "<script>alert(1)</script>\n");
+//! ```
+//!
+//! <https://github.github.com/gfm/#disallowed-raw-html-extension->
+use std::borrow::Cow;
+
+use crate::parser::renderer::HtmlRenderOptions;
+use crate::{MarkdownThat, Renderer};
+
+const DISALLOWED_TAGS: [&str; 9] = [
+    "title",
+    "textarea",
+    "style",
+    "xmp",
+    "iframe",
+    "noembed",
+    "noframes",
+    "script",
+    "plaintext",
+];
+
+pub fn add(md: &mut MarkdownThat) {
+    md.ext
+        .get_or_insert_default::<HtmlRenderOptions>()
+        .tagfilter = true;
+}
+
+/// Replace `<` of disallowed opening and closing tags in `html` with `&lt;`.
+pub fn filter_tags(html: &str) -> Cow<'_, str> {
+    let mut result = String::new();
+    let mut last = 0;
+
+    for (pos, _) in html.match_indices('<') {
+        if is_disallowed_tag(&html[pos + 1..]) {
+            result.push_str(&html[last..pos]);
+            result.push_str("&lt;");
+            last = pos + 1;
+        }
+    }
+
+    if last == 0 {
+        return Cow::Borrowed(html);
+    }
+    result.push_str(&html[last..]);
+    Cow::Owned(result)
+}
+
+// write raw HTML, escaping disallowed tags if it's enabled in render options
+pub(crate) fn write_html(html: &str, fmt: &mut dyn Renderer) {
+    let enabled = fmt
+        .ext()
+        .get::<HtmlRenderOptions>()
+        .is_some_and(|options| options.tagfilter);
+    if enabled {
+        fmt.text_raw(&filter_tags(html));
+    } else {
+        fmt.text_raw(html);
+    }
+}
+
+// `src` is what follows `<`
+fn is_disallowed_tag(src: &str) -> bool {
+    let src = src.strip_prefix('/').unwrap_or(src);
+    DISALLOWED_TAGS.iter().any(|tag| {
+        src.get(..tag.len())
+            .is_some_and(|name| name.eq_ignore_ascii_case(tag))
+            && match src.as_bytes().get(tag.len()) {
+                None => true,
+                Some(&b) => b.is_ascii_whitespace() || b == b'>' || b == b'/',
+            }
+    })
+}
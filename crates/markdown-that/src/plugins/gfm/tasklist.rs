@@ -0,0 +1,253 @@
+//! Task list items
+//!
+//! `- [ ] todo` or `- [x] done`
+//!
+//! ```rust
+//! let md = &mut markdown_that::MarkdownThat::new();
+//! markdown_that::plugins::cmark::add(md);
+//! markdown_that::plugins::gfm::tasklist::add(md);
+//!
+//! let html = md.parse("- [x] done\n- todo").render();
+//! assert_eq!(html, concat!(
+//!     "<ul class=\"contains-task-list\">\n",
+//!     "<li class=\"task-list-item\"><input checked=\"\" disabled=\"\" type=\"checkbox\"> done</li>\n",
+//!     "<li>todo</li>\n",
+//!     "</ul>\n",
+//! ));
+//! ```
+//!
+//! <https://github.github.com/gfm/#task-list-items-extension->
+use crate::common::sourcemap::SourcePos;
+use crate::parser::block::builtin::BlockParserRule;
+use crate::parser::core::CoreRule;
+use crate::parser::extset::MarkdownThatExt;
+use crate::parser::inline::InlineRoot;
+use crate::parser::inline::builtin::InlineParserRule;
+use crate::parser::renderer::{AnsiRenderer, HtmlRenderOptions, MarkdownRenderer, TextRenderer};
+use crate::plugins::cmark::block::list::{BulletList, ListItem, ListRenderContext, OrderedList};
+use crate::plugins::cmark::block::paragraph::Paragraph;
+use crate::{MarkdownThat, Node, NodeValue, Renderer};
+
+/// List item that starts with `[ ]` or `[x]`, it replaces [ListItem].
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TaskListItem {
+    pub checked: bool,
+    /// Position of `[ ]` or `[x]` in the source, used by [toggle].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub marker_srcmap: Option<SourcePos>,
+    /// Checkbox is rendered without `disabled` attribute, see [TaskListOptions::enabled].
+    pub enabled: bool,
+}
+
+impl TaskListItem {
+    /// Marker as it's written in Markdown (`[ ] ` or `[x] `), used by text renderers.
+    pub fn marker(&self) -> &'static str {
+        if self.checked { "[x] " } else { "[ ] " }
+    }
+}
+
+impl NodeValue for TaskListItem {
+    fn render(&self, node: &Node, fmt: &mut dyn Renderer) {
+        let mut attrs = Vec::new();
+        if self.checked {
+            attrs.push(("checked", String::new()));
+        }
+        if !self.enabled {
+            attrs.push(("disabled", String::new()));
+        }
+        attrs.push(("type", "checkbox".to_owned()));
+
+        fmt.open("li", &node.attrs);
+        let gfm = fmt
+            .ext()
+            .get::<HtmlRenderOptions>()
+            .is_some_and(|options| options.gfm);
+        if gfm {
+            // void element, written without closing slash like GitHub does
+            fmt.open("input", &attrs);
+        } else {
+            fmt.self_close("input", &attrs);
+        }
+        fmt.text_raw(" ");
+        fmt.contents(&node.children);
+        fmt.close("li");
+        fmt.cr();
+    }
+
+    fn render_markdown(&self, node: &Node, fmt: &mut MarkdownRenderer) {
+        ListRenderContext::render_item(node, fmt, self.marker());
+    }
+
+    fn render_text(&self, node: &Node, fmt: &mut TextRenderer) {
+        ListRenderContext::render_item(node, fmt, self.marker());
+    }
+
+    fn render_ansi(&self, node: &Node, fmt: &mut AnsiRenderer) {
+        ListRenderContext::render_item(node, fmt, self.marker());
+    }
+}
+
+/// Settings for [add_with].
+#[derive(Debug, Default, Clone, Copy)]
+pub struct TaskListOptions {
+    /// Render checkboxes that can be clicked (without `disabled` attribute),
+    /// [toggle] updates the source when they are.
+    pub enabled: bool,
+    /// Add `task-list-item` class to items and `contains-task-list` class
+    /// to lists that have them, same as GitHub does.
+    pub classes: bool,
+}
+
+impl MarkdownThatExt for TaskListOptions {}
+
+/// Add task list items with disabled checkboxes and GitHub classes.
+pub fn add(md: &mut MarkdownThat) {
+    add_with(
+        md,
+        TaskListOptions {
+            enabled: false,
+            classes: true,
+        },
+    );
+}
+
+pub fn add_with(md: &mut MarkdownThat, options: TaskListOptions) {
+    md.ext.insert(options);
+
+    if !md.has_rule::<TaskListRule>() {
+        md.add_rule::<TaskListRule>()
+            .after::<BlockParserRule>()
+            .before::<InlineParserRule>();
+    }
+}
+
+/// Check or uncheck the task list item with marker at `offset` (any byte of `[ ]`),
+/// returns updated `src`, or `None` if there is no task list item there.
+///
+/// ```rust
+/// use markdown_that::plugins::gfm::tasklist;
+///
+/// let md = &mut markdown_that::MarkdownThat::new();
+/// markdown_that::plugins::cmark::add(md);
+/// tasklist::add(md);
+///
+/// let src = "- [ ] foo\n- [x] bar\n";
+/// assert_eq!(tasklist::toggle(md, src, 2).unwrap(), "- [x] foo\n- [x] bar\n");
+/// assert_eq!(tasklist::toggle(md, src, 12).unwrap(), "- [ ] foo\n- [ ] bar\n");
+/// assert_eq!(tasklist::toggle(md, src, 6), None);
+/// ```
+pub fn toggle(md: &MarkdownThat, src: &str, offset: usize) -> Option<String> {
+    let mut found = None;
+    md.parse(src).walk(|node, _| {
+        let Some(item) = node.cast::<TaskListItem>() else {
+            return;
+        };
+        let Some((start, end)) = item.marker_srcmap.map(|map| map.get_byte_offsets()) else {
+            return;
+        };
+        if (start..end).contains(&offset) {
+            found = Some((start, item.checked));
+        }
+    });
+
+    let (start, checked) = found?;
+    let mut result = src.to_owned();
+    result.replace_range(start + 1..start + 2, if checked { " " } else { "x" });
+    Some(result)
+}
+
+#[doc(hidden)]
+pub struct TaskListRule;
+impl CoreRule for TaskListRule {
+    fn run(root: &mut Node, md: &MarkdownThat) {
+        let options = md.ext.get::<TaskListOptions>().copied().unwrap_or_default();
+
+        root.walk_mut(|node, _| {
+            if !node.is::<BulletList>() && !node.is::<OrderedList>() {
+                return;
+            }
+
+            let mut found = false;
+            for item in node.children.iter_mut() {
+                if let Some(task) = task_list_item(item, &options) {
+                    item.replace(task);
+                    if options.classes {
+                        item.attrs.push(("class", "task-list-item".to_owned()));
+                    }
+                    found = true;
+                }
+            }
+
+            if found && options.classes {
+                node.attrs.push(("class", "contains-task-list".to_owned()));
+            }
+        });
+    }
+}
+
+// strip the marker from the item content if it starts with one
+fn task_list_item(item: &mut Node, options: &TaskListOptions) -> Option<TaskListItem> {
+    if !item.is::<ListItem>() {
+        return None;
+    }
+
+    // paragraphs are unwrapped in tight lists
+    let first = item.children.first_mut()?;
+    let inline = if first.is::<Paragraph>() {
+        first.children.first_mut()?
+    } else {
+        first
+    };
+    let inline = inline.cast_mut::<InlineRoot>()?;
+
+    let marker_srcmap = match inline.mapping.first() {
+        Some(&(0, src_pos)) => Some(SourcePos::new(src_pos, src_pos + 3)),
+        _ => None,
+    };
+    let checked = strip_marker(inline)?;
+
+    Some(TaskListItem {
+        checked,
+        marker_srcmap,
+        enabled: options.enabled,
+    })
+}
+
+// remove `[ ]` or `[x]` with the whitespace after it from the start of the content,
+// returns whether the box is checked
+fn strip_marker(inline: &mut InlineRoot) -> Option<bool> {
+    let bytes = inline.content.as_bytes();
+    if bytes.len() < 4 || bytes[0] != b'[' || bytes[2] != b']' {
+        return None;
+    }
+
+    let checked = match bytes[1] {
+        b' ' | b'\t' => false,
+        b'x' | b'X' => true,
+        _ => return None,
+    };
+
+    let rest = &inline.content[3..];
+    let trimmed = rest.trim_start_matches([' ', '\t', '\n']);
+    if trimmed.len() == rest.len() || trimmed.is_empty() {
+        return None;
+    }
+
+    let len = inline.content.len() - trimmed.len();
+    inline.content.replace_range(..len, "");
+
+    // shift mapping, the first line now starts after the marker
+    let mut mapping = Vec::with_capacity(inline.mapping.len());
+    for &(pos, src_pos) in inline.mapping.iter() {
+        if pos <= len {
+            mapping.clear();
+            mapping.push((0, src_pos + len - pos));
+        } else {
+            mapping.push((pos - len, src_pos));
+        }
+    }
+    inline.mapping = mapping;
+
+    Some(checked)
+}
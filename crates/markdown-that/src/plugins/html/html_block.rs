@@ -8,9 +8,12 @@ use std::sync::LazyLock;
 use super::utils::blocks::*;
 use super::utils::regexps::*;
 use crate::parser::block::{BlockRule, BlockState};
+use crate::parser::renderer::MarkdownRenderer;
+use crate::plugins::gfm::tagfilter;
 use crate::{MarkdownThat, Node, NodeValue, Renderer};
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct HtmlBlock {
     pub content: String,
 }
@@ -18,9 +21,15 @@ pub struct HtmlBlock {
 impl NodeValue for HtmlBlock {
     fn render(&self, _: &Node, fmt: &mut dyn Renderer) {
         fmt.cr();
-        fmt.text_raw(&self.content);
+        tagfilter::write_html(&self.content, fmt);
         fmt.cr();
     }
+
+    fn render_markdown(&self, _: &Node, fmt: &mut MarkdownRenderer) {
+        fmt.open_block();
+        fmt.text_raw(self.content.trim_end_matches('\n'));
+        fmt.close_block();
+    }
 }
 
 pub fn add(md: &mut MarkdownThat) {
@@ -3,15 +3,22 @@
 //! <https://spec.commonmark.org/0.30/#raw-html>
 use super::utils::regexps::*;
 use crate::parser::inline::{InlineRule, InlineState};
+use crate::parser::renderer::MarkdownRenderer;
+use crate::plugins::gfm::tagfilter;
 use crate::{MarkdownThat, Node, NodeValue, Renderer};
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct HtmlInline {
     pub content: String,
 }
 
 impl NodeValue for HtmlInline {
     fn render(&self, _: &Node, fmt: &mut dyn Renderer) {
+        tagfilter::write_html(&self.content, fmt);
+    }
+
+    fn render_markdown(&self, _: &Node, fmt: &mut MarkdownRenderer) {
         fmt.text_raw(&self.content);
     }
 }
@@ -0,0 +1,138 @@
+//! Markdownlint-style checks, they add [Diagnostics] to the parsed document.
+//!
+//! ```rust
+//! use markdown_that::parser::diagnostics::Diagnostics;
+//!
+//! let md = &mut markdown_that::MarkdownThat::new();
+//! markdown_that::plugins::cmark::add(md);
+//! markdown_that::plugins::lint::add(md);
+//!
+//! let ast = md.parse("# title\n\n### subtitle\n\n![](image.png)\n");
+//! let messages: Vec<_> = Diagnostics::of(&ast).iter().map(|d| d.to_string()).collect();
+//! assert_eq!(messages, [
+//!     "warning[MD001]: heading levels should only increment by one level at a time (expected h2, got h3)",
+//!     "warning[MD045]: image has no alt text",
+//! ]);
+//! ```
+//!
+//! Built-in checks are listed in [rules], you can add your own by implementing [LintRule]:
+//!
+//! ```rust
+//! use markdown_that::parser::diagnostics::{Diagnostic, Diagnostics, Severity};
+//! use markdown_that::plugins::cmark::block::hr::ThematicBreak;
+//! use markdown_that::plugins::lint::LintRule;
+//! use markdown_that::{MarkdownThat, Node};
+//!
+//! struct NoHorizontalRules;
+//! impl LintRule for NoHorizontalRules {
+//!     fn check(root: &Node, _: &MarkdownThat, diagnostics: &mut Vec<Diagnostic>) {
+//!         root.walk(|node, _| {
+//!             if node.is::<ThematicBreak>() {
+//!                 diagnostics.push(Diagnostic::new(
+//!                     Severity::Error, "no-hr", "horizontal rules are not allowed", node.srcmap,
+//!                 ));
+//!             }
+//!         });
+//!     }
+//! }
+//!
+//! let md = &mut MarkdownThat::new();
+//! markdown_that::plugins::cmark::add(md);
+//! markdown_that::plugins::lint::add_rule::<NoHorizontalRules>(md);
+//!
+//! let ast = md.parse("foo\n\n---\n");
+//! assert_eq!(Diagnostics::of(&ast)[0].code, "no-hr");
+//! ```
+pub mod rules;
+
+use crate::common::TypeKey;
+use crate::parser::block::builtin::BlockParserRule;
+use crate::parser::core::{CoreRule, Root};
+use crate::parser::diagnostics::{Diagnostic, Diagnostics};
+use crate::parser::extset::MarkdownThatExt;
+use crate::parser::inline::builtin::InlineParserRule;
+use crate::plugins::cmark::block::reference::UsedReferences;
+use crate::{MarkdownThat, Node};
+
+/// A check that runs on the whole document after it's parsed.
+pub trait LintRule: 'static {
+    /// Inspect the tree and add found problems to `diagnostics`.
+    fn check(root: &Node, md: &MarkdownThat, diagnostics: &mut Vec<Diagnostic>);
+}
+
+type CheckFn = fn(&Node, &MarkdownThat, &mut Vec<Diagnostic>);
+
+#[derive(Debug, Default)]
+struct LintRules(Vec<(TypeKey, CheckFn)>);
+impl MarkdownThatExt for LintRules {}
+
+/// Add all [built-in checks](rules).
+pub fn add(md: &mut MarkdownThat) {
+    add_rule::<rules::HeadingIncrement>(md);
+    add_rule::<rules::NoEmptyLinks>(md);
+    add_rule::<rules::NoAltText>(md);
+    add_rule::<rules::UnusedReferences>(md);
+}
+
+/// Add a single check, it runs after all other core rules.
+pub fn add_rule<T: LintRule>(md: &mut MarkdownThat) {
+    if !md.has_rule::<LintRunner>() {
+        md.add_rule::<TrackReferences>()
+            .after::<BlockParserRule>()
+            .before::<InlineParserRule>();
+        md.add_rule::<LintRunner>().after_all();
+        // checks like unused definitions need the whole document
+        md.mark_document_rule::<LintRunner>();
+    }
+
+    let rules = md.ext.get_or_insert_default::<LintRules>();
+    let key = TypeKey::of::<T>();
+    if !rules.0.iter().any(|(k, _)| *k == key) {
+        rules.0.push((key, T::check));
+    }
+}
+
+pub fn has_rule<T: LintRule>(md: &MarkdownThat) -> bool {
+    md.ext
+        .get::<LintRules>()
+        .is_some_and(|rules| rules.0.iter().any(|(k, _)| *k == TypeKey::of::<T>()))
+}
+
+pub fn remove_rule<T: LintRule>(md: &mut MarkdownThat) {
+    if let Some(rules) = md.ext.get_mut::<LintRules>() {
+        rules.0.retain(|(k, _)| *k != TypeKey::of::<T>());
+    }
+}
+
+// collect references used by links, so unused ones can be reported
+#[doc(hidden)]
+pub struct TrackReferences;
+impl CoreRule for TrackReferences {
+    fn run(root: &mut Node, _: &MarkdownThat) {
+        let data = root.cast_mut::<Root>().unwrap();
+        data.ext.get_or_insert_default::<UsedReferences>();
+    }
+}
+
+#[doc(hidden)]
+pub struct LintRunner;
+impl CoreRule for LintRunner {
+    fn run(root: &mut Node, md: &MarkdownThat) {
+        let Some(rules) = md.ext.get::<LintRules>() else {
+            return;
+        };
+
+        let mut found = Vec::new();
+        for (_, check) in rules.0.iter() {
+            check(root, md, &mut found);
+        }
+
+        let data = root.cast_mut::<Root>().unwrap();
+        let diagnostics = data.ext.get_or_insert_default::<Diagnostics>();
+        diagnostics.0.extend(found);
+        // parser and each check report in their own order, show them as they appear in the source
+        diagnostics
+            .0
+            .sort_by_key(|diagnostic| diagnostic.srcmap.map(|map| map.get_byte_offsets().0));
+    }
+}
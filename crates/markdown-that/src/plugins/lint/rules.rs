@@ -0,0 +1,110 @@
+//! Built-in checks, named after the respective [markdownlint](https://github.com/DavidAnson/markdownlint) rules.
+//!
+//! Some problems are reported by the parser itself, these don't need a check here:
+//!  - `MD053` - duplicate link reference definition (first one is used)
+//!  - `MD056` - table row has a different number of cells than the header
+use crate::common::utils::normalize_reference;
+use crate::parser::core::Root;
+use crate::parser::diagnostics::{Diagnostic, Severity};
+use crate::plugins::cmark::block::heading::ATXHeading;
+use crate::plugins::cmark::block::lheading::SetextHeader;
+use crate::plugins::cmark::block::reference::{Definition, UsedReferences};
+use crate::plugins::cmark::inline::image::Image;
+use crate::plugins::cmark::inline::link::Link;
+use crate::plugins::lint::LintRule;
+use crate::{MarkdownThat, Node};
+
+/// `MD001` - heading levels should only increment by one level at a time.
+pub struct HeadingIncrement;
+impl LintRule for HeadingIncrement {
+    fn check(root: &Node, _: &MarkdownThat, diagnostics: &mut Vec<Diagnostic>) {
+        let mut prev_level = None;
+        root.walk(|node, _| {
+            let level = if let Some(heading) = node.cast::<ATXHeading>() {
+                heading.level
+            } else if let Some(heading) = node.cast::<SetextHeader>() {
+                heading.level
+            } else {
+                return;
+            };
+
+            if let Some(prev_level) = prev_level.filter(|prev| level > prev + 1) {
+                diagnostics.push(Diagnostic::new(
+                    Severity::Warning,
+                    "MD001",
+                    format!(
+                        "heading levels should only increment by one level at a time (expected h{}, got h{level})",
+                        prev_level + 1
+                    ),
+                    node.srcmap,
+                ));
+            }
+            prev_level = Some(level);
+        });
+    }
+}
+
+/// `MD042` - links should have a destination.
+pub struct NoEmptyLinks;
+impl LintRule for NoEmptyLinks {
+    fn check(root: &Node, _: &MarkdownThat, diagnostics: &mut Vec<Diagnostic>) {
+        root.walk(|node, _| {
+            if let Some(link) = node.cast::<Link>() {
+                if link.url.is_empty() || link.url == "#" {
+                    diagnostics.push(Diagnostic::new(
+                        Severity::Warning,
+                        "MD042",
+                        "link has no destination",
+                        node.srcmap,
+                    ));
+                }
+            }
+        });
+    }
+}
+
+/// `MD045` - images should have alternate text.
+pub struct NoAltText;
+impl LintRule for NoAltText {
+    fn check(root: &Node, _: &MarkdownThat, diagnostics: &mut Vec<Diagnostic>) {
+        root.walk(|node, _| {
+            if node.is::<Image>() && node.collect_text().trim().is_empty() {
+                diagnostics.push(Diagnostic::new(
+                    Severity::Warning,
+                    "MD045",
+                    "image has no alt text",
+                    node.srcmap,
+                ));
+            }
+        });
+    }
+}
+
+/// `MD053` - link reference definitions should be used.
+pub struct UnusedReferences;
+impl LintRule for UnusedReferences {
+    fn check(root: &Node, _: &MarkdownThat, diagnostics: &mut Vec<Diagnostic>) {
+        let Some(used) = root
+            .cast::<Root>()
+            .and_then(|root| root.ext.get::<UsedReferences>())
+        else {
+            return;
+        };
+
+        root.walk(|node, _| {
+            if let Some(definition) = node.cast::<Definition>() {
+                if !used.0.contains(&normalize_reference(&definition.label)) {
+                    diagnostics.push(Diagnostic::new(
+                        Severity::Warning,
+                        "MD053",
+                        format!(
+                            "link reference definition \"{}\" is never used",
+                            definition.label
+                        ),
+                        node.srcmap,
+                    ));
+                }
+            }
+        });
+    }
+}
@@ -16,5 +16,7 @@
 //! ```
 pub mod cmark;
 pub mod extra;
+pub mod gfm;
 pub mod html;
+pub mod lint;
 pub mod sourcepos;
@@ -26,15 +26,32 @@ impl CoreRule for SyntaxPosRule {
         let source = root.cast::<Root>().unwrap().content.as_str();
         let mapping = SourceWithLineStarts::new(source);
 
-        root.walk_mut(|node, _| {
-            if let Some(map) = node.srcmap {
-                let ((startline, startcol), (endline, endcol)) = map.get_positions(&mapping);
-                node.attrs.push((
-                    "data-sourcepos",
-                    format!("{}:{}-{}:{}", startline, startcol, endline, endcol),
-                ));
-            }
-        });
+        root.walk_mut(|node, _| set_sourcepos(node, &mapping, 0));
+    }
+}
+
+// add (or update, if node was shifted by incremental reparsing) `data-sourcepos` attribute,
+// `line_offset` is a number of lines before the source in `mapping` (used in streaming)
+pub(crate) fn set_sourcepos(node: &mut Node, mapping: &SourceWithLineStarts, line_offset: u32) {
+    let Some(map) = node.srcmap else {
+        return;
+    };
+    let ((startline, startcol), (endline, endcol)) = map.get_positions(mapping);
+    let value = format!(
+        "{}:{}-{}:{}",
+        startline + line_offset,
+        startcol,
+        endline + line_offset,
+        endcol
+    );
+
+    match node
+        .attrs
+        .iter_mut()
+        .find(|(name, _)| *name == "data-sourcepos")
+    {
+        Some(attr) => attr.1 = value,
+        None => node.attrs.push(("data-sourcepos", value)),
     }
 }
 
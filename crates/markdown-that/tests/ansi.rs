@@ -0,0 +1,180 @@
+use markdown_that::parser::renderer::{AnsiRenderOptions, AnsiRenderer};
+
+fn run_with(input: &str, options: AnsiRenderOptions, output: &str) {
+    let md = &mut markdown_that::MarkdownThat::new();
+    markdown_that::plugins::cmark::add(md);
+    markdown_that::plugins::html::add(md);
+    markdown_that::plugins::extra::strikethrough::add(md);
+    markdown_that::plugins::extra::tables::add(md);
+    let node = md.parse(input);
+    assert_eq!(node.render_ansi(&options), output);
+}
+
+fn run(input: &str, output: &str) {
+    run_with(input, AnsiRenderOptions::default(), output);
+}
+
+#[test]
+fn inline_markup() {
+    run(
+        "Some *emph*, **strong**, ~~strike~~ and `code`",
+        "Some \x1b[3memph\x1b[0m, \x1b[1mstrong\x1b[0m, \x1b[9mstrike\x1b[0m and \x1b[36mcode\x1b[0m\n",
+    );
+}
+
+#[test]
+fn nested_styles() {
+    run("**bold *both***", "\x1b[1mbold \x1b[3mboth\x1b[0m\n");
+}
+
+#[test]
+fn blocks() {
+    run_with(
+        "# Title\n\nSub\n---\n\n***\n\n    code\n\n      more",
+        AnsiRenderOptions {
+            width: Some(10),
+            ..Default::default()
+        },
+        "\x1b[1;4mTitle\x1b[0m\n\n\x1b[1mSub\x1b[0m\n\n\x1b[2m──────────\x1b[0m\n\n    \x1b[2mcode\x1b[0m\n\n    \x1b[2m  more\x1b[0m\n",
+    );
+}
+
+#[test]
+fn code_keeps_blank_lines() {
+    run(
+        "```\nfoo\n\nbar\n```",
+        "    \x1b[2mfoo\x1b[0m\n\n    \x1b[2mbar\x1b[0m\n",
+    );
+}
+
+#[test]
+fn control_chars_replaced() {
+    run(
+        "foo \x1b[31mred\x07\n\n    code\x1b]0;title\x07\tx",
+        "foo \u{FFFD}[31mred\u{FFFD}\n\n    \x1b[2mcode\u{FFFD}]0;title\u{FFFD}\tx\x1b[0m\n",
+    );
+}
+
+#[test]
+fn hyperlinks() {
+    run(
+        "[link *text*](http://x.com) <http://foo.bar>",
+        "\x1b]8;;http://x.com\x1b\\\x1b[4mlink \x1b[3mtext\x1b]8;;\x1b\\\x1b[0m \x1b]8;;http://foo.bar\x1b\\\x1b[4mhttp://foo.bar\x1b[0m\x1b]8;;\x1b\\\n",
+    );
+}
+
+#[test]
+fn hyperlinks_disabled() {
+    run_with(
+        "[link](http://x.com) ![alt](i.png)",
+        AnsiRenderOptions {
+            hyperlinks: false,
+            ..Default::default()
+        },
+        "\x1b[4mlink\x1b[0m \x1b[2m(http://x.com) [alt] (i.png)\x1b[0m\n",
+    );
+}
+
+#[test]
+fn wrapping() {
+    run_with(
+        "one two three four\nfive six seven eight nine ten",
+        AnsiRenderOptions {
+            width: Some(16),
+            ..Default::default()
+        },
+        "one two three\nfour five six\nseven eight nine\nten\n",
+    );
+}
+
+#[test]
+fn no_wrapping() {
+    run_with(
+        "one two three four\nfive  \nsix",
+        AnsiRenderOptions {
+            width: None,
+            ..Default::default()
+        },
+        "one two three four\nfive\nsix\n",
+    );
+}
+
+#[test]
+fn blockquotes() {
+    run_with(
+        "> quoted text that wraps around\n>\n> second",
+        AnsiRenderOptions {
+            width: Some(16),
+            ..Default::default()
+        },
+        "\x1b[2m│\x1b[0m quoted text\n\x1b[2m│\x1b[0m that wraps\n\x1b[2m│\x1b[0m around\n\x1b[2m│\x1b[0m\n\x1b[2m│\x1b[0m second\n",
+    );
+}
+
+#[test]
+fn lists() {
+    run(
+        "- one\n- two\n  - nested\n\n1. a\n2. b",
+        "• one\n• two\n  • nested\n\n1. a\n2. b\n",
+    );
+    run_with(
+        "- item text that wraps",
+        AnsiRenderOptions {
+            width: Some(12),
+            ..Default::default()
+        },
+        "• item text\n  that wraps\n",
+    );
+}
+
+#[test]
+fn styles_do_not_leak_into_prefixes() {
+    run_with(
+        "> *emphasis that wraps*",
+        AnsiRenderOptions {
+            width: Some(14),
+            ..Default::default()
+        },
+        "\x1b[2m│\x1b[0m \x1b[3memphasis\x1b[0m\n\x1b[2m│\x1b[0m \x1b[3mthat wraps\x1b[0m\n",
+    );
+}
+
+#[test]
+fn tables() {
+    run(
+        "| a | b |\n|:-|-:|\n| *x* | long cell |\n| y |",
+        "┌───┬───────────┐\n│ \x1b[1ma\x1b[0m │         \x1b[1mb\x1b[0m │\n├───┼───────────┤\n│ \x1b[3mx\x1b[0m │ long cell │\n│ y │           │\n└───┴───────────┘\n",
+    );
+}
+
+#[test]
+fn html_is_dropped() {
+    run("<div>\nfoo\n</div>\n\nbar <b>baz</b>", "bar baz\n");
+}
+
+#[test]
+fn visible_width() {
+    assert_eq!(AnsiRenderer::visible_width("plain"), 5);
+    assert_eq!(AnsiRenderer::visible_width("\x1b[1;4mbold\x1b[0m"), 4);
+    assert_eq!(
+        AnsiRenderer::visible_width("\x1b]8;;http://x.com\x1b\\link\x1b]8;;\x1b\\"),
+        4
+    );
+    assert_eq!(AnsiRenderer::visible_width("│ ─"), 3);
+}
+
+#[cfg(feature = "syntect")]
+#[test]
+fn syntax_highlighting() {
+    let md = &mut markdown_that::MarkdownThat::new();
+    markdown_that::plugins::cmark::add(md);
+    markdown_that::plugins::extra::syntect::add(md);
+    let node = md.parse("```rust\nfn main() {}\n```");
+    let output = node.render_ansi(&AnsiRenderOptions::default());
+    assert!(output.starts_with("    \x1b[1;38;2;"));
+    assert!(!output.contains('<'));
+    assert_eq!(
+        AnsiRenderer::visible_width(output.trim_end()),
+        "    fn main() {}".len()
+    );
+}
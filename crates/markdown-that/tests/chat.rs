@@ -0,0 +1,121 @@
+use markdown_that::parser::renderer::{ChatDialect, ChatRenderOptions};
+
+fn run_with(input: &str, dialect: ChatDialect, output: &str) {
+    let md = &mut markdown_that::MarkdownThat::new();
+    markdown_that::plugins::cmark::add(md);
+    markdown_that::plugins::html::add(md);
+    markdown_that::plugins::extra::strikethrough::add(md);
+    markdown_that::plugins::extra::tables::add(md);
+    let node = md.parse(input);
+    assert_eq!(node.render_chat(&ChatRenderOptions { dialect }), output);
+}
+
+fn slack(input: &str, output: &str) {
+    run_with(input, ChatDialect::Slack, output);
+}
+
+fn discord(input: &str, output: &str) {
+    run_with(input, ChatDialect::Discord, output);
+}
+
+#[test]
+fn slack_inline() {
+    slack(
+        "**bold** *italic* ~~strike~~ `code`\nwrapped  \nbroken",
+        "*bold* _italic_ ~strike~ `code` wrapped\nbroken\n",
+    );
+}
+
+#[test]
+fn slack_escaping() {
+    slack("a & b < c > d `x<y`", "a &amp; b &lt; c &gt; d `x&lt;y`\n");
+}
+
+#[test]
+fn slack_links() {
+    slack(
+        "[the | docs](http://x.com/?a=1&b=2) <http://foo.bar> ![logo](l.png)",
+        "<http://x.com/?a=1&amp;b=2|the \u{FF5C} docs> <http://foo.bar> <l.png|logo>\n",
+    );
+}
+
+#[test]
+fn slack_headings() {
+    slack(
+        "# Release **1.0**\n\ntext\n\nSub\n---",
+        "*Release 1.0*\n\ntext\n\n*Sub*\n",
+    );
+}
+
+#[test]
+fn slack_lists_and_quotes() {
+    slack(
+        "- one\n- two\n  1. a\n  2. b\n\n> quote\n>\n> more",
+        "• one\n• two\n  1. a\n  2. b\n\n> quote\n>\n> more\n",
+    );
+}
+
+#[test]
+fn slack_code_and_tables() {
+    slack(
+        "```rust\nlet a = b && c;\n```\n\n| a | b |\n|---|--:|\n| 1 | 22 |",
+        "```\nlet a = b &amp;&amp; c;\n```\n\n```\na |  b\n--+---\n1 | 22\n```\n",
+    );
+}
+
+#[test]
+fn discord_inline() {
+    discord(
+        "**bold** *italic* ~~strike~~ `code` [docs](http://x.com) <http://foo.bar>",
+        "**bold** *italic* ~~strike~~ `code` [docs](http://x.com) http://foo.bar\n",
+    );
+}
+
+#[test]
+fn discord_headings_and_code() {
+    discord(
+        "# One\n\n#### Four\n\n```rust\nfn main() {}\n```",
+        "# One\n\n### Four\n\n```rust\nfn main() {}\n```\n",
+    );
+}
+
+#[test]
+fn discord_escaping() {
+    discord(
+        "\\*not em\\* a\\_b \\<x\\>\n\n\\- not list\n\n1\\. not list",
+        "\\*not em\\* a\\_b \\<x\\>\n\n\\- not list\n\n1\\. not list\n",
+    );
+}
+
+#[test]
+fn nested_styles() {
+    slack("# Title with **bold**", "*Title with bold*\n");
+    discord("**a *b* c**", "**a *b* c**\n");
+}
+
+#[cfg(feature = "syntect")]
+#[test]
+fn syntax_highlighting() {
+    let md = &mut markdown_that::MarkdownThat::new();
+    markdown_that::plugins::cmark::add(md);
+    markdown_that::plugins::extra::syntect::add(md);
+    let node = md.parse("```rust\nlet x = \"<a>\";\n```");
+    assert_eq!(
+        node.render_chat(&ChatRenderOptions {
+            dialect: ChatDialect::Discord
+        }),
+        "```rust\nlet x = \"<a>\";\n```\n"
+    );
+}
+
+#[test]
+fn footnotes() {
+    let md = &mut markdown_that::MarkdownThat::new();
+    markdown_that::plugins::cmark::add(md);
+    markdown_that::plugins::extra::footnotes::add(md);
+    let node = md.parse("foo[^a] bar^[*baz*] x[^a]\n\n[^a]: one\n\n    two\n");
+    assert_eq!(
+        node.render_chat(&ChatRenderOptions::default()),
+        "foo[1] bar[2] x[1]\n\n1. one\n\n   two\n\n2. _baz_\n"
+    );
+}
@@ -0,0 +1,142 @@
+fn run(input: &str, output: &str) {
+    let md = &mut markdown_that::MarkdownThat::new();
+    markdown_that::plugins::cmark::add(md);
+    markdown_that::plugins::html::add(md);
+    markdown_that::plugins::extra::strikethrough::add(md);
+    markdown_that::plugins::extra::tables::add(md);
+    let node = md.parse(input);
+    assert_eq!(node.render_docbook(), output);
+}
+
+#[test]
+fn paragraphs() {
+    run(
+        "foo *em* **strong** ~~del~~ `code`\nbar  \nbaz",
+        "<para>foo <emphasis>em</emphasis> <emphasis role=\"strong\">strong</emphasis> \
+         <emphasis role=\"strikethrough\">del</emphasis> <literal>code</literal>\n\
+         bar<?linebreak?>\nbaz</para>\n",
+    );
+}
+
+#[test]
+fn sections() {
+    run(
+        "intro\n\n# One\n\nfirst\n\n## Two\n\nsecond\n\n### Three\n\n## Four\n\nFive\n====\n\nlast",
+        "<para>intro</para>\n\
+         <section>\n<title>One</title>\n<para>first</para>\n\
+         <section>\n<title>Two</title>\n<para>second</para>\n\
+         <section>\n<title>Three</title>\n</section>\n</section>\n\
+         <section>\n<title>Four</title>\n</section>\n</section>\n\
+         <section>\n<title>Five</title>\n<para>last</para>\n</section>\n",
+    );
+}
+
+#[test]
+fn sections_skipping_levels() {
+    run(
+        "### Deep\n\na\n\n# Top\n\nb",
+        "<section>\n<title>Deep</title>\n<para>a</para>\n</section>\n\
+         <section>\n<title>Top</title>\n<para>b</para>\n</section>\n",
+    );
+}
+
+#[test]
+fn nested_headings() {
+    run(
+        "> ## Quoted\n> text",
+        "<blockquote>\n<bridgehead renderas=\"sect2\">Quoted</bridgehead>\n<para>text</para>\n</blockquote>\n",
+    );
+}
+
+#[test]
+fn lists() {
+    run(
+        "- one\n- two\n  1. a\n  2. b",
+        "<itemizedlist spacing=\"compact\">\n\
+         <listitem>\n<para>one</para>\n</listitem>\n\
+         <listitem>\n<para>two</para>\n\
+         <orderedlist spacing=\"compact\">\n\
+         <listitem>\n<para>a</para>\n</listitem>\n\
+         <listitem>\n<para>b</para>\n</listitem>\n\
+         </orderedlist>\n</listitem>\n</itemizedlist>\n",
+    );
+}
+
+#[test]
+fn loose_lists() {
+    run(
+        "3. x\n\n   y\n4. z",
+        "<orderedlist startingnumber=\"3\">\n\
+         <listitem>\n<para>x</para>\n<para>y</para>\n</listitem>\n\
+         <listitem>\n<para>z</para>\n</listitem>\n\
+         </orderedlist>\n",
+    );
+}
+
+#[test]
+fn code_blocks() {
+    run(
+        "```rust\nfn main() -> Vec<u8> {}\n```\n\n    indented",
+        "<programlisting language=\"rust\">fn main() -&gt; Vec&lt;u8&gt; {}</programlisting>\n\
+         <programlisting>indented</programlisting>\n",
+    );
+}
+
+#[test]
+fn links_and_images() {
+    run(
+        "[a & b](http://x.com/?a=1&b=2) <http://foo.bar> ![alt](i.png) ![](j.png)",
+        "<para><ulink url=\"http://x.com/?a=1&amp;b=2\">a &amp; b</ulink> \
+         <ulink url=\"http://foo.bar\">http://foo.bar</ulink> \
+         <inlinemediaobject><imageobject><imagedata fileref=\"i.png\"/></imageobject>\
+         <textobject><phrase>alt</phrase></textobject></inlinemediaobject> \
+         <inlinemediaobject><imageobject><imagedata fileref=\"j.png\"/></imageobject></inlinemediaobject></para>\n",
+    );
+}
+
+#[test]
+fn tables() {
+    run(
+        "| a | b |\n|:--|---|\n| 1 | *2* |",
+        "<informaltable>\n<tgroup cols=\"2\">\n\
+         <colspec colname=\"c1\" align=\"left\"/>\n<colspec colname=\"c2\"/>\n\
+         <thead>\n<row><entry>a</entry><entry>b</entry></row>\n</thead>\n\
+         <tbody>\n<row><entry>1</entry><entry><emphasis>2</emphasis></entry></row>\n</tbody>\n\
+         </tgroup>\n</informaltable>\n",
+    );
+}
+
+#[test]
+fn dropped_nodes() {
+    run(
+        "<div>\nfoo\n</div>\n\n***\n\nbar <b>baz</b>",
+        "<para>bar baz</para>\n",
+    );
+}
+
+#[cfg(feature = "syntect")]
+#[test]
+fn syntax_highlighting() {
+    let md = &mut markdown_that::MarkdownThat::new();
+    markdown_that::plugins::cmark::add(md);
+    markdown_that::plugins::extra::syntect::add(md);
+    let node = md.parse("```rust\nlet x = \"<a>\";\n```");
+    assert_eq!(
+        node.render_docbook(),
+        "<programlisting language=\"rust\">let x = &quot;&lt;a&gt;&quot;;</programlisting>\n"
+    );
+}
+
+#[test]
+fn footnotes() {
+    let md = &mut markdown_that::MarkdownThat::new();
+    markdown_that::plugins::cmark::add(md);
+    markdown_that::plugins::extra::footnotes::add(md);
+    let node = md.parse("foo[^a] bar^[baz] x[^a]\n\n[^a]: one\n\n    two\n");
+    assert_eq!(
+        node.render_docbook(),
+        "<para>foo[1] bar[2] x[1]</para>\n\
+         <orderedlist>\n<listitem>\n<para>one</para>\n<para>two</para>\n</listitem>\n\
+         <listitem>\n<para>baz</para>\n</listitem>\n</orderedlist>\n"
+    );
+}
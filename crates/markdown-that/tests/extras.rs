@@ -44,6 +44,254 @@ fn no_max_indent() {
     assert_eq!(result, "<p>paragraph</p>\n<ul>\n<li>item</li>\n</ul>\n");
 }
 
+#[test]
+fn render_to_writer() {
+    let md = &mut markdown_that::MarkdownThat::new();
+    markdown_that::plugins::cmark::add(md);
+    markdown_that::plugins::html::add(md);
+    let node = md.parse("# foo\0\n\n<b>bar\0</b>\n\n![\0](/\0)");
+    let expected = node.render();
+    assert!(expected.contains('\u{FFFD}'));
+    assert!(!expected.contains('\0'));
+
+    let mut bytes = Vec::new();
+    node.render_to(&mut bytes).unwrap();
+    assert_eq!(String::from_utf8(bytes).unwrap(), expected);
+
+    let mut string = String::new();
+    node.render_fmt(&mut string).unwrap();
+    assert_eq!(string, expected);
+}
+
+#[test]
+fn render_to_writer_error() {
+    struct Limited(usize);
+
+    impl std::io::Write for Limited {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            if self.0 < buf.len() {
+                return Err(std::io::Error::new(std::io::ErrorKind::WriteZero, "full"));
+            }
+            self.0 -= buf.len();
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let md = &mut markdown_that::MarkdownThat::new();
+    markdown_that::plugins::cmark::add(md);
+    let node = md.parse("foo\n\nbar\n\nbaz");
+
+    assert!(node.render_to(Limited(1000)).is_ok());
+    let err = node.render_to(Limited(10)).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::WriteZero);
+    assert_eq!(err.to_string(), "full");
+}
+
+#[test]
+fn html_options_softbreak() {
+    use markdown_that::parser::renderer::{HtmlRenderOptions, SoftbreakMode};
+    let md = &mut markdown_that::MarkdownThat::new();
+    markdown_that::plugins::cmark::add(md);
+    let node = md.parse("foo\nbar");
+
+    let render = |softbreak, xhtml| {
+        node.render_with(&HtmlRenderOptions {
+            softbreak,
+            xhtml,
+            ..Default::default()
+        })
+    };
+    assert_eq!(render(SoftbreakMode::Newline, false), "<p>foo\nbar</p>\n");
+    assert_eq!(render(SoftbreakMode::Break, false), "<p>foo<br>\nbar</p>\n");
+    assert_eq!(
+        render(SoftbreakMode::Break, true),
+        "<p>foo<br />\nbar</p>\n"
+    );
+    assert_eq!(render(SoftbreakMode::Space, false), "<p>foo bar</p>\n");
+}
+
+#[test]
+fn html_options_lang_prefix() {
+    use markdown_that::parser::renderer::HtmlRenderOptions;
+    let md = &mut markdown_that::MarkdownThat::new();
+    markdown_that::plugins::cmark::add(md);
+    let node = md.parse("```rust\nfoo\n```");
+
+    assert_eq!(
+        node.render(),
+        "<pre><code class=\"language-rust\">foo\n</code></pre>\n"
+    );
+    let options = HtmlRenderOptions {
+        lang_prefix: Some("lang-".into()),
+        ..Default::default()
+    };
+    assert_eq!(
+        node.render_with(&options),
+        "<pre><code class=\"lang-rust\">foo\n</code></pre>\n"
+    );
+}
+
+#[test]
+fn html_options_attr_quote() {
+    use markdown_that::parser::renderer::{AttrQuote, HtmlRenderOptions};
+    let md = &mut markdown_that::MarkdownThat::new();
+    markdown_that::plugins::cmark::add(md);
+    let node = md.parse("[a](<'\">)");
+
+    assert_eq!(node.render(), "<p><a href=\"'%22\">a</a></p>\n");
+    let mut node = md.parse("[a](/url)");
+    node.children[0].children[0]
+        .attrs
+        .push(("title", "it's \"quoted\"".into()));
+    let options = HtmlRenderOptions {
+        attr_quote: AttrQuote::Single,
+        ..Default::default()
+    };
+    assert_eq!(
+        node.render_with(&options),
+        "<p><a title='it&#x27;s \"quoted\"' href='/url'>a</a></p>\n"
+    );
+}
+
+#[test]
+fn html_options_indent() {
+    use markdown_that::parser::renderer::HtmlRenderOptions;
+    let md = &mut markdown_that::MarkdownThat::new();
+    markdown_that::plugins::cmark::add(md);
+    let node = md.parse("> - foo\n>\n>   ```\n>   code\n>     block\n>   ```\n> - bar\n>   baz");
+    let options = HtmlRenderOptions {
+        indent: 2,
+        ..Default::default()
+    };
+    assert_eq!(
+        node.render_with(&options),
+        concat!(
+            "<blockquote>\n",
+            "  <ul>\n",
+            "    <li>\n",
+            "      <p>foo</p>\n",
+            "      <pre><code>code\n",
+            "  block\n",
+            "</code></pre>\n",
+            "    </li>\n",
+            "    <li>\n",
+            "      <p>bar\n",
+            "      baz</p>\n",
+            "    </li>\n",
+            "  </ul>\n",
+            "</blockquote>\n",
+        )
+    );
+}
+
+#[test]
+fn html_options_indent_raw_html() {
+    use markdown_that::parser::renderer::HtmlRenderOptions;
+    let md = &mut markdown_that::MarkdownThat::new();
+    markdown_that::plugins::cmark::add(md);
+    markdown_that::plugins::html::add(md);
+    let node = md.parse("> <div>\n>   <p>raw</p>\n> </div>");
+    let options = HtmlRenderOptions {
+        indent: 2,
+        ..Default::default()
+    };
+    assert_eq!(
+        node.render_with(&options),
+        concat!(
+            "<blockquote>\n",
+            "<div>\n",
+            "  <p>raw</p>\n",
+            "</div>\n",
+            "</blockquote>\n",
+        )
+    );
+}
+
+#[test]
+fn html_renderer_wrapper() {
+    use markdown_that::Renderer;
+    use markdown_that::parser::extset::RenderExtSet;
+    use markdown_that::parser::renderer::HTMLRenderer;
+
+    // renderer that adds `rel="nofollow"` to all links
+    struct NoFollow(HTMLRenderer);
+
+    impl Renderer for NoFollow {
+        fn open(&mut self, tag: &str, attrs: &[(&str, String)]) {
+            if tag == "a" {
+                let mut attrs = attrs.to_vec();
+                attrs.push(("rel", "nofollow".into()));
+                self.0.open(tag, &attrs);
+            } else {
+                self.0.open(tag, attrs);
+            }
+        }
+        fn close(&mut self, tag: &str) {
+            self.0.close(tag);
+        }
+        fn self_close(&mut self, tag: &str, attrs: &[(&str, String)]) {
+            self.0.self_close(tag, attrs);
+        }
+        fn contents(&mut self, nodes: &[markdown_that::Node]) {
+            for node in nodes {
+                node.node_value.render(node, self);
+            }
+        }
+        fn cr(&mut self) {
+            self.0.cr();
+        }
+        fn text(&mut self, text: &str) {
+            self.0.text(text);
+        }
+        fn text_raw(&mut self, text: &str) {
+            self.0.text_raw(text);
+        }
+        fn ext(&mut self) -> &mut RenderExtSet {
+            self.0.ext()
+        }
+    }
+
+    let md = &mut markdown_that::MarkdownThat::new();
+    markdown_that::plugins::cmark::add(md);
+    let node = md.parse("[*foo*](/url)");
+    let mut fmt = NoFollow(HTMLRenderer::default());
+    node.node_value.render(&node, &mut fmt);
+    assert_eq!(
+        String::from(fmt.0),
+        "<p><a href=\"/url\" rel=\"nofollow\"><em>foo</em></a></p>\n"
+    );
+}
+
+#[test]
+fn merge_attrs() {
+    use markdown_that::parser::renderer::merge_attrs;
+    let attrs = [
+        ("class", "a".to_owned()),
+        ("data-x", "1".to_owned()),
+        ("style", "color: red".to_owned()),
+        ("class", "b".to_owned()),
+        ("data-x", "2".to_owned()),
+        ("style", "margin: 0".to_owned()),
+    ];
+    let merged = merge_attrs(&attrs)
+        .into_iter()
+        .map(|(name, value)| (name, value.into_owned()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        merged,
+        [
+            ("class", "a b".to_owned()),
+            ("data-x", "1".to_owned()),
+            ("data-x", "2".to_owned()),
+            ("style", "color: red;margin: 0".to_owned()),
+        ]
+    );
+}
+
 /*#[test]
 fn no_block_parser() {
     let md = &mut markdown_that::MarkdownThat::new();
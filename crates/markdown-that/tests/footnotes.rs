@@ -0,0 +1,238 @@
+use markdown_that::MarkdownThat;
+use markdown_that::parser::renderer::{HtmlRenderOptions, MarkdownFormatOptions};
+use markdown_that::plugins::extra::footnotes::{FootnoteItem, FootnoteReference};
+
+fn md() -> MarkdownThat {
+    let mut md = MarkdownThat::new();
+    markdown_that::plugins::cmark::add(&mut md);
+    markdown_that::plugins::extra::footnotes::add(&mut md);
+    md
+}
+
+fn section(items: &[&str]) -> String {
+    let mut result = String::from(concat!(
+        "<hr class=\"footnotes-sep\">\n",
+        "<section class=\"footnotes\">\n",
+        "<ol class=\"footnotes-list\">\n",
+    ));
+    for item in items {
+        result += item;
+    }
+    result + "</ol>\n</section>\n"
+}
+
+#[test]
+fn definitions() {
+    let src = "Here is a footnote reference,[^1] and another.[^longnote]\n\n\
+        [^1]: Here is the footnote.\n\n\
+        [^longnote]: Here's one with multiple blocks.\n\n    \
+        Subsequent paragraphs are indented to show that they\n\
+        belong to the previous footnote.\n";
+    assert_eq!(
+        md().render(src),
+        String::from(concat!(
+            "<p>Here is a footnote reference,<sup class=\"footnote-ref\"><a href=\"#fn1\" id=\"fnref1\">[1]</a></sup> ",
+            "and another.<sup class=\"footnote-ref\"><a href=\"#fn2\" id=\"fnref2\">[2]</a></sup></p>\n",
+        )) + &section(&[
+            "<li id=\"fn1\" class=\"footnote-item\"><p>Here is the footnote. <a href=\"#fnref1\" class=\"footnote-backref\">↩︎</a></p>\n</li>\n",
+            concat!(
+                "<li id=\"fn2\" class=\"footnote-item\"><p>Here's one with multiple blocks.</p>\n",
+                "<p>Subsequent paragraphs are indented to show that they\n",
+                "belong to the previous footnote. <a href=\"#fnref2\" class=\"footnote-backref\">↩︎</a></p>\n</li>\n",
+            ),
+        ])
+    );
+}
+
+#[test]
+fn multiple_references() {
+    assert_eq!(
+        md().render("foo[^1]bar[^1]baz[^1]\n\n[^1]: x\n"),
+        String::from(concat!(
+            "<p>foo<sup class=\"footnote-ref\"><a href=\"#fn1\" id=\"fnref1\">[1]</a></sup>",
+            "bar<sup class=\"footnote-ref\"><a href=\"#fn1\" id=\"fnref1:1\">[1:1]</a></sup>",
+            "baz<sup class=\"footnote-ref\"><a href=\"#fn1\" id=\"fnref1:2\">[1:2]</a></sup></p>\n",
+        )) + &section(&[concat!(
+            "<li id=\"fn1\" class=\"footnote-item\"><p>x ",
+            "<a href=\"#fnref1\" class=\"footnote-backref\">↩︎</a> ",
+            "<a href=\"#fnref1:1\" class=\"footnote-backref\">↩︎</a> ",
+            "<a href=\"#fnref1:2\" class=\"footnote-backref\">↩︎</a></p>\n</li>\n",
+        )])
+    );
+}
+
+#[test]
+fn inline_footnotes() {
+    assert_eq!(
+        md().render("foo^[ *bar* [baz](/url) ]\n"),
+        String::from(
+            "<p>foo<sup class=\"footnote-ref\"><a href=\"#fn1\" id=\"fnref1\">[1]</a></sup></p>\n"
+        ) + &section(&[concat!(
+            "<li id=\"fn1\" class=\"footnote-item\"><p> <em>bar</em> <a href=\"/url\">baz</a>  ",
+            "<a href=\"#fnref1\" class=\"footnote-backref\">↩︎</a></p>\n</li>\n",
+        )])
+    );
+}
+
+#[test]
+fn numbered_by_first_reference() {
+    let src = "[^b] ^[inline [^a]] [^b]\n\n[^a]: a ^[nested]\n[^b]: b\n";
+    let ast = md().parse(src);
+
+    let mut refs = Vec::new();
+    let mut items = Vec::new();
+    ast.walk(|node, _| {
+        if let Some(reference) = node.cast::<FootnoteReference>() {
+            refs.push((reference.label.clone(), reference.id, reference.sub_id));
+        } else if let Some(item) = node.cast::<FootnoteItem>() {
+            items.push((item.label.clone(), item.id));
+        }
+    });
+
+    let label = |s: &str| Some(s.to_owned());
+    assert_eq!(
+        refs,
+        [
+            (label("b"), 0, 0),
+            (None, 1, 0),
+            (label("b"), 0, 1),
+            (label("a"), 2, 0),
+            (None, 3, 0),
+        ]
+    );
+    assert_eq!(
+        items,
+        [(label("b"), 0), (None, 1), (label("a"), 2), (None, 3)]
+    );
+}
+
+#[test]
+fn labels_are_normalized() {
+    let html = md().render("[^Foo] [^FOO]\n\n[^foo]: first\n[^fOO]: second\n");
+    assert!(html.contains("id=\"fnref1:1\""));
+    assert!(html.contains("<p>first <a"));
+    assert!(!html.contains("second"));
+}
+
+#[test]
+fn not_footnotes() {
+    let md = md();
+    // undefined label, spaces and line breaks in labels
+    // (`[^ bar]: x` is a link reference definition then)
+    assert_eq!(
+        md.render("[^foo] [^ bar] [^bar\n]\n\n[^ bar]: x\n"),
+        "<p>[^foo] <a href=\"x\">^ bar</a> [^bar\n]</p>\n"
+    );
+    // unused definitions are removed, so there is no section
+    assert_eq!(md.render("foo\n\n[^1]: bar\n"), "<p>foo</p>\n");
+    // empty label
+    assert_eq!(md.render("[^] ^\n"), "<p>[^] ^</p>\n");
+    // links take precedence
+    assert_eq!(
+        md.render("[^1](/url)\n\n[^1]: foo\n"),
+        "<p><a href=\"/url\">^1</a></p>\n"
+    );
+}
+
+#[test]
+fn definition_interrupts_paragraph() {
+    assert_eq!(
+        md().render("para\n[^1]: note\n\n> [^1]\n"),
+        String::from(concat!(
+            "<p>para</p>\n",
+            "<blockquote>\n",
+            "<p><sup class=\"footnote-ref\"><a href=\"#fn1\" id=\"fnref1\">[1]</a></sup></p>\n",
+            "</blockquote>\n",
+        )) + &section(&[
+            "<li id=\"fn1\" class=\"footnote-item\"><p>note <a href=\"#fnref1\" class=\"footnote-backref\">↩︎</a></p>\n</li>\n",
+        ])
+    );
+}
+
+#[test]
+fn backrefs_without_paragraph() {
+    assert_eq!(
+        md().render("foo[^1]\n\n[^1]:\n        code\n"),
+        String::from(
+            "<p>foo<sup class=\"footnote-ref\"><a href=\"#fn1\" id=\"fnref1\">[1]</a></sup></p>\n"
+        ) + &section(&[concat!(
+            "<li id=\"fn1\" class=\"footnote-item\">\n",
+            "<pre><code>code\n</code></pre>\n",
+            " <a href=\"#fnref1\" class=\"footnote-backref\">↩︎</a>\n",
+            "</li>\n",
+        )])
+    );
+}
+
+#[test]
+fn xhtml() {
+    let html = md()
+        .parse("a[^1]\n\n[^1]: b")
+        .render_with(&HtmlRenderOptions {
+            xhtml: true,
+            ..Default::default()
+        });
+    assert!(html.contains("<hr class=\"footnotes-sep\" />\n"));
+}
+
+#[test]
+fn sourcemaps() {
+    let src = "foo [^1] ^[bar]\n\n- [^1]: baz\n  qux\n";
+    let ast = md().parse(src);
+    let mut spans = Vec::new();
+    ast.walk(|node, _| {
+        if node.is::<FootnoteReference>() || node.is::<FootnoteItem>() {
+            let (start, end) = node.srcmap.unwrap().get_byte_offsets();
+            spans.push(&src[start..end]);
+        }
+    });
+    assert_eq!(spans, ["[^1]", "^[bar]", "[^1]: baz\n  qux", "^[bar]"]);
+}
+
+#[test]
+fn markdown_and_text() {
+    let ast = md().parse("foo[^a] bar^[baz]\n\n[^a]: one\n\n    two\n");
+    assert_eq!(
+        ast.render_markdown(),
+        "foo[^a] bar[^2]\n\n[^a]: one\n\n    two\n\n[^2]: baz\n"
+    );
+    assert_eq!(
+        ast.render_text(),
+        "foo[1] bar[2]\n\n[1] one\n\n    two\n\n[2] baz\n"
+    );
+}
+
+#[test]
+fn inline_footnotes_round_trip() {
+    let md = md();
+    let ast = md.parse("a[^2] b^[inline] c^[more]\n\n[^2]: y\n[^3]: unused\n");
+    let markdown = ast.render_markdown();
+    assert_eq!(
+        markdown,
+        "a[^2] b[^4] c[^5]\n\n[^2]: y\n\n[^4]: inline\n\n[^5]: more\n"
+    );
+    assert_eq!(md.parse(&markdown).render(), ast.render());
+}
+
+#[test]
+fn formatting() {
+    let md = md();
+    let format = |src: &str| {
+        md.parse(src)
+            .format_markdown(&MarkdownFormatOptions::default())
+    };
+
+    // definitions are kept where they are written
+    let src = "Intro[^a].\n\n[^a]: Note\n\nlast\n";
+    assert_eq!(format(src), src);
+
+    // definitions removed from containers and inline footnotes are written at the end
+    for src in [
+        "Intro[^a] and ^[inline].\n\n> [^a]: Note\n\n* last\n\n[^b]: unused\n",
+        "a[^2] b^[x] c[^3]\n\n[^2]: y\n\n    z\n[^3]: w\n",
+    ] {
+        let formatted = format(src);
+        assert_eq!(format(&formatted), formatted);
+        assert_eq!(md.parse(&formatted).render(), md.parse(src).render());
+    }
+}
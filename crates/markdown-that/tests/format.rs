@@ -0,0 +1,215 @@
+use markdown_that::MarkdownThat;
+use markdown_that::parser::renderer::MarkdownFormatOptions;
+
+fn md() -> MarkdownThat {
+    let mut md = MarkdownThat::new();
+    markdown_that::plugins::cmark::add(&mut md);
+    markdown_that::plugins::extra::tables::add(&mut md);
+    markdown_that::plugins::extra::strikethrough::add(&mut md);
+    md
+}
+
+fn format(input: &str) -> String {
+    md().parse(input)
+        .format_markdown(&MarkdownFormatOptions::default())
+}
+
+fn format_with(input: &str, options: MarkdownFormatOptions) -> String {
+    md().parse(input).format_markdown(&options)
+}
+
+#[test]
+fn formatted_document_is_unchanged() {
+    let input = "# Title\n\nSome *text* with [a link][ref].\n\n- one\n- two\n\n1. first\n2. second\n\n```rust\nfn main() {}\n```\n\n[ref]: https://example.com\n";
+    assert_eq!(format(input), input);
+}
+
+#[test]
+fn bullet_markers() {
+    assert_eq!(format("* one\n* two\n"), "- one\n- two\n");
+    assert_eq!(format("+ one\n\n  - nested\n"), "- one\n\n  - nested\n");
+
+    let options = MarkdownFormatOptions {
+        bullet_marker: None,
+        ..Default::default()
+    };
+    assert_eq!(format_with("* one\n+ two\n", options), "* one\n+ two\n");
+}
+
+#[test]
+fn adjacent_lists_are_not_merged() {
+    assert_eq!(format("* one\n\n+ two\n"), "- one\n\n* two\n");
+}
+
+#[test]
+fn ordered_lists_are_renumbered() {
+    assert_eq!(format("1. a\n1. b\n1. c\n"), "1. a\n2. b\n3. c\n");
+    assert_eq!(format("3) a\n7) b\n"), "3) a\n4) b\n");
+}
+
+#[test]
+fn setext_headings() {
+    assert_eq!(format("Title\n=====\n\nSub\n---\n"), "# Title\n\n## Sub\n");
+    assert_eq!(format("Multi\nline\n===\n"), "# Multi line\n");
+    // trailing `#` isn't taken as closing sequence
+    assert_eq!(format("C #\n===\n"), "# C \\#\n");
+
+    let options = MarkdownFormatOptions {
+        atx_headings: false,
+        ..Default::default()
+    };
+    assert_eq!(format_with("Title\n=\n", options), "Title\n=====\n");
+}
+
+#[test]
+fn tables_are_aligned() {
+    assert_eq!(
+        format("| a | b |\n|-|:-:|\n| long cell | x |\n"),
+        "| a         |  b  |\n| --------- | :-: |\n| long cell |  x  |\n"
+    );
+}
+
+#[test]
+fn fences() {
+    assert_eq!(format("~~~~ rust\ncode\n~~~~\n"), "```rust\ncode\n```\n");
+    assert_eq!(
+        format("~~~\n```\ninner\n```\n~~~\n"),
+        "````\n```\ninner\n```\n````\n"
+    );
+    // backticks aren't allowed in info string of a backtick fence
+    assert_eq!(format("~~~~ a`b\ncode\n~~~~\n"), "~~~a`b\ncode\n~~~\n");
+
+    let options = MarkdownFormatOptions {
+        fence_marker: Some('~'),
+        ..Default::default()
+    };
+    assert_eq!(format_with("```js\nx\n```\n", options), "~~~js\nx\n~~~\n");
+}
+
+#[test]
+fn paragraphs_are_wrapped() {
+    let options = MarkdownFormatOptions {
+        wrap_width: Some(20),
+        ..Default::default()
+    };
+    assert_eq!(
+        format_with(
+            "The quick brown fox\njumps over\nthe lazy dog.\n\n> quoted text that is long\n",
+            options
+        ),
+        "The quick brown fox\njumps over the lazy\ndog.\n\n> quoted text that\n> is long\n"
+    );
+}
+
+#[test]
+fn wrapping_keeps_syntax() {
+    let options = MarkdownFormatOptions {
+        wrap_width: Some(10),
+        ..Default::default()
+    };
+    let input = "foo [a link](http://example.com/long) `code span`\\\nhard - 1. #\n";
+    assert_eq!(
+        format_with(input, options),
+        "foo\n[a link](http://example.com/long)\n`code span`\\\nhard - 1. #\n"
+    );
+}
+
+#[test]
+fn blank_lines_are_kept() {
+    assert_eq!(
+        format("para\n\n\n\n* item\n\n\npara"),
+        "para\n\n\n\n- item\n\n\npara"
+    );
+}
+
+#[test]
+fn unchanged_syntax_is_kept() {
+    let input = "# Heading #\n\n* * *\n\n[ref]:   /url   'title'\n\nSee [ref] and ![img][ref].\n";
+    assert_eq!(format(input), input);
+
+    // only the list is rewritten, links inside of it are kept as written
+    assert_eq!(
+        format("* see [ref]\n* and [text][ref]\n\n[ref]: /url\n"),
+        "- see [ref]\n- and [text][ref]\n\n[ref]: /url\n"
+    );
+}
+
+#[test]
+fn rewritten_block_keeps_following_block_separate() {
+    assert_eq!(format("-    foo\n\n  bar\n"), "- foo\n\nbar\n");
+}
+
+#[test]
+fn dedented_block_keeps_following_blocks_at_their_column() {
+    let input = "*   a\n\n  - c\n\n    d\n";
+    let output = format(input);
+    assert_eq!(md().parse(&output).render(), md().parse(input).render());
+
+    let input = "*   a\n\n1.  f\n\n   - c\n  > e\n";
+    let output = format(input);
+    assert_eq!(md().parse(&output).render(), md().parse(input).render());
+}
+
+#[test]
+fn indentation_of_first_block_is_kept() {
+    assert_eq!(format("   - c\n\n  ***\n"), "   - c\n\n  ***\n");
+}
+
+#[test]
+fn highlighted_fence_is_unchanged() {
+    let mut md = md();
+    markdown_that::plugins::extra::add(&mut md);
+    let input = "```rust\nfn main() {}\n```\n";
+    assert_eq!(
+        md.parse(input)
+            .format_markdown(&MarkdownFormatOptions::default()),
+        input
+    );
+
+    let input = "~~~rust\nfn main() {}\n~~~\n\n* text\n";
+    let options = MarkdownFormatOptions {
+        fence_marker: None,
+        bullet_marker: None,
+        ..Default::default()
+    };
+    assert_eq!(md.parse(input).format_markdown(&options), input);
+}
+
+#[test]
+fn blocks_without_markdown_syntax_are_copied() {
+    use markdown_that::parser::core::CoreRule;
+    use markdown_that::parser::renderer::Renderer;
+    use markdown_that::plugins::cmark::block::paragraph::Paragraph;
+    use markdown_that::{Node, NodeValue};
+
+    #[derive(Debug)]
+    struct Note;
+    impl NodeValue for Note {
+        fn render(&self, node: &Node, fmt: &mut dyn Renderer) {
+            fmt.open("aside", &node.attrs);
+            fmt.contents(&node.children);
+            fmt.close("aside");
+        }
+    }
+
+    // turns paragraphs into notes, keeping their source positions
+    struct NoteRule;
+    impl CoreRule for NoteRule {
+        fn run(root: &mut Node, _: &MarkdownThat) {
+            for node in root.children.iter_mut() {
+                if node.is::<Paragraph>() {
+                    node.replace(Note);
+                }
+            }
+        }
+    }
+
+    let mut md = md();
+    md.add_rule::<NoteRule>();
+    let input = "*   list\n\nSome *text*\n__here__.\n";
+    assert_eq!(
+        md.parse(input)
+            .format_markdown(&MarkdownFormatOptions::default()),
+        "- list\n\nSome *text*\n__here__.\n"
+    );
+}
@@ -0,0 +1,109 @@
+fn run(input: &str, output: &str) {
+    let md = &mut markdown_that::MarkdownThat::new();
+    markdown_that::plugins::cmark::add(md);
+    markdown_that::plugins::html::add(md);
+    markdown_that::plugins::extra::strikethrough::add(md);
+    markdown_that::plugins::extra::tables::add(md);
+    let node = md.parse(input);
+    assert_eq!(node.render_gemtext(), output);
+}
+
+#[test]
+fn paragraphs() {
+    run(
+        "Some *emph*, ~~strike~~ and `code`\nwrapped  \nbroken\n\nnext",
+        "Some emph, strike and code wrapped\nbroken\n\nnext\n",
+    );
+}
+
+#[test]
+fn headings() {
+    run(
+        "# One\n\n## Two\n\n#### Four\n\nSetext\n---",
+        "# One\n\n## Two\n\n### Four\n\n## Setext\n",
+    );
+}
+
+#[test]
+fn links_after_paragraph() {
+    run(
+        "See [the docs](http://x.com/docs) and <http://foo.bar>.\n\n![a *cat*](cat.png)\n\nend",
+        "See the docs and http://foo.bar.\n=> http://x.com/docs the docs\n=> http://foo.bar\n\n\
+         a cat\n=> cat.png a cat\n\nend\n",
+    );
+}
+
+#[test]
+fn links_in_headings_and_items() {
+    run(
+        "# [Title](/t)\n\n- [a](/a)\n- b",
+        "# Title\n=> /t Title\n\n* a\n=> /a a\n* b\n",
+    );
+}
+
+#[test]
+fn lists() {
+    run(
+        "- one\n- two\n  - nested\n\n3. x\n\n   para\n4. y",
+        "* one\n* two\n* nested\n\n* 3. x\n\npara\n\n* 4. y\n",
+    );
+}
+
+#[test]
+fn blockquotes() {
+    run(
+        "> quote\n> line\n>\n> - item\n\n***",
+        "> quote line\n\n> * item\n\n---\n",
+    );
+}
+
+#[test]
+fn preformatted() {
+    run(
+        "```rust title=\"main.rs\"\nfn main() {}\n```\n\n    indented",
+        "```rust title=\"main.rs\"\nfn main() {}\n```\n\n```\nindented\n```\n",
+    );
+}
+
+#[test]
+fn tables() {
+    run(
+        "| a | bbb | c |\n|---|:-:|--:|\n| 1 | 2 | [30](/x) |",
+        "```\na | bbb |  c\n--+-----+---\n1 |  2  | 30\n```\n",
+    );
+}
+
+#[test]
+fn line_types_in_text() {
+    run(
+        "\\# not heading\n\n\\=> not link\n\n\\* not item\n\n\\> not quote",
+        " # not heading\n\n => not link\n\n * not item\n\n > not quote\n",
+    );
+}
+
+#[test]
+fn html_is_dropped() {
+    run("<div>\nfoo\n</div>\n\nbar <b>baz</b>", "bar baz\n");
+}
+
+#[cfg(feature = "syntect")]
+#[test]
+fn syntax_highlighting() {
+    let md = &mut markdown_that::MarkdownThat::new();
+    markdown_that::plugins::cmark::add(md);
+    markdown_that::plugins::extra::syntect::add(md);
+    let node = md.parse("```rust\nlet x = \"<a>\";\n```");
+    assert_eq!(node.render_gemtext(), "```rust\nlet x = \"<a>\";\n```\n");
+}
+
+#[test]
+fn footnotes() {
+    let md = &mut markdown_that::MarkdownThat::new();
+    markdown_that::plugins::cmark::add(md);
+    markdown_that::plugins::extra::footnotes::add(md);
+    let node = md.parse("foo[^a] bar^[baz] x[^a]\n\n[^a]: one\n\n    two\n");
+    assert_eq!(
+        node.render_gemtext(),
+        "foo[1] bar[2] x[1]\n\n* 1. one\n\ntwo\n\n* 2. baz\n"
+    );
+}
@@ -0,0 +1,208 @@
+use markdown_that::parser::diagnostics::Diagnostics;
+use markdown_that::parser::incremental::TextEdit;
+use markdown_that::{MarkdownThat, Node};
+
+fn md() -> MarkdownThat {
+    let mut md = MarkdownThat::new();
+    markdown_that::plugins::cmark::add(&mut md);
+    markdown_that::plugins::html::add(&mut md);
+    markdown_that::plugins::extra::tables::add(&mut md);
+    markdown_that::plugins::extra::strikethrough::add(&mut md);
+    markdown_that::plugins::extra::typographer::add(&mut md);
+    markdown_that::plugins::sourcepos::add(&mut md);
+    md
+}
+
+fn dump(node: &Node) -> Vec<String> {
+    let mut result = vec![];
+    node.walk(|node, depth| {
+        result.push(format!("{depth} {} {:?}", node.name(), node.srcmap));
+    });
+    result
+}
+
+fn diagnostics(node: &Node) -> Vec<String> {
+    Diagnostics::of(node)
+        .iter()
+        .map(|d| format!("{d} {:?}", d.srcmap))
+        .collect()
+}
+
+// reparse must give the same result as parsing the new source from scratch
+fn check(md: &MarkdownThat, source: &str, edit: TextEdit) {
+    let mut new_source = source.to_owned();
+    new_source.replace_range(edit.range.clone(), &edit.text);
+
+    let expected = md.parse(&new_source);
+    let actual = md.reparse(md.parse(source), &edit);
+    let context = format!("{source:?} with {edit:?}");
+
+    assert_eq!(actual.render(), expected.render(), "{context}");
+    assert_eq!(dump(&actual), dump(&expected), "{context}");
+    assert_eq!(actual.collect_text(), expected.collect_text(), "{context}");
+    assert_eq!(diagnostics(&actual), diagnostics(&expected), "{context}");
+}
+
+fn run(source: &str, range: std::ops::Range<usize>, text: &str) {
+    check(&md(), source, TextEdit::new(range, text));
+}
+
+#[test]
+fn typing_in_paragraph() {
+    run("# title\n\nfoo\n\nbar\n", 12..12, "d");
+    run("# title\n\nfoo\n\nbar\n", 9..10, "");
+    run("# title\n\nfoo\n\nbar\n", 0..0, "x");
+}
+
+#[test]
+fn fences() {
+    let source = "para\n\ncode\n\n# heading\n\n- item\n";
+    // opening fence turns the rest of the document into code
+    run(source, 6..6, "```\n");
+    // closing it again
+    run("para\n\n```\ncode\n\n# heading\n", 10..10, "```\n");
+    run("para\n\n```\ncode\n```\n\n# heading\n", 15..19, "");
+}
+
+#[test]
+fn blank_lines() {
+    // removing a blank line merges paragraphs
+    run("foo\n\nbar\n\nbaz\n", 4..5, "");
+    // adding one splits them
+    run("foo\nbar\nbaz\n", 4..4, "\n");
+    run("- a\n\n- b\n\n- c\n", 4..5, "");
+}
+
+#[test]
+fn lazy_continuation() {
+    run("foo\n# bar\n\nbaz\n", 4..6, "");
+    run("> foo\n\nbar\n", 6..7, "");
+}
+
+#[test]
+fn setext_underline() {
+    run("title\n\n---\n\ntext\n", 6..7, "");
+    run("title\ntext\n", 6..10, "===");
+}
+
+#[test]
+fn table_header() {
+    run("a | b\n\nx\n", 6..7, "-|-\n");
+}
+
+#[test]
+fn reference_definitions() {
+    let source = "[foo]\n\ntext\n\n[foo]: /url\n";
+    // definition removed
+    run(source, 13..18, "");
+    // definition changed
+    run(source, 20..23, "/other");
+    // definition added before the existing one
+    run(source, 6..6, "\n[foo]: /first\n");
+    // edit near a definition
+    run(source, 7..11, "more text");
+}
+
+#[test]
+fn footnotes() {
+    let md = &mut md();
+    markdown_that::plugins::extra::footnotes::add(md);
+    // unused definition is removed, and it's far from the edit,
+    // so it isn't in the reparsed blocks
+    let source = "foo\n\n[^a]: note\n\none\n\ntwo\n\nthree\n";
+    // first reference added to a later paragraph
+    check(md, source, TextEdit::new(32..32, "[^a]"));
+    // inline footnote added
+    check(md, source, TextEdit::new(32..32, "^[inline]"));
+    // reference removed
+    check(
+        md,
+        "foo[^a]\n\n[^a]: note\n\none\n",
+        TextEdit::new(3..7, ""),
+    );
+}
+
+#[test]
+fn document_rules() {
+    use markdown_that::parser::core::CoreRule;
+
+    // writes the number of blocks into the first one
+    struct CountBlocks;
+    impl CoreRule for CountBlocks {
+        fn run(root: &mut Node, _: &MarkdownThat) {
+            let count = root.children.len().to_string();
+            if let Some(first) = root.children.first_mut() {
+                first.attrs.retain(|(name, _)| *name != "data-blocks");
+                first.attrs.push(("data-blocks", count));
+            }
+        }
+    }
+
+    let md = &mut md();
+    md.add_rule::<CountBlocks>();
+    md.mark_document_rule::<CountBlocks>();
+    check(md, "foo\n\nbar\n\nbaz\n", TextEdit::new(14..14, "\nnew\n"));
+}
+
+#[test]
+fn edit_at_end() {
+    run("foo\n\nbar", 8..8, "\n\nbaz");
+    run("foo\n\nbar\n", 3..9, "");
+    run("", 0..0, "# foo\n");
+    run("foo", 0..3, "");
+}
+
+#[test]
+fn source_positions_are_shifted() {
+    let md = md();
+    let ast = md.reparse(md.parse("foo\n\nbar\n"), &TextEdit::new(0..0, "line\n\n"));
+    assert_eq!(
+        ast.render(),
+        "<p data-sourcepos=\"1:1-1:4\">line</p>\n\
+         <p data-sourcepos=\"3:1-3:3\">foo</p>\n\
+         <p data-sourcepos=\"5:1-5:3\">bar</p>\n"
+    );
+    assert_eq!(ast.children[2].srcmap.unwrap().get_byte_offsets(), (11, 14));
+}
+
+#[test]
+#[cfg(feature = "linkify")]
+fn linkify() {
+    let mut md = md();
+    markdown_that::plugins::extra::linkify::add(&mut md);
+    check(
+        &md,
+        "see http://example.com\n\nfoo\n",
+        TextEdit::new(4..6, ""),
+    );
+    check(
+        &md,
+        "foo\n\nhttp://example.com\n",
+        TextEdit::new(0..3, "see https://example.org"),
+    );
+}
+
+#[test]
+fn every_single_char_edit() {
+    let source = "# Title\n\nSome *text*\nwith `code` and [link](/url).\n\n\
+                  > quote\nlazy\n\n- one\n- two\n\n  nested\n\n1. first\n\n\
+                  ```rust\nfn main() {}\n```\n\n    indented\n\n\
+                  | a | b |\n|---|---|\n| c | d |\n\n<div>\nhtml\n</div>\n\n\
+                  Setext\n---\n\n***\n\nend (c) -- text\n";
+    let md = md();
+
+    for (pos, ch) in source.char_indices() {
+        check(&md, source, TextEdit::new(pos..pos + ch.len_utf8(), ""));
+        for text in ["\n", " ", "#", "-", "`", ">", "=", "|", "x"] {
+            check(&md, source, TextEdit::new(pos..pos, text));
+        }
+    }
+}
+
+#[test]
+fn table_diagnostics() {
+    let source = "| a | b |\n|---|---|\n| c |\n\npara\n\n| a |\n|---|\n| b | c |\n";
+    run(source, 0..0, "x\n\n");
+    run(source, 24..24, "more ");
+    run(source, 14..14, " | d");
+}
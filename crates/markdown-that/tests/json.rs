@@ -0,0 +1,175 @@
+#![cfg(feature = "serde")]
+use markdown_that::parser::json::NodeRegistry;
+use markdown_that::{MarkdownThat, Node, NodeValue, Renderer};
+
+fn md() -> MarkdownThat {
+    let mut md = MarkdownThat::new();
+    markdown_that::plugins::cmark::add(&mut md);
+    markdown_that::plugins::html::add(&mut md);
+    markdown_that::plugins::extra::add(&mut md);
+    md
+}
+
+#[test]
+fn roundtrip() {
+    let input = r#"# Heading
+
+Paragraph with *emph*, **strong**, ~~strike~~, `code`, \* &amp; [link](/url "title"),
+![image](/img.png) <http://example.org> www.example.com <b>html</b>  
+hardbreak
+
+> - item
+> - item
+>
+> 3. ordered
+
+***
+
+    indented
+
+```rust
+fn main() {}
+```
+
+<div>
+block
+</div>
+
+| a | b |
+|:-|-:|
+| 1 | 2 |
+
+[ref]: /url
+"#;
+    let md = &md();
+    let registry = NodeRegistry::new();
+
+    let node = md.parse(input);
+    let json = registry.to_json(&node).unwrap();
+    let string = serde_json::to_string(&json).unwrap();
+    let imported = registry
+        .from_json(&serde_json::from_str(&string).unwrap())
+        .unwrap();
+
+    assert_eq!(imported.render(), node.render());
+    assert_eq!(registry.to_json(&imported).unwrap(), json);
+}
+
+#[test]
+fn custom_lang_prefix() {
+    let md = &mut MarkdownThat::new();
+    markdown_that::plugins::cmark::add(md);
+    markdown_that::plugins::cmark::block::fence::set_lang_prefix(md, "lang-");
+    let registry = NodeRegistry::new();
+
+    let node = md.parse("```rust\nfn main() {}\n```\n");
+    let json = registry.to_json(&node).unwrap();
+    let imported = registry.from_json(&json).unwrap();
+
+    assert!(imported.render().contains(r#"class="lang-rust""#));
+    assert_eq!(imported.render(), node.render());
+    assert_eq!(registry.to_json(&imported).unwrap(), json);
+}
+
+#[test]
+fn footnotes() {
+    let md = &mut md();
+    markdown_that::plugins::extra::footnotes::add(md);
+    let registry = NodeRegistry::new();
+
+    let node = md.parse("foo[^1] bar^[*baz*] [^1]\n\n[^1]: note\n");
+    let json = registry.to_json(&node).unwrap();
+    let imported = registry.from_json(&json).unwrap();
+
+    assert_eq!(imported.render(), node.render());
+    assert_eq!(registry.to_json(&imported).unwrap(), json);
+}
+
+#[test]
+fn shape() {
+    let md = &md();
+    let mut node = md.parse("[foo](/bar)");
+    node.children[0].attrs.push(("class", "x".into()));
+    let json = NodeRegistry::new().to_json(&node).unwrap();
+
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "type": "Root",
+            "srcmap": [0, 11],
+            "value": { "content": "[foo](/bar)" },
+            "children": [{
+                "type": "Paragraph",
+                "srcmap": [0, 11],
+                "attrs": [["class", "x"]],
+                "children": [{
+                    "type": "Link",
+                    "srcmap": [0, 11],
+                    "value": { "url": "/bar", "title": null },
+                    "children": [{
+                        "type": "Text",
+                        "srcmap": [1, 4],
+                        "value": { "content": "foo" },
+                    }],
+                }],
+            }],
+        })
+    );
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+struct Custom {
+    value: u32,
+}
+
+impl NodeValue for Custom {
+    fn render(&self, _: &Node, fmt: &mut dyn Renderer) {
+        fmt.text(&self.value.to_string());
+    }
+}
+
+#[test]
+fn custom_nodes() {
+    let mut node = Node::new(Custom { value: 42 });
+    node.children.push(Node::new(Custom { value: 43 }));
+
+    let mut registry = NodeRegistry::new();
+    assert!(registry.to_json(&node).is_err());
+
+    registry.add::<Custom>("Custom");
+    let json = registry.to_json(&node).unwrap();
+    let imported = registry.from_json(&json).unwrap();
+    assert_eq!(imported.cast::<Custom>().unwrap().value, 42);
+    assert_eq!(imported.children[0].cast::<Custom>().unwrap().value, 43);
+
+    registry.remove::<Custom>();
+    assert!(!registry.has::<Custom>());
+    assert!(registry.from_json(&json).is_err());
+}
+
+#[test]
+fn unknown_attrs() {
+    let md = &md();
+    let mut node = md.parse("foo");
+    node.children[0].attrs.push(("data-foo", "x".into()));
+
+    let mut registry = NodeRegistry::new();
+    let json = registry.to_json(&node).unwrap();
+    assert!(registry.from_json(&json).is_err());
+
+    registry.add_attr("data-foo");
+    let imported = registry.from_json(&json).unwrap();
+    assert_eq!(
+        imported.children[0].attrs,
+        vec![("data-foo", "x".to_owned())]
+    );
+}
+
+#[test]
+fn unknown_static_strings() {
+    let md = &md();
+    let registry = NodeRegistry::new();
+    let mut json = registry.to_json(&md.parse("&amp;")).unwrap();
+    json["children"][0]["children"][0]["value"]["info"] = "custom".into();
+    assert!(registry.from_json(&json).is_err());
+}
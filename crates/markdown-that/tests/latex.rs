@@ -0,0 +1,149 @@
+use markdown_that::parser::renderer::LatexRenderOptions;
+
+fn run_with(input: &str, options: LatexRenderOptions, output: &str) {
+    let md = &mut markdown_that::MarkdownThat::new();
+    markdown_that::plugins::cmark::add(md);
+    markdown_that::plugins::html::add(md);
+    markdown_that::plugins::extra::strikethrough::add(md);
+    markdown_that::plugins::extra::tables::add(md);
+    let node = md.parse(input);
+    assert_eq!(node.render_latex(&options), output);
+}
+
+fn run(input: &str, output: &str) {
+    run_with(input, LatexRenderOptions::default(), output);
+}
+
+#[test]
+fn headings() {
+    run(
+        "# one\n\n## two\n\n### three\n\n#### four\n\nfive\n-----",
+        "\\section{one}\n\n\\subsection{two}\n\n\\subsubsection{three}\n\n\\paragraph{four}\n\n\\subsection{five}\n",
+    );
+}
+
+#[test]
+fn inline_markup() {
+    run(
+        "Some *emph*, **strong**, ~~strike~~ and `code`",
+        "Some \\emph{emph}, \\textbf{strong}, \\sout{strike} and \\texttt{code}\n",
+    );
+}
+
+#[test]
+fn escaping() {
+    run(
+        "50% & $5 #1 a_b {x} ~ ^ \\\\ <>|",
+        "50\\% \\& \\$5 \\#1 a\\_b \\{x\\} \\textasciitilde{} \\textasciicircum{} \\textbackslash{} \\textless{}\\textgreater{}\\textbar{}\n",
+    );
+}
+
+#[test]
+fn line_breaks() {
+    run("foo\nbar  \n[baz]", "foo\nbar\\\\\n{}[baz]\n");
+}
+
+#[test]
+fn links_and_images() {
+    run(
+        "[link *text*](http://x.com/a#b%20c) ![alt](image.png) <http://foo.bar>",
+        "\\href{http://x.com/a\\#b\\%20c}{link \\emph{text}} \\includegraphics{image.png} \\href{http://foo.bar}{http://foo.bar}\n",
+    );
+}
+
+#[test]
+fn image_url_is_escaped() {
+    run("![alt](a%20b.png)", "\\includegraphics{a\\%20b.png}\n");
+}
+
+#[test]
+fn code_verbatim() {
+    run(
+        "```rust\nfn main() {}\n```\n\n    indented\n\nand `a_b`",
+        "\\begin{verbatim}\nfn main() {}\n\\end{verbatim}\n\n\\begin{verbatim}\nindented\n\\end{verbatim}\n\nand \\texttt{a\\_b}\n",
+    );
+}
+
+#[test]
+fn code_listings() {
+    run_with(
+        "``` rust title\nfn main() {}\n```\n\n    indented",
+        LatexRenderOptions { listings: true },
+        "\\begin{lstlisting}[language=rust]\nfn main() {}\n\\end{lstlisting}\n\n\\begin{lstlisting}\nindented\n\\end{lstlisting}\n",
+    );
+}
+
+#[test]
+fn code_listings_language_is_sanitized() {
+    run_with(
+        "```c++],frame=single%\ncode\n```\n\n```%]\ncode\n```",
+        LatexRenderOptions { listings: true },
+        "\\begin{lstlisting}[language=c++framesingle]\ncode\n\\end{lstlisting}\n\n\\begin{lstlisting}\ncode\n\\end{lstlisting}\n",
+    );
+}
+
+#[test]
+fn lists() {
+    run(
+        "- one\n- [two]\n  1. a\n  2. b",
+        "\\begin{itemize}\n\\item one\n\\item {}[two]\n\\begin{enumerate}\n\\item a\n\\item b\n\\end{enumerate}\n\\end{itemize}\n",
+    );
+}
+
+#[test]
+fn loose_ordered_list() {
+    run(
+        "3. x\n\n   para\n4. y",
+        "\\begin{enumerate}\n\\setcounter{enumi}{2}\n\\item x\n\npara\n\\item y\n\\end{enumerate}\n",
+    );
+}
+
+#[test]
+fn blockquotes_and_hr() {
+    run(
+        "> quote\n>\n> more\n\n---",
+        "\\begin{quote}\nquote\n\nmore\n\\end{quote}\n\n\\noindent\\rule{\\linewidth}{0.4pt}\n",
+    );
+}
+
+#[test]
+fn tables() {
+    run(
+        "| a | b | c | d |\n|:-|-:|:-:|-|\n| *x* | y\\|z | w |",
+        "\\begin{tabular}{lrcl}\n\\hline\n\\textbf{a} & \\textbf{b} & \\textbf{c} & \\textbf{d} \\\\\n\\hline\n\\emph{x} & y\\textbar{}z & w &  \\\\\n\\hline\n\\end{tabular}\n",
+    );
+}
+
+#[test]
+fn html_is_dropped() {
+    run("<div>\nfoo\n</div>\n\nbar <b>baz</b>", "bar baz\n");
+}
+
+#[cfg(feature = "syntect")]
+#[test]
+fn syntax_highlighting() {
+    let md = &mut markdown_that::MarkdownThat::new();
+    markdown_that::plugins::cmark::add(md);
+    markdown_that::plugins::extra::syntect::add(md);
+    let node = md.parse("```rust\nlet x = \"<a>\";\n```");
+    assert_eq!(
+        node.render_latex(&LatexRenderOptions::default()),
+        "\\begin{verbatim}\nlet x = \"<a>\";\n\\end{verbatim}\n"
+    );
+    assert_eq!(
+        node.render_latex(&LatexRenderOptions { listings: true }),
+        "\\begin{lstlisting}[language=rust]\nlet x = \"<a>\";\n\\end{lstlisting}\n"
+    );
+}
+
+#[test]
+fn task_lists() {
+    let md = &mut markdown_that::MarkdownThat::new();
+    markdown_that::plugins::cmark::add(md);
+    markdown_that::plugins::gfm::tasklist::add(md);
+    let node = md.parse("- [x] done\n- [ ] todo\n- plain\n\nafter");
+    assert_eq!(
+        node.render_latex(&LatexRenderOptions::default()),
+        "\\begin{itemize}\n\\item {}[x] done\n\\item {}[ ] todo\n\\item plain\n\\end{itemize}\n\nafter\n"
+    );
+}
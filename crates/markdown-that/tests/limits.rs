@@ -0,0 +1,218 @@
+use markdown_that::MarkdownThat;
+use markdown_that::parser::core::Root;
+use markdown_that::parser::inline::Text;
+use markdown_that::parser::limits::ParseError;
+use markdown_that::plugins::cmark::inline::emphasis::Em;
+use std::time::Duration;
+
+fn md() -> MarkdownThat {
+    let mut md = MarkdownThat::new();
+    markdown_that::plugins::cmark::add(&mut md);
+    md
+}
+
+#[test]
+fn default_limits() {
+    let md = md();
+    let node = md.try_parse("# hello\n\n> *world*\n").unwrap();
+    assert_eq!(
+        node.render(),
+        "<h1>hello</h1>\n<blockquote>\n<p><em>world</em></p>\n</blockquote>\n"
+    );
+}
+
+#[test]
+fn max_source_bytes() {
+    let mut md = md();
+    md.limits.max_source_bytes = Some(10);
+
+    assert!(md.try_parse("# 10 bytes").is_ok());
+    assert_eq!(
+        md.try_parse("# 11 bytes!").unwrap_err(),
+        ParseError::SourceTooLarge {
+            size: 11,
+            limit: 10
+        }
+    );
+    assert_eq!(
+        md.parse("# <b>bytes</b>").render(),
+        "# &lt;b&gt;bytes&lt;/b&gt;"
+    );
+}
+
+#[test]
+fn max_nodes() {
+    let mut md = md();
+    md.limits.max_nodes = Some(2);
+
+    let src = "# a\n\n# b\n\n# c\n\n# d\n";
+    assert_eq!(md.parse(src).render(), "<h1>a</h1>\n<h1>b</h1>\n# c\n\n# d");
+    assert_eq!(
+        md.try_parse(src).unwrap_err(),
+        ParseError::TooManyNodes { limit: 2 }
+    );
+
+    md.limits.max_nodes = Some(4);
+    assert!(md.try_parse(src).is_ok());
+
+    // inline nodes are counted too
+    md.limits.max_nodes = Some(3);
+    assert_eq!(
+        md.parse("*a* *b* *c*").render(),
+        "<p><em>a</em> *b* *c*</p>\n"
+    );
+}
+
+#[test]
+fn max_nodes_is_not_exceeded() {
+    let mut md = md();
+    markdown_that::plugins::html::add(&mut md);
+    let src = "# a *b*\n\nc `d` <e>\n\n> f [g](h)\n\n".repeat(100);
+
+    for max_nodes in [1, 2, 3, 10, 99, 500] {
+        md.limits.max_nodes = Some(max_nodes);
+        let node = md.parse(&src);
+        let mut count = 0;
+        node.walk(|node, _| {
+            // text between nodes and nodes created by rules internally
+            if !node.is::<Text>() && !node.is::<Em>() && !node.is::<Root>() {
+                count += 1;
+            }
+        });
+        assert!(count <= max_nodes, "{count} nodes, limit is {max_nodes}");
+    }
+}
+
+#[test]
+fn max_nesting_blocks() {
+    let mut md = md();
+    md.limits.max_nesting = 3;
+
+    assert_eq!(
+        md.parse("> > > a\n> > > b\n\nc").render(),
+        "<blockquote>\n<blockquote>\n&gt; a\n&gt; b\n</blockquote>\n</blockquote>\n<p>c</p>\n"
+    );
+    assert_eq!(
+        md.try_parse("- a\n  - b\n    - c\n").unwrap_err(),
+        ParseError::TooDeep { limit: 3 }
+    );
+    // text in a list item is at depth 4: list, item, paragraph, text
+    assert!(md.try_parse("> a\n\n- b\n").is_err());
+    assert!(md.try_parse("> a\n\n# *b*\n").is_ok());
+}
+
+#[test]
+fn max_nesting_inline() {
+    let mut md = md();
+    md.limits.max_nesting = 4;
+
+    assert_eq!(
+        md.parse("[![![a](b)](c)](d)").render(),
+        "<p><a href=\"d\"><img src=\"c\" alt=\"![a](b)\"></a></p>\n"
+    );
+    assert_eq!(
+        md.try_parse("[![![a](b)](c)](d)").unwrap_err(),
+        ParseError::TooDeep { limit: 4 }
+    );
+}
+
+#[test]
+fn deeply_nested_input() {
+    let md = md();
+    let src = format!("{}a", "> ".repeat(10000));
+    assert_eq!(
+        md.try_parse(&src).unwrap_err(),
+        ParseError::TooDeep { limit: 100 }
+    );
+}
+
+#[test]
+fn time_budget() {
+    let mut md = md();
+    md.limits.time_budget = Some(Duration::ZERO);
+
+    assert_eq!(md.parse("# a\n\n*b*").render(), "# a\n\n*b*");
+    assert_eq!(
+        md.try_parse("# a").unwrap_err(),
+        ParseError::TimedOut {
+            budget: Duration::ZERO
+        }
+    );
+
+    md.limits.time_budget = Some(Duration::from_secs(60));
+    assert!(md.try_parse("# a").is_ok());
+}
+
+#[test]
+fn limits_apply_to_stream() {
+    let mut md = md();
+    md.limits.max_nodes = Some(3);
+    md.limits.max_nesting = 3;
+
+    let src = "*a* *b* *c*\n\n> > > > deep\n";
+    let mut stream = md.stream();
+    let mut html = String::new();
+    for line in src.split_inclusive('\n') {
+        for node in stream.push(line) {
+            html.push_str(&node.render());
+        }
+    }
+    for node in stream.finish() {
+        html.push_str(&node.render());
+    }
+    // each part of the input gets its own budget
+    assert_eq!(
+        html,
+        "<p><em>a</em> *b* *c*</p>\n<blockquote>\n<blockquote>\n&gt; &gt; deep\n</blockquote>\n</blockquote>\n"
+    );
+}
+
+#[test]
+fn limits_apply_to_reparse() {
+    use markdown_that::parser::incremental::TextEdit;
+
+    let mut md = md();
+    md.limits.max_nodes = Some(4);
+
+    let ast = md.parse("x\n\nfoo\n");
+    let ast = md.reparse(ast, &TextEdit::new(3..6, "*a* *b* *c*"));
+    assert_eq!(ast.render(), "<p>x</p>\n<p><em>a</em> *b* *c*</p>\n");
+
+    md.limits.max_source_bytes = Some(10);
+    let ast = md.reparse(ast, &TextEdit::new(0..1, "# x"));
+    assert_eq!(ast.render(), "# x\n\n*a* *b* *c*\n");
+}
+
+#[test]
+fn error_messages() {
+    assert_eq!(
+        ParseError::SourceTooLarge { size: 5, limit: 4 }.to_string(),
+        "source is 5 bytes, limit is 4 bytes"
+    );
+    assert_eq!(
+        ParseError::TooDeep { limit: 3 }.to_string(),
+        "nesting is deeper than 3 levels"
+    );
+}
+
+#[test]
+fn max_nesting_footnotes() {
+    let mut md = md();
+    markdown_that::plugins::extra::footnotes::add(&mut md);
+    md.limits.max_nesting = 4;
+
+    // definition content is moved into footnote section, one level deeper
+    let src = "a[^1]\n\n[^1]: > b\n";
+    let ast = md.parse(src);
+    let mut depth = 0;
+    ast.walk(|_, level| depth = depth.max(level));
+    assert!(depth <= 4);
+    assert!(ast.render().contains(
+        "<li id=\"fn1\" class=\"footnote-item\">b <a href=\"#fnref1\" class=\"footnote-backref\">↩︎</a>\n</li>"
+    ));
+    assert_eq!(
+        md.try_parse(src).unwrap_err(),
+        ParseError::TooDeep { limit: 4 }
+    );
+    assert!(md.try_parse("a[^1]\n\n[^1]: b\n").is_ok());
+}
@@ -0,0 +1,240 @@
+use markdown_that::parser::diagnostics::{Diagnostic, Diagnostics, Severity};
+use markdown_that::plugins::lint::{self, LintRule};
+use markdown_that::{MarkdownThat, Node};
+
+fn md() -> MarkdownThat {
+    let mut md = MarkdownThat::new();
+    markdown_that::plugins::cmark::add(&mut md);
+    markdown_that::plugins::extra::tables::add(&mut md);
+    lint::add(&mut md);
+    md
+}
+
+// code, message and byte range of each diagnostic
+fn run(md: &MarkdownThat, src: &str) -> Vec<(&'static str, String, (usize, usize))> {
+    let ast = md.parse(src);
+    Diagnostics::of(&ast)
+        .iter()
+        .map(|d| {
+            (
+                d.code,
+                d.message.clone(),
+                d.srcmap.unwrap().get_byte_offsets(),
+            )
+        })
+        .collect()
+}
+
+fn codes(md: &MarkdownThat, src: &str) -> Vec<&'static str> {
+    run(md, src).into_iter().map(|(code, _, _)| code).collect()
+}
+
+#[test]
+fn clean_document() {
+    let src = "# title\n\n## section\n\n[link][ref] ![alt](image.png)\n\n[ref]: /url\n";
+    assert!(run(&md(), src).is_empty());
+}
+
+#[test]
+fn heading_increment() {
+    let md = md();
+    assert_eq!(
+        run(&md, "# a\n\n### b\n"),
+        [(
+            "MD001",
+            "heading levels should only increment by one level at a time (expected h2, got h3)"
+                .into(),
+            (5, 10)
+        )]
+    );
+    assert_eq!(codes(&md, "a\n===\n\nb\n---\n\n#### c\n"), ["MD001"]);
+    // first heading and decrements are fine
+    assert!(codes(&md, "### a\n\n#### b\n\n# c\n\n## d\n").is_empty());
+}
+
+#[test]
+fn empty_links() {
+    let md = md();
+    assert_eq!(
+        run(&md, "[a]() [b](#) [c](/c)"),
+        [
+            ("MD042", "link has no destination".into(), (0, 5)),
+            ("MD042", "link has no destination".into(), (6, 12)),
+        ]
+    );
+}
+
+#[test]
+fn images_without_alt_text() {
+    let md = md();
+    assert_eq!(
+        run(&md, "![](a.png) ![ ](b.png) ![c](c.png)"),
+        [
+            ("MD045", "image has no alt text".into(), (0, 10)),
+            ("MD045", "image has no alt text".into(), (11, 22)),
+        ]
+    );
+}
+
+#[test]
+fn unused_references() {
+    let md = md();
+    let src = "[a] [B][] [text][c] ![d]\n\n[a]: /a\n[b]: /b\n[c]: /c\n[d]: /d\n[e]: /e\n";
+    assert_eq!(
+        run(&md, src),
+        [(
+            "MD053",
+            "link reference definition \"e\" is never used".into(),
+            (58, 65)
+        )]
+    );
+    // references inside link text
+    assert!(codes(&md, "[[a]](/b)\n\n[a]: /a\n").is_empty());
+}
+
+#[test]
+fn duplicate_references() {
+    let src = "[a]\n\n[a]: /a\n[A]: /b\n";
+    assert_eq!(
+        run(&md(), src),
+        [(
+            "MD053",
+            "duplicate link reference definition \"A\"".into(),
+            (13, 20)
+        )]
+    );
+
+    // reported by the parser, so lint plugin isn't needed
+    let mut md = MarkdownThat::new();
+    markdown_that::plugins::cmark::add(&mut md);
+    assert_eq!(codes(&md, src), ["MD053"]);
+    assert!(codes(&md, "[a]: /a\n").is_empty());
+}
+
+#[test]
+fn duplicate_references_with_custom_map() {
+    use markdown_that::parser::block::builtin::BlockParserRule;
+    use markdown_that::parser::core::{CoreRule, Root};
+    use markdown_that::plugins::cmark::block::reference::{
+        CustomReferenceMap, DefaultReferenceMap, ReferenceMap,
+    };
+
+    // resolves any label, so definitions are only looked up in the document
+    #[derive(Debug, Default)]
+    struct Fallback(DefaultReferenceMap);
+    impl CustomReferenceMap for Fallback {
+        fn insert(&mut self, label: String, destination: String, title: Option<String>) -> bool {
+            self.0.insert(label, destination, title)
+        }
+
+        fn get(&self, label: &str) -> Option<(&str, Option<&str>)> {
+            self.0.get(label).or(Some(("/fallback", None)))
+        }
+    }
+
+    struct AddFallback;
+    impl CoreRule for AddFallback {
+        fn run(root: &mut Node, _: &MarkdownThat) {
+            let data = root.cast_mut::<Root>().unwrap();
+            data.ext.insert(ReferenceMap::new(Fallback::default()));
+        }
+    }
+
+    let mut md = md();
+    md.add_rule::<AddFallback>().before::<BlockParserRule>();
+    assert!(codes(&md, "[a] [b]\n\n[a]: /a\n[b]: /b\n").is_empty());
+    assert_eq!(codes(&md, "[a]\n\n[a]: /a\n[ A ]: /b\n"), ["MD053"]);
+}
+
+#[test]
+fn table_column_count() {
+    let md = md();
+    let src = "| a | b |\n|---|---|\n| c | d |\n| e |\n| f | g | h |\n";
+    assert_eq!(
+        run(&md, src),
+        [
+            (
+                "MD056",
+                "table row has 1 cells, expected 2".into(),
+                (30, 35)
+            ),
+            (
+                "MD056",
+                "table row has 3 cells, expected 2".into(),
+                (36, 49)
+            ),
+        ]
+    );
+}
+
+#[test]
+fn diagnostics_are_sorted() {
+    let src = "[e]: /e\n\n# a\n\n### b\n\n![](c)\n\n| a | b |\n|---|---|\n| c |\n";
+    assert_eq!(codes(&md(), src), ["MD053", "MD001", "MD045", "MD056"]);
+}
+
+#[test]
+fn display() {
+    let diagnostic = Diagnostic::new(Severity::Error, "X001", "something is wrong", None);
+    assert_eq!(diagnostic.to_string(), "error[X001]: something is wrong");
+    assert!(Severity::Info < Severity::Warning && Severity::Warning < Severity::Error);
+}
+
+struct NoCodeBlocks;
+impl LintRule for NoCodeBlocks {
+    fn check(root: &Node, _: &MarkdownThat, diagnostics: &mut Vec<Diagnostic>) {
+        root.walk(|node, _| {
+            if node.is::<markdown_that::plugins::cmark::block::fence::CodeFence>() {
+                diagnostics.push(Diagnostic::new(
+                    Severity::Info,
+                    "custom",
+                    "code block",
+                    node.srcmap,
+                ));
+            }
+        });
+    }
+}
+
+#[test]
+fn custom_rules() {
+    let mut md = md();
+    lint::add_rule::<NoCodeBlocks>(&mut md);
+    assert!(lint::has_rule::<NoCodeBlocks>(&md));
+    assert_eq!(codes(&md, "```\ncode\n```\n\n![](a)"), ["custom", "MD045"]);
+
+    lint::remove_rule::<lint::rules::NoAltText>(&mut md);
+    assert!(!lint::has_rule::<lint::rules::NoAltText>(&md));
+    assert_eq!(codes(&md, "```\ncode\n```\n\n![](a)"), ["custom"]);
+
+    // only custom rule is added
+    let mut md = MarkdownThat::new();
+    markdown_that::plugins::cmark::add(&mut md);
+    lint::add_rule::<NoCodeBlocks>(&mut md);
+    assert_eq!(codes(&md, "```\ncode\n```\n\n# a\n\n### b\n"), ["custom"]);
+}
+
+#[test]
+fn reparse() {
+    use markdown_that::parser::incremental::TextEdit;
+
+    let md = md();
+    let ast = md.parse("# a\n\n### b\n\npara\n\n![](x.png)\n");
+    let ast = md.reparse(ast, &TextEdit::new(0..0, "abc\n\n"));
+    let expected = run(&md, "abc\n\n# a\n\n### b\n\npara\n\n![](x.png)\n");
+    let actual: Vec<_> = Diagnostics::of(&ast)
+        .iter()
+        .map(|d| {
+            (
+                d.code,
+                d.message.clone(),
+                d.srcmap.unwrap().get_byte_offsets(),
+            )
+        })
+        .collect();
+    assert_eq!(actual, expected);
+    assert_eq!(
+        codes(&md, "abc\n\n# a\n\n### b\n\npara\n\n![](x.png)\n"),
+        ["MD001", "MD045"]
+    );
+}
@@ -0,0 +1,124 @@
+use markdown_that::parser::renderer::ManRenderOptions;
+
+fn run_with(input: &str, options: ManRenderOptions, output: &str) {
+    let md = &mut markdown_that::MarkdownThat::new();
+    markdown_that::plugins::cmark::add(md);
+    markdown_that::plugins::html::add(md);
+    markdown_that::plugins::extra::tables::add(md);
+    let node = md.parse(input);
+    assert_eq!(node.render_man(&options), output);
+}
+
+fn run(input: &str, output: &str) {
+    run_with(input, ManRenderOptions::default(), output);
+}
+
+#[test]
+fn title() {
+    run_with(
+        "text",
+        ManRenderOptions {
+            title: Some("MYAPP".into()),
+            section: "8".into(),
+            manual: Some("System \"Admin\"".into()),
+            ..Default::default()
+        },
+        ".TH \"MYAPP\" \"8\" \"\" \"\" \"System \\(dqAdmin\\(dq\"\n.PP\ntext\n",
+    );
+}
+
+#[test]
+fn headings() {
+    run(
+        "# NAME\n\nmyapp\n\n## Options\n\n### Deep",
+        ".SH NAME\n.PP\nmyapp\n.SS Options\n.PP\n\\fBDeep\\fR\n",
+    );
+}
+
+#[test]
+fn fonts() {
+    run(
+        "*em* and **strong *both*** with `code`",
+        ".PP\n\\fIem\\fR and \\fBstrong \\f(BIboth\\fB\\fR with \\f(CRcode\\fR\n",
+    );
+}
+
+#[test]
+fn escaping() {
+    run(
+        ".dot\n'quote and \\\\ back --flag",
+        ".PP\n\\&.dot\n\\&'quote and \\e back \\-\\-flag\n",
+    );
+}
+
+#[test]
+fn line_breaks() {
+    run("foo  \nbar", ".PP\nfoo\n.br\nbar\n");
+}
+
+#[test]
+fn code_blocks() {
+    run(
+        "```sh\n.start\nrun --x\n```\n\n    indented",
+        ".PP\n.RS 4\n.nf\n\\&.start\nrun \\-\\-x\n.fi\n.RE\n.PP\n.RS 4\n.nf\nindented\n.fi\n.RE\n",
+    );
+}
+
+#[test]
+fn lists() {
+    run(
+        "- one\n- two\n  1. a\n  2. b",
+        ".IP \\(bu 2\none\n.IP \\(bu 2\ntwo\n.RS\n.IP \"1.\" 4\na\n.IP \"2.\" 4\nb\n.RE\n",
+    );
+}
+
+#[test]
+fn loose_lists() {
+    run(
+        "3. x\n\n   para\n4. y\n\nafter",
+        ".IP \"3.\" 4\nx\n.IP\npara\n.IP \"4.\" 4\ny\n.PP\nafter\n",
+    );
+}
+
+#[test]
+fn blockquotes() {
+    run("> quote\n>\n> more", ".RS\n.PP\nquote\n.PP\nmore\n.RE\n");
+}
+
+#[test]
+fn links() {
+    run(
+        "[link](http://x.com/a-b) <http://foo.bar> ![alt](i.png)",
+        ".PP\nlink <http://x.com/a\\-b> http://foo.bar alt\n",
+    );
+}
+
+#[test]
+fn html_is_dropped() {
+    run("<div>\nfoo\n</div>\n\nbar <b>baz</b>", ".PP\nbar baz\n");
+}
+
+#[cfg(feature = "syntect")]
+#[test]
+fn syntax_highlighting() {
+    let md = &mut markdown_that::MarkdownThat::new();
+    markdown_that::plugins::cmark::add(md);
+    markdown_that::plugins::extra::syntect::add(md);
+    let node = md.parse("```sh\n.start\nrun --x \"<a>\"\n```");
+    assert_eq!(
+        node.render_man(&ManRenderOptions::default()),
+        ".PP\n.RS 4\n.nf\n\\&.start\nrun \\-\\-x \"<a>\"\n.fi\n.RE\n"
+    );
+}
+
+#[test]
+fn task_lists() {
+    let md = &mut markdown_that::MarkdownThat::new();
+    markdown_that::plugins::cmark::add(md);
+    markdown_that::plugins::gfm::tasklist::add(md);
+    let node = md.parse("- [x] done\n- [ ] todo\n- plain\n\nafter");
+    assert_eq!(
+        node.render_man(&ManRenderOptions::default()),
+        ".IP \\(bu 2\n[x] done\n.IP \\(bu 2\n[ ] todo\n.IP \\(bu 2\nplain\n.PP\nafter\n"
+    );
+}
@@ -0,0 +1,313 @@
+#![cfg(feature = "serde")]
+use markdown_that::MarkdownThat;
+use markdown_that::parser::mdast::MdastExporter;
+use serde_json::{Value, json};
+
+fn run(input: &str) -> Value {
+    let md = &mut MarkdownThat::new();
+    markdown_that::plugins::cmark::add(md);
+    markdown_that::plugins::html::add(md);
+    markdown_that::plugins::extra::strikethrough::add(md);
+    markdown_that::plugins::extra::tables::add(md);
+    markdown_that::plugins::gfm::tasklist::add(md);
+    markdown_that::plugins::extra::footnotes::add(md);
+    let node = md.parse(input);
+    MdastExporter::new().export(&node)
+}
+
+// remove positions to make assertions shorter
+fn strip(mut value: Value) -> Value {
+    match &mut value {
+        Value::Object(object) => {
+            object.remove("position");
+            for (_, value) in object.iter_mut() {
+                *value = strip(value.take());
+            }
+        }
+        Value::Array(array) => {
+            for value in array.iter_mut() {
+                *value = strip(value.take());
+            }
+        }
+        _ => {}
+    }
+    value
+}
+
+#[test]
+fn positions() {
+    assert_eq!(
+        run("# foo\n\n*bar*\nbaz\n"),
+        json!({
+            "type": "root",
+            "children": [{
+                "type": "heading",
+                "depth": 1,
+                "children": [{
+                    "type": "text",
+                    "value": "foo",
+                    "position": {
+                        "start": { "line": 1, "column": 3, "offset": 2 },
+                        "end": { "line": 1, "column": 6, "offset": 5 },
+                    },
+                }],
+                "position": {
+                    "start": { "line": 1, "column": 1, "offset": 0 },
+                    "end": { "line": 1, "column": 6, "offset": 5 },
+                },
+            }, {
+                "type": "paragraph",
+                "children": [{
+                    "type": "emphasis",
+                    "children": [{
+                        "type": "text",
+                        "value": "bar",
+                        "position": {
+                            "start": { "line": 3, "column": 2, "offset": 8 },
+                            "end": { "line": 3, "column": 5, "offset": 11 },
+                        },
+                    }],
+                    "position": {
+                        "start": { "line": 3, "column": 1, "offset": 7 },
+                        "end": { "line": 3, "column": 6, "offset": 12 },
+                    },
+                }, {
+                    "type": "text",
+                    "value": "\nbaz",
+                    "position": {
+                        "start": { "line": 3, "column": 6, "offset": 12 },
+                        "end": { "line": 4, "column": 4, "offset": 16 },
+                    },
+                }],
+                "position": {
+                    "start": { "line": 3, "column": 1, "offset": 7 },
+                    "end": { "line": 4, "column": 4, "offset": 16 },
+                },
+            }],
+            "position": {
+                "start": { "line": 1, "column": 1, "offset": 0 },
+                "end": { "line": 5, "column": 1, "offset": 17 },
+            },
+        })
+    );
+}
+
+#[test]
+fn blocks() {
+    assert_eq!(
+        strip(run(
+            "Title\n===\n\n> quote\n\n***\n\n    code\n\n```rust  ignore\nfn main() {}\n```\n\n<div>\n\n[foo]: /url 'title'\n"
+        )),
+        json!({
+            "type": "root",
+            "children": [
+                { "type": "heading", "depth": 1, "children": [{ "type": "text", "value": "Title" }] },
+                { "type": "blockquote", "children": [
+                    { "type": "paragraph", "children": [{ "type": "text", "value": "quote" }] },
+                ] },
+                { "type": "thematicBreak" },
+                { "type": "code", "lang": null, "meta": null, "value": "code" },
+                { "type": "code", "lang": "rust", "meta": "ignore", "value": "fn main() {}" },
+                { "type": "html", "value": "<div>" },
+                { "type": "definition", "identifier": "foo", "label": "foo", "url": "/url", "title": "title" },
+            ],
+        })
+    );
+}
+
+#[test]
+fn lists() {
+    assert_eq!(
+        strip(run("- a\n- b\n  > c\n\n3) d\n\n   e\n")),
+        json!({
+            "type": "root",
+            "children": [{
+                "type": "list", "ordered": false, "start": null, "spread": false,
+                "children": [{
+                    "type": "listItem", "spread": false, "checked": null,
+                    "children": [{ "type": "paragraph", "children": [{ "type": "text", "value": "a" }] }],
+                }, {
+                    "type": "listItem", "spread": false, "checked": null,
+                    "children": [
+                        { "type": "paragraph", "children": [{ "type": "text", "value": "b" }] },
+                        { "type": "blockquote", "children": [
+                            { "type": "paragraph", "children": [{ "type": "text", "value": "c" }] },
+                        ] },
+                    ],
+                }],
+            }, {
+                "type": "list", "ordered": true, "start": 3, "spread": true,
+                "children": [{
+                    "type": "listItem", "spread": true, "checked": null,
+                    "children": [
+                        { "type": "paragraph", "children": [{ "type": "text", "value": "d" }] },
+                        { "type": "paragraph", "children": [{ "type": "text", "value": "e" }] },
+                    ],
+                }],
+            }],
+        })
+    );
+}
+
+#[test]
+fn task_list() {
+    assert_eq!(
+        strip(run("- [x] a\n- b\n")),
+        json!({
+            "type": "root",
+            "children": [{
+                "type": "list", "ordered": false, "start": null, "spread": false,
+                "children": [{
+                    "type": "listItem", "spread": false, "checked": true,
+                    "children": [{ "type": "paragraph", "children": [{ "type": "text", "value": "a" }] }],
+                }, {
+                    "type": "listItem", "spread": false, "checked": null,
+                    "children": [{ "type": "paragraph", "children": [{ "type": "text", "value": "b" }] }],
+                }],
+            }],
+        })
+    );
+}
+
+#[test]
+fn footnotes() {
+    assert_eq!(
+        strip(run("a[^Note] b^[*c*] [^note]\n\n[^Note]: d\n\n    e\n")),
+        json!({
+            "type": "root",
+            "children": [{
+                "type": "paragraph",
+                "children": [
+                    { "type": "text", "value": "a" },
+                    { "type": "footnoteReference", "identifier": "note", "label": "Note" },
+                    { "type": "text", "value": " b" },
+                    { "type": "footnoteReference", "identifier": "2", "label": "2" },
+                    { "type": "text", "value": " " },
+                    { "type": "footnoteReference", "identifier": "note", "label": "note" },
+                ],
+            }, {
+                "type": "footnoteDefinition", "identifier": "note", "label": "Note",
+                "children": [
+                    { "type": "paragraph", "children": [{ "type": "text", "value": "d" }] },
+                    { "type": "paragraph", "children": [{ "type": "text", "value": "e" }] },
+                ],
+            }, {
+                "type": "footnoteDefinition", "identifier": "2", "label": "2",
+                "children": [{
+                    "type": "paragraph",
+                    "children": [{ "type": "emphasis", "children": [{ "type": "text", "value": "c" }] }],
+                }],
+            }],
+        })
+    );
+}
+
+#[test]
+fn tight_list_positions() {
+    let result = run("- foo\n");
+    assert_eq!(
+        result["children"][0]["children"][0]["children"][0]["position"],
+        json!({
+            "start": { "line": 1, "column": 3, "offset": 2 },
+            "end": { "line": 1, "column": 6, "offset": 5 },
+        })
+    );
+}
+
+#[test]
+fn inlines() {
+    assert_eq!(
+        strip(run(
+            "*a* __b__ `c` ~~d~~ [e](/f \"g\") ![h *i*](/j) <http://k> <b>l</b>\\\nm &amp; \\*"
+        )),
+        json!({
+            "type": "root",
+            "children": [{
+                "type": "paragraph",
+                "children": [
+                    { "type": "emphasis", "children": [{ "type": "text", "value": "a" }] },
+                    { "type": "text", "value": " " },
+                    { "type": "strong", "children": [{ "type": "text", "value": "b" }] },
+                    { "type": "text", "value": " " },
+                    { "type": "inlineCode", "value": "c" },
+                    { "type": "text", "value": " " },
+                    { "type": "delete", "children": [{ "type": "text", "value": "d" }] },
+                    { "type": "text", "value": " " },
+                    { "type": "link", "url": "/f", "title": "g", "children": [{ "type": "text", "value": "e" }] },
+                    { "type": "text", "value": " " },
+                    { "type": "image", "url": "/j", "title": null, "alt": "h i" },
+                    { "type": "text", "value": " " },
+                    { "type": "link", "url": "http://k", "title": null, "children": [{ "type": "text", "value": "http://k" }] },
+                    { "type": "text", "value": " " },
+                    { "type": "html", "value": "<b>" },
+                    { "type": "text", "value": "l" },
+                    { "type": "html", "value": "</b>" },
+                    { "type": "break" },
+                    { "type": "text", "value": "m & *" },
+                ],
+            }],
+        })
+    );
+}
+
+#[test]
+fn tables() {
+    assert_eq!(
+        strip(run("| a | b | c |\n|:-|-:|-|\n| d |")),
+        json!({
+            "type": "root",
+            "children": [{
+                "type": "table",
+                "align": ["left", "right", null],
+                "children": [{
+                    "type": "tableRow",
+                    "children": [
+                        { "type": "tableCell", "children": [{ "type": "text", "value": "a" }] },
+                        { "type": "tableCell", "children": [{ "type": "text", "value": "b" }] },
+                        { "type": "tableCell", "children": [{ "type": "text", "value": "c" }] },
+                    ],
+                }, {
+                    "type": "tableRow",
+                    "children": [
+                        { "type": "tableCell", "children": [{ "type": "text", "value": "d" }] },
+                        { "type": "tableCell", "children": [] },
+                        { "type": "tableCell", "children": [] },
+                    ],
+                }],
+            }],
+        })
+    );
+}
+
+#[test]
+fn custom_handlers() {
+    let md = &mut MarkdownThat::new();
+    markdown_that::plugins::cmark::add(md);
+    let node = md.parse("*foo* bar");
+
+    let mut exporter = MdastExporter::new();
+    exporter.remove::<markdown_that::plugins::cmark::inline::emphasis::Em>();
+    exporter.add::<markdown_that::plugins::cmark::block::paragraph::Paragraph>(|node, ctx| {
+        ctx.parent(node, json!({ "type": "custom" }))
+    });
+
+    assert_eq!(
+        strip(exporter.export(&node)),
+        json!({
+            "type": "root",
+            "children": [{ "type": "custom", "children": [{ "type": "text", "value": "foo bar" }] }],
+        })
+    );
+}
+
+#[test]
+fn unicode_positions() {
+    let result = run("# αβ\n");
+    assert_eq!(
+        result["children"][0]["children"][0]["position"],
+        json!({
+            "start": { "line": 1, "column": 3, "offset": 2 },
+            "end": { "line": 1, "column": 5, "offset": 6 },
+        })
+    );
+}
@@ -0,0 +1,202 @@
+#![cfg(feature = "serde")]
+use markdown_that::MarkdownThat;
+use markdown_that::parser::pandoc::PandocExporter;
+use serde_json::{Value, json};
+
+fn run(input: &str) -> Value {
+    let md = &mut MarkdownThat::new();
+    markdown_that::plugins::cmark::add(md);
+    markdown_that::plugins::html::add(md);
+    markdown_that::plugins::extra::strikethrough::add(md);
+    markdown_that::plugins::extra::tables::add(md);
+    markdown_that::plugins::gfm::tasklist::add(md);
+    markdown_that::plugins::extra::footnotes::add(md);
+    markdown_that::plugins::extra::heading_anchors::add(md, |s| s.to_lowercase().replace(' ', "-"));
+    let node = md.parse(input);
+    let doc = PandocExporter::new().export(&node);
+    assert_eq!(doc["pandoc-api-version"], json!([1, 23, 1]));
+    assert_eq!(doc["meta"], json!({}));
+    doc["blocks"].clone()
+}
+
+fn str(s: &str) -> Value {
+    json!({ "t": "Str", "c": s })
+}
+
+fn space() -> Value {
+    json!({ "t": "Space" })
+}
+
+fn no_attr() -> Value {
+    json!(["", [], []])
+}
+
+#[test]
+fn paragraph() {
+    assert_eq!(
+        run("foo  bar \\* &amp;\nbaz\\\nquux"),
+        json!([{ "t": "Para", "c": [
+            str("foo"), space(), str("bar"), space(), str("*"), space(), str("&"),
+            { "t": "SoftBreak" }, str("baz"), { "t": "LineBreak" }, str("quux"),
+        ] }])
+    );
+}
+
+#[test]
+fn headings() {
+    assert_eq!(
+        run("# Hello world\n\nSub\n---"),
+        json!([
+            { "t": "Header", "c": [1, ["hello-world", [], []], [str("Hello"), space(), str("world")]] },
+            { "t": "Header", "c": [2, ["sub", [], []], [str("Sub")]] },
+        ])
+    );
+}
+
+#[test]
+fn code() {
+    assert_eq!(
+        run("```rust ignore\nfn main() {}\n```\n\n    indented\n\n`inline`"),
+        json!([
+            { "t": "CodeBlock", "c": [["", ["rust"], []], "fn main() {}"] },
+            { "t": "CodeBlock", "c": [no_attr(), "indented"] },
+            { "t": "Para", "c": [{ "t": "Code", "c": [no_attr(), "inline"] }] },
+        ])
+    );
+}
+
+#[test]
+fn lists() {
+    assert_eq!(
+        run("- a\n- b\n\n---\n\n2) c\n\n   d\n"),
+        json!([
+            { "t": "BulletList", "c": [
+                [{ "t": "Plain", "c": [str("a")] }],
+                [{ "t": "Plain", "c": [str("b")] }],
+            ] },
+            { "t": "HorizontalRule" },
+            { "t": "OrderedList", "c": [
+                [2, { "t": "Decimal" }, { "t": "OneParen" }],
+                [[
+                    { "t": "Para", "c": [str("c")] },
+                    { "t": "Para", "c": [str("d")] },
+                ]],
+            ] },
+        ])
+    );
+}
+
+#[test]
+fn task_list() {
+    assert_eq!(
+        run("- [ ] a\n- [x] b\n"),
+        json!([
+            { "t": "BulletList", "c": [
+                [{ "t": "Plain", "c": [str("☐"), space(), str("a")] }],
+                [{ "t": "Plain", "c": [str("☒"), space(), str("b")] }],
+            ] },
+        ])
+    );
+}
+
+#[test]
+fn footnotes() {
+    let note = json!({ "t": "Note", "c": [{ "t": "Para", "c": [str("b")] }] });
+    assert_eq!(
+        run("a[^1] c^[*d*] [^1]\n\n[^1]: b\n"),
+        json!([
+            { "t": "Para", "c": [
+                str("a"), note, space(), str("c"),
+                { "t": "Note", "c": [{ "t": "Para", "c": [{ "t": "Emph", "c": [str("d")] }] }] },
+                space(), note,
+            ] },
+        ])
+    );
+}
+
+#[test]
+fn self_referencing_footnote() {
+    assert_eq!(
+        run("a[^1]\n\n[^1]: see[^1]\n"),
+        json!([
+            { "t": "Para", "c": [
+                str("a"),
+                { "t": "Note", "c": [{ "t": "Para", "c": [
+                    str("see"),
+                    { "t": "Superscript", "c": [str("1")] },
+                ] }] },
+            ] },
+        ])
+    );
+}
+
+#[test]
+fn blockquote_and_html() {
+    assert_eq!(
+        run("> quote <b>x</b>\n\n<div>\n\n[ref]: /url"),
+        json!([
+            { "t": "BlockQuote", "c": [{ "t": "Para", "c": [
+                str("quote"), space(),
+                { "t": "RawInline", "c": ["html", "<b>"] },
+                str("x"),
+                { "t": "RawInline", "c": ["html", "</b>"] },
+            ] }] },
+            { "t": "RawBlock", "c": ["html", "<div>\n"] },
+        ])
+    );
+}
+
+#[test]
+fn inlines() {
+    assert_eq!(
+        run("*a* **b** ~~c~~ [d](/e \"f\") ![g](/h) <http://i>"),
+        json!([{ "t": "Para", "c": [
+            { "t": "Emph", "c": [str("a")] }, space(),
+            { "t": "Strong", "c": [str("b")] }, space(),
+            { "t": "Strikeout", "c": [str("c")] }, space(),
+            { "t": "Link", "c": [no_attr(), [str("d")], ["/e", "f"]] }, space(),
+            { "t": "Image", "c": [no_attr(), [str("g")], ["/h", ""]] }, space(),
+            { "t": "Link", "c": [["", ["uri"], []], [str("http://i")], ["http://i", ""]] },
+        ] }])
+    );
+}
+
+#[test]
+fn table() {
+    let cell = |s: &str| json!([no_attr(), { "t": "AlignDefault" }, 1, 1, [{ "t": "Plain", "c": [str(s)] }]]);
+    let empty = json!([no_attr(), { "t": "AlignDefault" }, 1, 1, []]);
+    assert_eq!(
+        run("| a | b |\n|:-|-:|\n| c |"),
+        json!([{ "t": "Table", "c": [
+            no_attr(),
+            [null, []],
+            [
+                [{ "t": "AlignLeft" }, { "t": "ColWidthDefault" }],
+                [{ "t": "AlignRight" }, { "t": "ColWidthDefault" }],
+            ],
+            [no_attr(), [[no_attr(), [cell("a"), cell("b")]]]],
+            [[no_attr(), 0, [], [[no_attr(), [cell("c"), empty]]]]],
+            [no_attr(), []],
+        ] }])
+    );
+}
+
+#[test]
+fn attributes() {
+    let md = &mut MarkdownThat::new();
+    markdown_that::plugins::cmark::add(md);
+    let mut node = md.parse("foo");
+    node.children[0].attrs.push(("class", "a b".into()));
+    node.children[0].attrs.push(("data-x", "y".into()));
+
+    let mut exporter = PandocExporter::new();
+    exporter.add::<markdown_that::plugins::cmark::block::paragraph::Paragraph>(|node, ctx| {
+        let attr = markdown_that::parser::pandoc::attr(node);
+        vec![json!({ "t": "Div", "c": [attr, [{ "t": "Para", "c": ctx.inlines(node) }]] })]
+    });
+
+    assert_eq!(
+        exporter.export(&node)["blocks"],
+        json!([{ "t": "Div", "c": [["", ["a", "b"], [["data-x", "y"]]], [{ "t": "Para", "c": [str("foo")] }]] }])
+    );
+}
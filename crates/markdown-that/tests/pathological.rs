@@ -4,13 +4,15 @@ use markdown_that::MarkdownThat;
 use std::sync::LazyLock;
 use std::time::SystemTime;
 
-static MD: LazyLock<MarkdownThat> = LazyLock::new(|| {
+static MD: LazyLock<MarkdownThat> = LazyLock::new(parser);
+
+fn parser() -> MarkdownThat {
     let mut parser = markdown_that::MarkdownThat::new();
     markdown_that::plugins::cmark::add(&mut parser);
     markdown_that::plugins::html::add(&mut parser);
     markdown_that::plugins::extra::add(&mut parser);
     parser
-});
+}
 
 fn run(src: &str) {
     let now = SystemTime::now();
@@ -157,3 +159,197 @@ mod markdownit {
         run(&format!("{}{}{}", "x", " ".repeat(100000), "x  \nx"));
     }
 }
+
+mod gfm {
+    use markdown_that::MarkdownThat;
+    use std::sync::LazyLock;
+    use std::time::SystemTime;
+
+    static MD: LazyLock<MarkdownThat> = LazyLock::new(|| {
+        let mut md = MarkdownThat::new();
+        markdown_that::plugins::cmark::add(&mut md);
+        markdown_that::plugins::gfm::add(&mut md);
+        md
+    });
+
+    fn run(src: &str) {
+        let now = SystemTime::now();
+        MD.parse(src);
+        dbg!(now.elapsed().ok().unwrap());
+    }
+
+    #[test]
+    fn strikethrough_closers_with_no_openers() {
+        run(&"a~ a~~ ".repeat(50000));
+    }
+
+    #[test]
+    fn strikethrough_openers_with_no_closers() {
+        run(&"~a ~~a ".repeat(50000));
+    }
+
+    #[test]
+    fn strikethrough_across_lines() {
+        run(&"> ~a\n>   b~ ".repeat(10000));
+    }
+
+    #[test]
+    fn autolink_parentheses() {
+        run(&format!("www.a.b/{}", ")".repeat(100000)));
+    }
+
+    #[test]
+    fn email_pattern() {
+        run(&"a_b@".repeat(50000));
+    }
+}
+
+mod depth {
+    // max_nesting must bound the depth of the resulting tree, whatever the input
+    use super::{MD, parser};
+    use markdown_that::parser::limits::ParseError;
+    use markdown_that::{MarkdownThat, Node};
+    use std::sync::LazyLock;
+
+    static MD_10: LazyLock<MarkdownThat> = LazyLock::new(|| {
+        let mut md = parser();
+        md.limits.max_nesting = 10;
+        md
+    });
+
+    fn max_depth(node: &Node) -> u32 {
+        let mut result = 0;
+        node.walk(|_, depth| result = result.max(depth));
+        result
+    }
+
+    // check depth with default and small limits, content must be kept as text
+    fn run(src: &str, text: &str) {
+        for md in [&*MD, &*MD_10] {
+            let limit = md.limits.max_nesting;
+            let ast = md.parse(src);
+            assert!(
+                max_depth(&ast) <= limit,
+                "depth {} > {limit}",
+                max_depth(&ast)
+            );
+            assert!(ast.collect_text().contains(text));
+        }
+    }
+
+    #[test]
+    fn nested_block_quotes() {
+        run(&format!("{}a", "> ".repeat(1000)), "a");
+    }
+
+    #[test]
+    fn error_is_reported() {
+        let src = format!("{}a", "> ".repeat(20));
+        assert!(MD.try_parse(&src).is_ok());
+        assert_eq!(
+            MD_10.try_parse(&src).unwrap_err(),
+            ParseError::TooDeep { limit: 10 }
+        );
+    }
+
+    #[test]
+    fn nested_lists() {
+        let src: String = (0..1000)
+            .map(|x| format!("{}* a\n", "  ".repeat(x)))
+            .collect();
+        run(&src, "a");
+    }
+
+    #[test]
+    fn nested_ordered_lists() {
+        run(&format!("{}a", "1. ".repeat(1000)), "a");
+    }
+
+    #[test]
+    fn nested_emphasis() {
+        run(
+            &format!("{}a{}", "*a **a".repeat(500), " a** a*".repeat(500)),
+            "a** a*",
+        );
+        run(
+            &format!("{}a{}", "*a _".repeat(500), "_ a*".repeat(500)),
+            "_ a*",
+        );
+    }
+
+    #[test]
+    fn nested_links() {
+        run(
+            &format!("{}a{}", "[".repeat(1000), "](b)".repeat(1000)),
+            "a",
+        );
+        run(
+            &format!("{}a{}", "![".repeat(1000), "](b)".repeat(1000)),
+            "a",
+        );
+    }
+
+    #[test]
+    fn emphasis_in_deep_quotes() {
+        run(&format!("{}*a **b** c*", "> ".repeat(99)), "b");
+        run(&format!("{}*a **b** c*", "> ".repeat(9)), "b");
+    }
+
+    #[test]
+    fn tables_in_deep_quotes() {
+        for depth in [8, 9, 10, 98, 99, 100] {
+            let prefix = "> ".repeat(depth);
+            let src = format!("{prefix}a|b\n{prefix}-|-\n{prefix}*c*|d\n");
+            let ast = MD.parse(&src);
+            assert!(max_depth(&ast) <= 100);
+            assert!(ast.collect_text().contains('c'));
+            let ast = MD_10.parse(&src);
+            assert!(max_depth(&ast) <= 10);
+            assert!(ast.collect_text().contains('c'));
+        }
+    }
+
+    #[test]
+    fn blocks_after_deep_content() {
+        // only content of the innermost container is kept as text
+        let mut md = parser();
+        md.limits.max_nesting = 3;
+        let html = md.parse("- - - - b\n\nnext *para*\n\n# heading\n").render();
+        assert!(html.ends_with("<p>next <em>para</em></p>\n<h1>heading</h1>\n"));
+
+        let src = format!("{}x\n\n# after *a*\n", "- ".repeat(60));
+        let html = MD.parse(&src).render();
+        assert!(html.ends_with("</ul>\n<h1>after <em>a</em></h1>\n"));
+        let html = MD_10.parse(&src).render();
+        assert!(html.ends_with("</ul>\n<h1>after <em>a</em></h1>\n"));
+
+        let src = format!("{}x\n\n> after *a*\n", "> ".repeat(20));
+        let html = MD_10.parse(&src).render();
+        assert!(html.ends_with("<p>after <em>a</em></p>\n</blockquote>\n"));
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn deprecated_max_nesting() {
+        let mut md = parser();
+        md.set_max_nesting(3);
+        assert_eq!(md.limits.max_nesting, 3);
+        assert_eq!(md.max_nesting(), 3);
+
+        let src = "> > > > b\n\nnext *para*\n";
+        assert_eq!(
+            md.try_parse(src).unwrap_err(),
+            ParseError::TooDeep { limit: 3 }
+        );
+        assert!(
+            md.parse(src)
+                .render()
+                .ends_with("<p>next <em>para</em></p>\n")
+        );
+    }
+
+    #[test]
+    fn mixed_nesting() {
+        run(&format!("{}_[*x*](a)_", "> - ".repeat(300)), "x");
+    }
+}
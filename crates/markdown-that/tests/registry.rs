@@ -0,0 +1,171 @@
+use markdown_that::parser::renderer::{
+    AnsiRenderOptions, AnsiRenderer, HTMLRenderer, LatexRenderer, ManRenderer, MarkdownRenderer,
+    RenderFallback, RenderFormat, RenderRegistry, TextRenderer,
+};
+use markdown_that::plugins::cmark::block::fence::CodeFence;
+use markdown_that::plugins::cmark::block::paragraph::Paragraph;
+use markdown_that::plugins::cmark::inline::emphasis::Strong;
+use markdown_that::plugins::cmark::inline::link::Link;
+use markdown_that::{MarkdownThat, Node, Renderer};
+
+fn parse(input: &str) -> Node {
+    let md = &mut MarkdownThat::new();
+    markdown_that::plugins::cmark::add(md);
+    md.parse(input)
+}
+
+// a format that isn't known to the node types at all
+struct BBCode {
+    registry: RenderRegistry<BBCode>,
+    out: String,
+}
+
+impl RenderFormat for BBCode {
+    fn registry(&self) -> &RenderRegistry<Self> {
+        &self.registry
+    }
+
+    fn write_text(&mut self, text: &str) {
+        self.out.push_str(&text.replace('[', "[[]"));
+    }
+}
+
+fn bbcode(fallback: RenderFallback<BBCode>) -> BBCode {
+    let mut registry = RenderRegistry::with_fallback(fallback);
+    registry.add::<Paragraph>(|node, fmt| {
+        fmt.render_children(node);
+        fmt.out.push('\n');
+    });
+    registry.add::<Strong>(|node, fmt| {
+        fmt.out.push_str("[b]");
+        fmt.render_children(node);
+        fmt.out.push_str("[/b]");
+    });
+    registry.add::<Link>(|node, fmt| {
+        let url = &node.cast::<Link>().unwrap().url;
+        fmt.out.push_str(&format!("[url={url}]"));
+        fmt.render_children(node);
+        fmt.out.push_str("[/url]");
+    });
+    BBCode {
+        registry,
+        out: String::new(),
+    }
+}
+
+#[test]
+fn custom_format_text_fallback() {
+    let mut fmt = bbcode(RenderFallback::Text);
+    fmt.registry
+        .add::<markdown_that::parser::core::Root>(|node, fmt| fmt.render_children(node));
+    fmt.render_node(&parse("**a** [*b*](http://x) [c]\n\nd"));
+    assert_eq!(fmt.out, "[b]a[/b] [url=http://x]b[/url] [[]c]\nd\n");
+}
+
+#[test]
+fn custom_format_children_fallback() {
+    let mut fmt = bbcode(RenderFallback::Children);
+    fmt.render_node(&parse("**a** *b* `c`"));
+    // text nodes have no handler either, so only markup is left
+    assert_eq!(fmt.out, "[b][/b]\n");
+}
+
+#[test]
+fn add_remove_contains() {
+    let mut registry = RenderRegistry::<BBCode>::new();
+    assert!(!registry.contains::<Link>());
+    registry.add::<Link>(|_, _| ());
+    assert!(registry.contains::<Link>());
+    assert!(registry.clone().contains::<Link>());
+    registry.remove::<Link>();
+    assert!(!registry.contains::<Link>());
+    assert!(format!("{registry:?}").contains("Children"));
+}
+
+#[test]
+fn ansi_handler() {
+    let mut fmt = AnsiRenderer::new(AnsiRenderOptions::default());
+    fmt.registry_mut().add::<Strong>(|node, fmt| {
+        fmt.push_style("31");
+        fmt.contents(&node.children);
+        fmt.pop_style();
+    });
+    fmt.render(&parse("**red** text"));
+    assert_eq!(String::from(fmt), "\x1b[31mred\x1b[0m text\n");
+}
+
+#[test]
+fn latex_handler() {
+    let mut fmt = LatexRenderer::default();
+    fmt.registry_mut().add::<CodeFence>(|node, fmt| {
+        let fence = node.cast::<CodeFence>().unwrap();
+        fmt.text_raw("\\begin{minted}{");
+        fmt.text_raw(&fence.info);
+        fmt.text_raw("}\n");
+        fmt.text_raw(&fence.content);
+        fmt.text_raw("\\end{minted}\n");
+    });
+    fmt.render(&parse("```rust\nfn main() {}\n```"));
+    assert_eq!(
+        String::from(fmt),
+        "\\begin{minted}{rust}\nfn main() {}\n\\end{minted}\n"
+    );
+}
+
+#[test]
+fn latex_default_handlers() {
+    let registry = LatexRenderer::default_registry();
+    assert!(registry.contains::<CodeFence>());
+    assert!(!registry.contains::<Link>());
+}
+
+#[test]
+fn man_handler() {
+    let mut fmt = ManRenderer::default();
+    fmt.registry_mut().add::<Link>(|node, fmt| {
+        let url = node.cast::<Link>().unwrap().url.clone();
+        fmt.cr();
+        fmt.text_raw(&format!(".UR {url}\n"));
+        fmt.contents(&node.children);
+        fmt.cr();
+        fmt.text_raw(".UE\n");
+    });
+    fmt.render(&parse("see [docs](http://x)"));
+    assert_eq!(String::from(fmt), ".PP\nsee \n.UR http://x\ndocs\n.UE\n");
+}
+
+#[test]
+fn html_handler() {
+    let mut fmt = HTMLRenderer::default();
+    fmt.registry_mut().add::<Strong>(|node, fmt| {
+        fmt.open("b", &[]);
+        fmt.contents(&node.children);
+        fmt.close("b");
+    });
+    fmt.render(&parse("**a** *b*"));
+    assert_eq!(String::from(fmt), "<p><b>a</b> <em>b</em></p>\n");
+}
+
+#[test]
+fn markdown_handler() {
+    let source = "**a** *b*";
+    let mut fmt = MarkdownRenderer::new(Some(source));
+    fmt.registry_mut().add::<Strong>(|node, fmt| {
+        fmt.text_raw("__");
+        fmt.contents(&node.children);
+        fmt.text_raw("__");
+    });
+    fmt.render(&parse(source));
+    assert_eq!(String::from(fmt), "__a__ *b*\n");
+}
+
+#[test]
+fn text_handler() {
+    let mut fmt = TextRenderer::new();
+    fmt.registry_mut().add::<Strong>(|node, fmt| {
+        let text = node.collect_text().to_uppercase();
+        fmt.text(&text);
+    });
+    fmt.render(&parse("**a** *b*"));
+    assert_eq!(String::from(fmt), "A b\n");
+}
@@ -0,0 +1,195 @@
+use markdown_that::parser::block::{BlockRule, BlockState};
+use markdown_that::{MarkdownThat, Node};
+use std::cell::Cell;
+
+fn md() -> MarkdownThat {
+    let mut md = MarkdownThat::new();
+    markdown_that::plugins::cmark::add(&mut md);
+    markdown_that::plugins::html::add(&mut md);
+    markdown_that::plugins::extra::tables::add(&mut md);
+    markdown_that::plugins::extra::typographer::add(&mut md);
+    markdown_that::plugins::sourcepos::add(&mut md);
+    md
+}
+
+fn dump(nodes: &[Node]) -> Vec<String> {
+    let mut result = vec![];
+    for node in nodes {
+        node.walk(|node, depth| {
+            result.push(format!("{depth} {} {:?}", node.name(), node.srcmap));
+        });
+    }
+    result
+}
+
+fn render(nodes: &[Node]) -> String {
+    nodes.iter().map(|node| node.render()).collect()
+}
+
+const SOURCE: &str = "# Title\n\n[ref]: /url\n  'title'\n\nSome *text*\n\
+                      with `code` and [link][ref].\n\n> quote\nlazy\n\n- one\n- two\n\n  nested\n\n\
+                      ```rust\nfn main() {}\n```\n\n    indented\n\n\
+                      | a | b |\n|---|---|\n| c | d |\n\n<div>\nhtml\n</div>\n\n\
+                      Setext\r\n---\r\n\r\n***\n\nend (c) -- text";
+
+#[test]
+fn same_as_parse_with_any_chunk_size() {
+    let md = md();
+    let expected = std::mem::take(&mut md.parse(SOURCE).children);
+
+    for size in 1..=SOURCE.len() {
+        let mut stream = md.stream();
+        let mut blocks = vec![];
+        for chunk in SOURCE.as_bytes().chunks(size) {
+            blocks.extend(stream.push(std::str::from_utf8(chunk).unwrap()));
+        }
+        blocks.extend(stream.finish());
+
+        assert_eq!(render(&blocks), render(&expected), "chunk size {size}");
+        assert_eq!(dump(&blocks), dump(&expected), "chunk size {size}");
+    }
+}
+
+#[test]
+fn blocks_are_emitted_when_complete() {
+    let md = md();
+    let mut stream = md.stream();
+    assert!(stream.push("para").is_empty());
+    assert!(stream.push("graph\n").is_empty());
+    assert!(stream.push("continued\n\n").is_empty());
+
+    let blocks = stream.push("```\n");
+    assert_eq!(
+        render(&blocks),
+        "<p data-sourcepos=\"1:1-2:9\">paragraph\ncontinued</p>\n"
+    );
+
+    // unclosed fence is only finished at the end of input
+    assert!(stream.push("code\n\n# not heading\n").is_empty());
+    assert_eq!(
+        render(&stream.finish()),
+        "<pre><code data-sourcepos=\"4:1-7:13\">code\n\n# not heading\n</code></pre>\n"
+    );
+}
+
+#[test]
+fn references_must_be_defined_before_use() {
+    let md = md();
+    let mut stream = md.stream();
+    let mut blocks = stream.push("[a] [b]\n\n[a]: /a\n\n");
+    blocks.extend(stream.push("[b]: /b\n\n[a] [b]\n"));
+    blocks.extend(stream.finish());
+
+    assert_eq!(
+        blocks[0].render(),
+        "<p data-sourcepos=\"1:1-1:7\">[a] [b]</p>\n"
+    );
+    assert_eq!(
+        blocks[3].render(),
+        "<p data-sourcepos=\"7:1-7:7\"><a data-sourcepos=\"7:1-7:3\" href=\"/a\">a</a> \
+         <a data-sourcepos=\"7:5-7:7\" href=\"/b\">b</a></p>\n"
+    );
+}
+
+// block rule that only counts how many times block parser tried it (in this thread,
+// so tests running in parallel don't affect each other)
+thread_local! {
+    static SCANNED_LINES: Cell<usize> = const { Cell::new(0) };
+}
+
+struct CountLines;
+
+impl BlockRule for CountLines {
+    fn run(_: &mut BlockState) -> Option<(Node, usize)> {
+        SCANNED_LINES.set(SCANNED_LINES.get() + 1);
+        None
+    }
+}
+
+#[test]
+fn long_list_is_not_rescanned() {
+    let mut md = md();
+    md.block.add_rule::<CountLines>().before_all();
+    let mut stream = md.stream();
+
+    const ITEMS: usize = 5000;
+    let mut source = String::new();
+    for idx in 0..ITEMS {
+        let line = format!("- item {idx}\n");
+        assert!(stream.push(&line).is_empty());
+        source.push_str(&line);
+    }
+    // each line is scanned a few times (list and its item), not once per push
+    assert!(SCANNED_LINES.get() < ITEMS * 10);
+
+    let mut blocks = stream.push("\npara\n");
+    assert_eq!(blocks.len(), 1);
+    assert_eq!(blocks[0].children.len(), ITEMS);
+
+    source.push_str("\npara\n");
+    blocks.extend(stream.finish());
+    assert_eq!(render(&blocks), md.parse(&source).render());
+}
+
+#[test]
+fn long_blocks_are_not_rescanned() {
+    let mut md = md();
+    md.block.add_rule::<CountLines>().before_all();
+
+    const LINES: usize = 5000;
+    for (first, line, last) in [
+        ("para\n", "text\n", "\n"),
+        ("> quote\n", "> text\n", "\n"),
+        ("```\n", "code\n", "```\n"),
+    ] {
+        SCANNED_LINES.set(0);
+        let mut stream = md.stream();
+        let mut source = first.to_owned();
+        assert!(stream.push(first).is_empty());
+        for _ in 0..LINES {
+            assert!(stream.push(line).is_empty());
+            source.push_str(line);
+        }
+        assert!(SCANNED_LINES.get() < LINES * 10, "{first:?}");
+
+        // block is emitted as soon as the next one starts
+        source.push_str(last);
+        source.push_str("# next\n");
+        let mut blocks = stream.push(last);
+        blocks.extend(stream.push("# next\n"));
+        assert_eq!(blocks.len(), 1, "{first:?}");
+
+        blocks.extend(stream.finish());
+        assert_eq!(render(&blocks), md.parse(&source).render(), "{first:?}");
+    }
+}
+
+#[test]
+fn footnotes_are_collected_at_the_end() {
+    let mut md = md();
+    markdown_that::plugins::extra::footnotes::add(&mut md);
+    let source = "foo[^1] bar^[inline]\n\n[^1]: note\n\nbaz[^1]\n";
+
+    let mut stream = md.stream();
+    let mut blocks = vec![];
+    for chunk in source.as_bytes().chunks(3) {
+        blocks.extend(stream.push(std::str::from_utf8(chunk).unwrap()));
+    }
+    assert!(blocks.is_empty());
+    blocks.extend(stream.finish());
+
+    let expected = std::mem::take(&mut md.parse(source).children);
+    assert_eq!(render(&blocks), render(&expected));
+    assert_eq!(dump(&blocks), dump(&expected));
+    assert_eq!(render(&blocks).matches("<section").count(), 1);
+}
+
+#[test]
+fn parse_reader() {
+    let md = md();
+    let node = md.parse_reader(SOURCE.as_bytes()).unwrap();
+    assert_eq!(node.render(), md.parse(SOURCE).render());
+
+    let err = md.parse_reader(&b"invalid \xff utf-8"[..]).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+}
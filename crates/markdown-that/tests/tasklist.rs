@@ -0,0 +1,151 @@
+use markdown_that::MarkdownThat;
+use markdown_that::plugins::gfm::tasklist::{self, TaskListItem, TaskListOptions};
+
+fn md(options: Option<TaskListOptions>) -> MarkdownThat {
+    let mut md = MarkdownThat::new();
+    markdown_that::plugins::cmark::add(&mut md);
+    match options {
+        Some(options) => tasklist::add_with(&mut md, options),
+        None => tasklist::add(&mut md),
+    }
+    md
+}
+
+// checked state and marker position of each item
+fn items(md: &MarkdownThat, src: &str) -> Vec<(bool, (usize, usize))> {
+    let mut result = Vec::new();
+    md.parse(src).walk(|node, _| {
+        if let Some(item) = node.cast::<TaskListItem>() {
+            result.push((item.checked, item.marker_srcmap.unwrap().get_byte_offsets()));
+        }
+    });
+    result
+}
+
+#[test]
+fn github_classes() {
+    let html = md(None).render("- [ ] a\n- [x] b\n- c\n\n1. d\n");
+    assert_eq!(
+        html,
+        concat!(
+            "<ul class=\"contains-task-list\">\n",
+            "<li class=\"task-list-item\"><input disabled=\"\" type=\"checkbox\"> a</li>\n",
+            "<li class=\"task-list-item\"><input checked=\"\" disabled=\"\" type=\"checkbox\"> b</li>\n",
+            "<li>c</li>\n",
+            "</ul>\n",
+            "<ol>\n",
+            "<li>d</li>\n",
+            "</ol>\n",
+        )
+    );
+}
+
+#[test]
+fn nested_lists() {
+    let html = md(None).render("1. a\n   - [X] b\n");
+    assert_eq!(
+        html,
+        concat!(
+            "<ol>\n",
+            "<li>a\n",
+            "<ul class=\"contains-task-list\">\n",
+            "<li class=\"task-list-item\"><input checked=\"\" disabled=\"\" type=\"checkbox\"> b</li>\n",
+            "</ul>\n",
+            "</li>\n",
+            "</ol>\n",
+        )
+    );
+}
+
+#[test]
+fn enabled_checkboxes() {
+    let md = md(Some(TaskListOptions {
+        enabled: true,
+        classes: false,
+    }));
+    assert_eq!(
+        md.render("- [x] a\n- [ ] b"),
+        concat!(
+            "<ul>\n",
+            "<li><input checked=\"\" type=\"checkbox\"> a</li>\n",
+            "<li><input type=\"checkbox\"> b</li>\n",
+            "</ul>\n",
+        )
+    );
+    assert_eq!(
+        md.parse("- [x] a").xrender(),
+        "<ul>\n<li><input checked=\"\" type=\"checkbox\" /> a</li>\n</ul>\n",
+    );
+}
+
+#[test]
+fn not_task_items() {
+    let md = md(None);
+    // marker must be followed by whitespace and some content
+    assert!(items(&md, "- [ ]\n- [x]   \n- [x]b\n- [y] c\n- [  ] d\n").is_empty());
+    // only at the start of the item
+    assert!(items(&md, "- a [x] b\n\n[ ] c\n").is_empty());
+}
+
+#[test]
+fn marker_position() {
+    let md = md(None);
+    let src = "- [ ] a\n- [x] b\n  - [X] c\n\n> 1. [\t] d\n>\n>    e\n";
+    assert_eq!(
+        items(&md, src),
+        [
+            (false, (2, 5)),
+            (true, (10, 13)),
+            (true, (20, 23)),
+            (false, (32, 35)),
+        ]
+    );
+}
+
+#[test]
+fn content_sourcemap() {
+    let md = md(None);
+    let src = "- [x]  *foo*\n  bar\n";
+    let ast = md.parse(src);
+    let mut spans = Vec::new();
+    ast.walk(|node, _| {
+        if node.is::<markdown_that::plugins::cmark::inline::emphasis::Em>() {
+            spans.push(node.srcmap.unwrap().get_byte_offsets());
+        }
+    });
+    assert_eq!(spans, [(7, 12)]);
+}
+
+#[test]
+fn toggle() {
+    let md = md(None);
+    let src = "- [ ] a\n- [x] b\n  - [X] c\n";
+    assert_eq!(
+        tasklist::toggle(&md, src, 2).as_deref(),
+        Some("- [x] a\n- [x] b\n  - [X] c\n")
+    );
+    assert_eq!(
+        tasklist::toggle(&md, src, 12).as_deref(),
+        Some("- [ ] a\n- [ ] b\n  - [X] c\n")
+    );
+    assert_eq!(
+        tasklist::toggle(&md, src, 21).as_deref(),
+        Some("- [ ] a\n- [x] b\n  - [ ] c\n")
+    );
+    // outside of the marker
+    assert_eq!(tasklist::toggle(&md, src, 0), None);
+    assert_eq!(tasklist::toggle(&md, src, 5), None);
+    assert_eq!(tasklist::toggle(&md, "[ ] a\n", 1), None);
+
+    // round trip
+    let toggled = tasklist::toggle(&md, src, 3).unwrap();
+    assert_eq!(tasklist::toggle(&md, &toggled, 3).as_deref(), Some(src));
+}
+
+#[test]
+fn markdown_and_text() {
+    let md = md(None);
+    let ast = md.parse("- [X] a\n- [ ] b\n");
+    assert_eq!(ast.render_markdown(), "- [x] a\n- [ ] b\n");
+    assert_eq!(ast.render_text(), "• [x] a\n• [ ] b\n");
+}
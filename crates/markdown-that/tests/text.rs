@@ -0,0 +1,77 @@
+fn run(input: &str, output: &str) {
+    let md = &mut markdown_that::MarkdownThat::new();
+    markdown_that::plugins::cmark::add(md);
+    markdown_that::plugins::html::add(md);
+    markdown_that::plugins::extra::strikethrough::add(md);
+    markdown_that::plugins::extra::tables::add(md);
+    let node = md.parse(input);
+    assert_eq!(node.render_text(), output);
+}
+
+#[test]
+fn inline_markup() {
+    run(
+        "Some *emph*, ~~strike~~ and `code` with \\* &amp; escapes",
+        "Some emph, strike and code with * & escapes\n",
+    );
+}
+
+#[test]
+fn images_and_links() {
+    run(
+        "[link *text*](http://example.com) ![alt **text**](image.png) <http://foo.bar>",
+        "link text alt text http://foo.bar\n",
+    );
+}
+
+#[test]
+fn line_breaks() {
+    run("foo\nbar  \nbaz", "foo\nbar\nbaz\n");
+}
+
+#[test]
+fn blocks() {
+    run(
+        "# Title\n\nparagraph\n\n---\n\n    code\n\n```\nfenced\n```",
+        "Title\n\nparagraph\n\n---\n\ncode\n\nfenced\n",
+    );
+}
+
+#[test]
+fn html_is_dropped() {
+    run("<div>\nfoo\n</div>\n\nbar <b>baz</b>", "bar baz\n");
+}
+
+#[test]
+fn lists() {
+    run(
+        "- one\n- two\n  - nested\n\n3. a\n\n   para\n4. b",
+        "• one\n• two\n  • nested\n\n3. a\n\n   para\n\n4. b\n",
+    );
+}
+
+#[test]
+fn blockquotes() {
+    run(
+        "> quote\n> > nested\n\n> > more",
+        "> quote\n>\n> > nested\n\n> > more\n",
+    );
+}
+
+#[test]
+fn tables() {
+    run("| a | b |\n|---|---|\n| 1 | *2* |", "a\tb\n1\t2\n");
+}
+
+#[test]
+fn tables_with_pipes() {
+    let md = &mut markdown_that::MarkdownThat::new();
+    markdown_that::plugins::cmark::add(md);
+    markdown_that::plugins::extra::tables::add(md);
+    let node = md.parse("| a | b |\n|---|---|\n| 1 | 2 |");
+
+    let mut fmt = markdown_that::parser::renderer::TextRenderer::new();
+    fmt.set_cell_separator(" | ");
+    fmt.render(&node);
+    assert_eq!(String::from(fmt), "a | b\n1 | 2\n");
+}
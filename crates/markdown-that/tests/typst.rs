@@ -0,0 +1,126 @@
+fn run(input: &str, output: &str) {
+    let md = &mut markdown_that::MarkdownThat::new();
+    markdown_that::plugins::cmark::add(md);
+    markdown_that::plugins::html::add(md);
+    markdown_that::plugins::extra::strikethrough::add(md);
+    markdown_that::plugins::extra::tables::add(md);
+    let node = md.parse(input);
+    assert_eq!(node.render_typst(), output);
+}
+
+#[test]
+fn headings() {
+    run(
+        "# Hello *world*\n\n### Deep\n\nSetext\nline\n======",
+        "= Hello _world_\n\n=== Deep\n\n= Setext line\n",
+    );
+}
+
+#[test]
+fn emphasis() {
+    run("*em* and **strong *both***", "_em_ and *strong _both_*\n");
+}
+
+#[test]
+fn emphasis_inside_words() {
+    run(
+        "wo**r**d **a**b a*b*",
+        "wo#strong[r]d #strong[a]b a#emph[b]\n",
+    );
+}
+
+#[test]
+fn strikethrough() {
+    run("foo ~~del~~ bar", "foo #strike[del] bar\n");
+}
+
+#[test]
+fn code() {
+    run(
+        "`code` ``a`b``\n\n```rust\nfn main() {}\n```\n\n    indented\n\n````\n```\n````",
+        "`code` #raw(\"a`b\")\n\n```rust\nfn main() {}\n```\n\n```\nindented\n```\n\n````\n```\n````\n",
+    );
+}
+
+#[test]
+fn lists() {
+    run(
+        "- one\n- two\n  1. a\n  2. b\n\n3. x\n\n   para\n4. y",
+        "- one\n- two\n  1. a\n  2. b\n\n3. x\n\n   para\n\n4. y\n",
+    );
+}
+
+#[test]
+fn blockquotes() {
+    run(
+        "> quote\n>\n> - a\n> - b\n\n***\n\nfoo  \nbar",
+        "#quote(block: true)[\n  quote\n\n  - a\n  - b\n]\n\n#line(length: 100%)\n\nfoo\\\nbar\n",
+    );
+}
+
+#[test]
+fn links_and_images() {
+    run(
+        "[link](http://x.com/\"q\" \"t\") <http://foo.bar> ![alt *x*](i.png) ![](j.png)",
+        "#link(\"http://x.com/%22q%22\")[link] #link(\"http://foo.bar\") #image(\"i.png\", alt: \"alt x\") #image(\"j.png\")\n",
+    );
+}
+
+#[test]
+fn text_after_expression() {
+    run(
+        "[a](u)(b) [a](u).foo [a](u). end",
+        "#link(\"u\")[a];(b) #link(\"u\")[a];.foo #link(\"u\")[a]. end\n",
+    );
+}
+
+#[test]
+fn tables() {
+    run(
+        "| a | b | c |\n|:--|:-:|--:|\n| 1 | *2* | 3 |\n\n| x |\n|---|\n| y |",
+        "#table(\n  columns: 3,\n  align: (left, center, right),\n  table.header([a], [b], [c]),\n  [1], [_2_], [3],\n)\n\n#table(\n  columns: 1,\n  table.header([x]),\n  [y],\n)\n",
+    );
+}
+
+#[test]
+fn escaping() {
+    run(
+        "#1 $5 @ref a\\_b \\[x\\] ~ \\\\ // -- -1 a-b 1.5",
+        "\\#1 \\$5 \\@ref a\\_b \\[x\\] \\~ \\\\ \\// \\-- \\-1 a-b 1.5\n",
+    );
+}
+
+#[test]
+fn escaping_line_start() {
+    run(
+        "\\- not list\n\n\\+ plus\n\n\\= eq\n\n1\\. num\n\n/ term\n\n- 2\\. item",
+        "\\- not list\n\n\\+ plus\n\n\\= eq\n\n1\\. num\n\n\\/ term\n\n- 2\\. item\n",
+    );
+}
+
+#[test]
+fn html_is_dropped() {
+    run("<div>\nfoo\n</div>\n\nbar <b>baz</b>", "bar baz\n");
+}
+
+#[cfg(feature = "syntect")]
+#[test]
+fn syntax_highlighting() {
+    let md = &mut markdown_that::MarkdownThat::new();
+    markdown_that::plugins::cmark::add(md);
+    markdown_that::plugins::extra::syntect::add(md);
+    let node = md.parse("```rust\nlet x = \"<a>\";\n```");
+    assert_eq!(node.render_typst(), "```rust\nlet x = \"<a>\";\n```\n");
+}
+
+#[test]
+fn footnotes() {
+    let md = &mut markdown_that::MarkdownThat::new();
+    markdown_that::plugins::cmark::add(md);
+    markdown_that::plugins::extra::footnotes::add(md);
+    let node = md.parse("foo[^a] bar^[baz] x[^a]\n\n[^a]: one\n\n    two\n");
+    assert_eq!(
+        node.render_typst(),
+        "foo\\[1\\] bar\\[2\\] x\\[1\\]\n\n1. one\n\n   two\n\n2. baz\n"
+    );
+}